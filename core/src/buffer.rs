@@ -1,22 +1,145 @@
 use std::fs;
 use std::io;
+use std::ops::Range;
 use std::path::Path;
 
-use crate::model::Position;
+use crate::model::{LineKind, ParsedLine, Position};
+use crate::parser::title_page;
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+const UTF8_BOM: char = '\u{feff}';
+const SENTENCE_TERMINATORS: [char; 3] = ['.', '!', '?'];
+const SENTENCE_CLOSERS: [char; 4] = ['"', '\'', ')', ']'];
+const UNDO_LIMIT: usize = 512;
+const ABBREVIATIONS: [&str; 12] = [
+    "mr", "mrs", "ms", "dr", "st", "jr", "sr", "vs", "etc", "e.g", "i.e", "no",
+];
+
+#[derive(Clone, Debug, Default)]
 pub struct Document {
     lines: Vec<String>,
+    line_ids: Vec<LineId>,
+    next_line_id: u64,
+    change_events: Option<Vec<ChangeEvent>>,
+    has_bom: bool,
+    history: EditHistory,
+}
+
+// Follows a logical line across edits. Splitting a line keeps the id on the first
+// half, joining keeps the id of the line that survives, and reordering moves ids
+// with their text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LineId(u64);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    Insert,
+    Delete,
+    Replace,
+}
+
+// A single mutation. Insert ranges are in post-edit coordinates; delete and
+// replace ranges cover the affected text before the edit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangeEvent {
+    pub range: Range<Position>,
+    pub kind: ChangeKind,
+}
+
+impl ChangeEvent {
+    // Where a position at or after the change lands once it is applied. Text after
+    // an edit never changes, so only the line and column offsets move.
+    fn shift(&self, position: Position) -> Position {
+        let key = |position: Position| (position.line, position.column);
+        let Range { start, end } = self.range;
+        match self.kind {
+            ChangeKind::Insert if key(position) >= key(start) => {
+                if position.line == start.line {
+                    Position {
+                        line: end.line,
+                        column: end.column + position.column - start.column,
+                    }
+                } else {
+                    Position {
+                        line: position.line + end.line - start.line,
+                        column: position.column,
+                    }
+                }
+            }
+            ChangeKind::Delete if key(position) >= key(end) => {
+                if position.line == end.line {
+                    Position {
+                        line: start.line,
+                        column: start.column + position.column - end.column,
+                    }
+                } else {
+                    Position {
+                        line: position.line - (end.line - start.line),
+                        column: position.column,
+                    }
+                }
+            }
+            ChangeKind::Delete if key(position) > key(start) => start,
+            _ => position,
+        }
+    }
+}
+
+// The text on the other side of one undo step, with the caret before and after
+// the edit. Undoing swaps it with the live text, so the same entry then serves
+// as the redo step.
+#[derive(Clone, Debug)]
+pub struct EditSnapshot {
+    lines: Vec<String>,
+    line_ids: Vec<LineId>,
+    before: Position,
+    after: Position,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UndoGroupKind {
+    Typing,
+    Deleting,
+}
+
+// A run of same-kind edits shares one undo step as long as the caret stays where
+// the previous edit left it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct UndoGroup {
+    kind: UndoGroupKind,
+    cursor: Position,
+}
+
+#[derive(Clone, Debug, Default)]
+struct EditHistory {
+    undo: Vec<EditSnapshot>,
+    redo: Vec<EditSnapshot>,
+    open_group: Option<UndoGroup>,
+}
+
+impl PartialEq for Document {
+    fn eq(&self, other: &Self) -> bool {
+        self.lines == other.lines
+    }
+}
+
+impl Eq for Document {}
+
 impl Document {
     pub fn new() -> Self {
         Self {
             lines: vec![String::new()],
+            line_ids: vec![LineId(0)],
+            next_line_id: 1,
+            change_events: None,
+            has_bom: false,
+            history: EditHistory::default(),
         }
     }
 
     pub fn from_text(text: &str) -> Self {
+        let stripped = text.strip_prefix(UTF8_BOM);
+        let has_bom = stripped.is_some();
+        let text = stripped.unwrap_or(text);
         let mut lines: Vec<String> = text
             .split('\n')
             .map(|line| line.trim_end_matches('\r').to_owned())
@@ -26,7 +149,15 @@ impl Document {
             lines.push(String::new());
         }
 
-        Self { lines }
+        let line_count = lines.len() as u64;
+        Self {
+            lines,
+            line_ids: (0..line_count).map(LineId).collect(),
+            next_line_id: line_count,
+            change_events: None,
+            has_bom,
+            history: EditHistory::default(),
+        }
     }
 
     pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
@@ -34,8 +165,18 @@ impl Document {
         Ok(Self::from_text(&text))
     }
 
+    // The BOM is file framing, not content: it never appears in `lines` or
+    // `to_text`, and is only written back when the loaded file had one.
     pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
-        fs::write(path, self.to_text())
+        if self.has_bom {
+            fs::write(path, format!("{UTF8_BOM}{}", self.to_text()))
+        } else {
+            fs::write(path, self.to_text())
+        }
+    }
+
+    pub fn has_bom(&self) -> bool {
+        self.has_bom
     }
 
     pub fn to_text(&self) -> String {
@@ -58,10 +199,149 @@ impl Document {
         self.line(line).map_or(0, char_count)
     }
 
+    // The line length when the line is blank or all whitespace.
+    pub fn first_non_whitespace_column(&self, line: usize) -> usize {
+        self.line(line).map_or(0, |text| {
+            text.chars().take_while(|ch| ch.is_whitespace()).count()
+        })
+    }
+
     pub fn lines(&self) -> &[String] {
         &self.lines
     }
 
+    // The `Title:` entry of the script's title page, for window titles and exports.
+    pub fn title(&self) -> Option<String> {
+        title_page(&self.lines)?.title().map(str::to_owned)
+    }
+
+    // Pairs each line with its kind from a parse of this document. The parse
+    // must cover every line; a stale one is a line/parse desync bug.
+    pub fn iter_lines_with_kind<'a>(
+        &'a self,
+        parsed: &'a [ParsedLine],
+    ) -> impl Iterator<Item = (usize, &'a str, &'a LineKind)> {
+        debug_assert_eq!(
+            self.lines.len(),
+            parsed.len(),
+            "parse is stale for this document"
+        );
+        self.lines
+            .iter()
+            .zip(parsed)
+            .enumerate()
+            .map(|(index, (line, parsed_line))| (index, line.as_str(), &parsed_line.kind))
+    }
+
+    pub fn observe_changes(&mut self) {
+        self.change_events.get_or_insert_with(Vec::new);
+    }
+
+    pub fn take_change_events(&mut self) -> Vec<ChangeEvent> {
+        self.change_events
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    fn record_change(&mut self, kind: ChangeKind, start: Position, end: Position) {
+        if let Some(events) = self.change_events.as_mut() {
+            events.push(ChangeEvent {
+                range: start..end,
+                kind,
+            });
+        }
+    }
+
+    // A copy of the text, line ids and BOM with no undo history, for baselines and
+    // save copies that should not hold on to every step.
+    pub fn without_history(&self) -> Self {
+        Self {
+            lines: self.lines.clone(),
+            line_ids: self.line_ids.clone(),
+            next_line_id: self.next_line_id,
+            change_events: None,
+            has_bom: self.has_bom,
+            history: EditHistory::default(),
+        }
+    }
+
+    // Captures the text before an edit, with the caret an undo should restore.
+    pub fn edit_snapshot(&self, cursor: Position) -> EditSnapshot {
+        EditSnapshot {
+            lines: self.lines.clone(),
+            line_ids: self.line_ids.clone(),
+            before: cursor,
+            after: cursor,
+        }
+    }
+
+    // Records `snapshot` as an undo step once its edit has left the caret at
+    // `cursor`. An edit in the open group folds into the previous step instead;
+    // newlines and line joins pass no group, so they always start their own.
+    // Returns whether a new step was started.
+    pub fn push_undo(
+        &mut self,
+        snapshot: EditSnapshot,
+        cursor: Position,
+        group: Option<UndoGroupKind>,
+    ) -> bool {
+        let history = &mut self.history;
+        let continues = history
+            .open_group
+            .zip(group)
+            .is_some_and(|(open, kind)| open.kind == kind && open.cursor == snapshot.before);
+        history.redo.clear();
+        let started = match history.undo.last_mut() {
+            Some(step) if continues => {
+                step.after = cursor;
+                false
+            }
+            _ => {
+                if history.undo.len() >= UNDO_LIMIT {
+                    history.undo.remove(0);
+                }
+                history.undo.push(EditSnapshot {
+                    after: cursor,
+                    ..snapshot
+                });
+                true
+            }
+        };
+        history.open_group = group.map(|kind| UndoGroup { kind, cursor });
+        started
+    }
+
+    // How many steps `undo` can still revert.
+    pub fn undo_depth(&self) -> usize {
+        self.history.undo.len()
+    }
+
+    // Reverts the last undo step and returns the caret it was taken at.
+    pub fn undo(&mut self) -> Option<Position> {
+        let mut step = self.history.undo.pop()?;
+        self.swap_history_text(&mut step);
+        let cursor = self.clamp_position(step.before);
+        self.history.redo.push(step);
+        self.history.open_group = None;
+        Some(cursor)
+    }
+
+    // Reapplies the last undone step and returns the caret the edit left.
+    pub fn redo(&mut self) -> Option<Position> {
+        let mut step = self.history.redo.pop()?;
+        self.swap_history_text(&mut step);
+        let cursor = self.clamp_position(step.after);
+        self.history.undo.push(step);
+        self.history.open_group = None;
+        Some(cursor)
+    }
+
+    fn swap_history_text(&mut self, step: &mut EditSnapshot) {
+        std::mem::swap(&mut self.lines, &mut step.lines);
+        std::mem::swap(&mut self.line_ids, &mut step.line_ids);
+    }
+
     pub fn clamp_position(&self, position: Position) -> Position {
         let last_line = self.line_count().saturating_sub(1);
         let line = position.line.min(last_line);
@@ -135,8 +415,56 @@ impl Document {
         }
     }
 
+    // Runs `edit` at every position, the last in the document first so an edit
+    // never moves a position still waiting for its turn. Positions already edited
+    // follow the later edits above them. Returns where each position ended up,
+    // in input order; duplicates are edited once.
+    pub fn edit_at_positions(
+        &mut self,
+        positions: &[Position],
+        mut edit: impl FnMut(&mut Self, Position) -> Position,
+    ) -> Vec<Position> {
+        let key = |position: &Position| (position.line, position.column);
+        let clamped = positions
+            .iter()
+            .map(|position| self.clamp_position(*position))
+            .collect::<Vec<_>>();
+        let mut order = clamped.clone();
+        order.sort_by_key(|position| std::cmp::Reverse(key(position)));
+        order.dedup();
+
+        let mut outer_events = self.change_events.replace(Vec::new());
+        let mut edited: Vec<(Position, Position)> = Vec::with_capacity(order.len());
+        for position in order {
+            let next = edit(self, position);
+            let events = self.change_events.replace(Vec::new()).unwrap_or_default();
+            for (_, result) in &mut edited {
+                *result = events
+                    .iter()
+                    .fold(*result, |result, event| event.shift(result));
+            }
+            if let Some(outer) = outer_events.as_mut() {
+                outer.extend(events);
+            }
+            edited.push((position, next));
+        }
+        self.change_events = outer_events;
+
+        clamped
+            .iter()
+            .map(|position| {
+                edited
+                    .iter()
+                    .find(|(original, _)| original == position)
+                    .map_or(*position, |(_, result)| *result)
+            })
+            .collect()
+    }
+
     pub fn insert_text(&mut self, position: Position, input: &str) -> Position {
-        let mut position = self.clamp_position(position);
+        let start = self.clamp_position(position);
+        let mut position = start;
+        let change_events = self.change_events.take();
 
         for ch in input.chars() {
             position = if ch == '\n' {
@@ -146,19 +474,79 @@ impl Document {
             };
         }
 
+        self.change_events = change_events;
+        if position != start {
+            self.record_change(ChangeKind::Insert, start, position);
+        }
         position
     }
 
+    pub fn insert_text_bulk(&mut self, position: Position, input: &str) -> Position {
+        let start = self.clamp_position(position);
+        if input.is_empty() {
+            return start;
+        }
+
+        let mut pieces = input.split('\n');
+        let first = pieces.next().unwrap_or_default();
+        let inserted = pieces.map(str::to_string).collect::<Vec<_>>();
+
+        let line = &mut self.lines[start.line];
+        let byte_index = char_to_byte_index(line, start.column);
+        let tail = line.split_off(byte_index);
+        line.push_str(first);
+
+        let end = match inserted.last() {
+            Some(last) => Position {
+                line: start.line + inserted.len(),
+                column: last.chars().count(),
+            },
+            None => Position {
+                line: start.line,
+                column: start.column + first.chars().count(),
+            },
+        };
+        let inserted_count = inserted.len();
+        self.lines.splice(start.line + 1..start.line + 1, inserted);
+        self.insert_line_ids(start.line + 1, inserted_count);
+        self.lines[end.line].push_str(&tail);
+
+        self.record_change(ChangeKind::Insert, start, end);
+        end
+    }
+
     pub fn insert_char(&mut self, position: Position, ch: char) -> Position {
         let position = self.clamp_position(position);
         let line = &mut self.lines[position.line];
         let byte_index = char_to_byte_index(line, position.column);
         line.insert(byte_index, ch);
 
-        Position {
+        let next = Position {
             line: position.line,
             column: position.column + 1,
+        };
+        self.record_change(ChangeKind::Insert, position, next);
+        next
+    }
+
+    // Replaces the char under `position`; at the end of a line it inserts instead.
+    pub fn overwrite_char(&mut self, position: Position, ch: char) -> Position {
+        let position = self.clamp_position(position);
+        if position.column >= self.line_len_chars(position.line) {
+            return self.insert_char(position, ch);
         }
+
+        let line = &mut self.lines[position.line];
+        let start = char_to_byte_index(line, position.column);
+        let end = char_to_byte_index(line, position.column + 1);
+        line.replace_range(start..end, ch.encode_utf8(&mut [0; 4]));
+
+        let next = Position {
+            line: position.line,
+            column: position.column + 1,
+        };
+        self.record_change(ChangeKind::Replace, position, next);
+        next
     }
 
     pub fn insert_newline(&mut self, position: Position) -> Position {
@@ -167,11 +555,14 @@ impl Document {
         let byte_index = char_to_byte_index(current, position.column);
         let tail = current.split_off(byte_index);
         self.lines.insert(position.line + 1, tail);
+        self.insert_line_ids(position.line + 1, 1);
 
-        Position {
+        let next = Position {
             line: position.line + 1,
             column: 0,
-        }
+        };
+        self.record_change(ChangeKind::Insert, position, next);
+        next
     }
 
     pub fn backspace(&mut self, position: Position) -> Position {
@@ -183,10 +574,12 @@ impl Document {
             let end = char_to_byte_index(line, position.column);
             line.replace_range(start..end, "");
 
-            return Position {
+            let next = Position {
                 line: position.line,
                 column: position.column - 1,
             };
+            self.record_change(ChangeKind::Delete, next, position);
+            return next;
         }
 
         if position.line == 0 {
@@ -194,14 +587,17 @@ impl Document {
         }
 
         let current = self.lines.remove(position.line);
+        self.line_ids.remove(position.line);
         let previous_line = position.line - 1;
         let previous_len = self.line_len_chars(previous_line);
         self.lines[previous_line].push_str(&current);
 
-        Position {
+        let next = Position {
             line: previous_line,
             column: previous_len,
-        }
+        };
+        self.record_change(ChangeKind::Delete, next, position);
+        next
     }
 
     pub fn delete(&mut self, position: Position) -> Position {
@@ -213,6 +609,14 @@ impl Document {
             let start = char_to_byte_index(line, position.column);
             let end = char_to_byte_index(line, position.column + 1);
             line.replace_range(start..end, "");
+            self.record_change(
+                ChangeKind::Delete,
+                position,
+                Position {
+                    line: position.line,
+                    column: position.column + 1,
+                },
+            );
             return position;
         }
 
@@ -221,7 +625,16 @@ impl Document {
         }
 
         let next_line = self.lines.remove(position.line + 1);
+        self.line_ids.remove(position.line + 1);
         self.lines[position.line].push_str(&next_line);
+        self.record_change(
+            ChangeKind::Delete,
+            position,
+            Position {
+                line: position.line + 1,
+                column: 0,
+            },
+        );
         position
     }
 
@@ -241,6 +654,7 @@ impl Document {
             let start_byte = char_to_byte_index(line, start.column);
             let end_byte = char_to_byte_index(line, end.column);
             line.replace_range(start_byte..end_byte, "");
+            self.record_change(ChangeKind::Delete, start, end);
             return start;
         }
 
@@ -250,8 +664,493 @@ impl Document {
         merged.push_str(&self.lines[end.line][end_suffix_start..]);
         self.lines[start.line] = merged;
         self.lines.drain(start.line.saturating_add(1)..=end.line);
+        self.line_ids.drain(start.line.saturating_add(1)..=end.line);
+        self.record_change(ChangeKind::Delete, start, end);
         start
     }
+
+    pub fn text_in_range(&self, start: Position, end: Position) -> String {
+        let mut start = self.clamp_position(start);
+        let mut end = self.clamp_position(end);
+        if position_after(start, end) {
+            std::mem::swap(&mut start, &mut end);
+        }
+
+        let start_line = &self.lines[start.line];
+        let start_byte = char_to_byte_index(start_line, start.column);
+        if start.line == end.line {
+            let end_byte = char_to_byte_index(start_line, end.column);
+            return start_line[start_byte..end_byte].to_owned();
+        }
+
+        let mut text = start_line[start_byte..].to_owned();
+        for line in &self.lines[start.line + 1..end.line] {
+            text.push('\n');
+            text.push_str(line);
+        }
+        let end_line = &self.lines[end.line];
+        text.push('\n');
+        text.push_str(&end_line[..char_to_byte_index(end_line, end.column)]);
+        text
+    }
+
+    pub fn insert_lines(&mut self, line: usize, lines: &[String]) -> Position {
+        let line = line.min(self.line_count());
+        self.lines.splice(line..line, lines.iter().cloned());
+        self.insert_line_ids(line, lines.len());
+
+        let end = Position {
+            line: line + lines.len(),
+            column: 0,
+        };
+        self.record_change(ChangeKind::Insert, Position { line, column: 0 }, end);
+        Position { line, column: 0 }
+    }
+
+    pub fn join_lines(&mut self, line: usize) -> Position {
+        let line = line.min(self.line_count().saturating_sub(1));
+        if line + 1 >= self.line_count() {
+            return Position {
+                line,
+                column: self.line_len_chars(line),
+            };
+        }
+
+        let next = self.lines.remove(line + 1);
+        self.line_ids.remove(line + 1);
+        let replaced_end = Position {
+            line: line + 1,
+            column: char_count(&next),
+        };
+        let next = next.trim_start();
+        let current = &mut self.lines[line];
+        current.truncate(current.trim_end().len());
+        let column = char_count(current);
+
+        if !current.is_empty() && !next.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(next);
+
+        self.record_change(
+            ChangeKind::Replace,
+            Position { line, column: 0 },
+            replaced_end,
+        );
+        Position { line, column }
+    }
+
+    // The cursor stays on the same line index, moving up when the last line goes.
+    // A lone line is cleared instead so the document never runs out of lines.
+    pub fn delete_line(&mut self, line: usize) -> Position {
+        let line = line.min(self.line_count() - 1);
+        let is_last = line + 1 == self.line_count();
+        let (start, end) = if !is_last {
+            (
+                Position { line, column: 0 },
+                Position {
+                    line: line + 1,
+                    column: 0,
+                },
+            )
+        } else if line > 0 {
+            (
+                Position {
+                    line: line - 1,
+                    column: self.line_len_chars(line - 1),
+                },
+                Position {
+                    line,
+                    column: self.line_len_chars(line),
+                },
+            )
+        } else {
+            (
+                Position::default(),
+                Position {
+                    line,
+                    column: self.line_len_chars(line),
+                },
+            )
+        };
+
+        if self.line_count() == 1 {
+            self.lines[0].clear();
+        } else {
+            self.lines.remove(line);
+            self.line_ids.remove(line);
+        }
+        self.record_change(ChangeKind::Delete, start, end);
+        self.line_start(line)
+    }
+
+    // Returns the caret on the copy at the same column.
+    pub fn duplicate_line(&mut self, position: Position) -> Position {
+        let position = self.clamp_position(position);
+        let copy = self.lines[position.line].clone();
+        self.insert_lines(position.line + 1, &[copy]);
+        Position {
+            line: position.line + 1,
+            column: position.column,
+        }
+    }
+
+    // Both return how many columns the line's text moved; dedent only takes spaces.
+    pub fn indent_line(&mut self, line: usize, width: usize) -> usize {
+        let line = line.min(self.line_count() - 1);
+        self.insert_text(Position { line, column: 0 }, &" ".repeat(width));
+        width
+    }
+
+    pub fn dedent_line(&mut self, line: usize, width: usize) -> usize {
+        let line = line.min(self.line_count() - 1);
+        let removed = self.lines[line]
+            .chars()
+            .take(width)
+            .take_while(|ch| *ch == ' ')
+            .count();
+        if removed > 0 {
+            self.delete_range(
+                Position { line, column: 0 },
+                Position {
+                    line,
+                    column: removed,
+                },
+            );
+        }
+        removed
+    }
+
+    // Returns whether the line had anything to uppercase.
+    pub fn uppercase_line(&mut self, line: usize) -> bool {
+        let line = line.min(self.line_count() - 1);
+        let upper = self.lines[line].to_uppercase();
+        if upper == self.lines[line] {
+            return false;
+        }
+
+        self.record_lines_replaced(line..line + 1);
+        self.lines[line] = upper;
+        true
+    }
+
+    // Lines for which `keep` returns true are left alone. Returns how many lines changed.
+    pub fn trim_trailing_whitespace(&mut self, keep: impl Fn(usize) -> bool) -> usize {
+        let mut trimmed = 0;
+        for line in 0..self.line_count() {
+            let len = self.lines[line].trim_end().len();
+            if len == self.lines[line].len() || keep(line) {
+                continue;
+            }
+
+            let start = Position {
+                line,
+                column: self.lines[line][..len].chars().count(),
+            };
+            let end = Position {
+                line,
+                column: self.line_len_chars(line),
+            };
+            self.record_change(ChangeKind::Delete, start, end);
+            self.lines[line].truncate(len);
+            trimmed += 1;
+        }
+        trimmed
+    }
+
+    pub fn move_sentence_forward(&self, position: Position) -> Position {
+        let position = self.clamp_position(position);
+        self.sentence_starts()
+            .into_iter()
+            .find(|start| position_after(*start, position))
+            .unwrap_or_else(|| self.end_position())
+    }
+
+    pub fn move_sentence_backward(&self, position: Position) -> Position {
+        let position = self.clamp_position(position);
+        self.sentence_starts()
+            .into_iter()
+            .rev()
+            .find(|start| position_after(position, *start))
+            .unwrap_or_default()
+    }
+
+    // Whether whitespace after a sentence terminator sits just before `position` on
+    // its line. Abbreviations and initials do not end a sentence.
+    pub fn ends_sentence_before(&self, position: Position) -> bool {
+        let position = self.clamp_position(position);
+        let chars = self.lines[position.line]
+            .chars()
+            .take(position.column)
+            .collect::<Vec<_>>();
+        let mut run_end = chars.len();
+        while run_end > 0 && chars[run_end - 1].is_whitespace() {
+            run_end -= 1;
+        }
+        if run_end == chars.len() {
+            return false;
+        }
+
+        let mut run_start = run_end;
+        while run_start > 0
+            && (SENTENCE_TERMINATORS.contains(&chars[run_start - 1])
+                || SENTENCE_CLOSERS.contains(&chars[run_start - 1]))
+        {
+            run_start -= 1;
+        }
+        while run_start < run_end && !SENTENCE_TERMINATORS.contains(&chars[run_start]) {
+            run_start += 1;
+        }
+        run_start < run_end && ends_sentence(&chars, run_start, run_end)
+    }
+
+    // A word step skips whitespace, then one run of word characters or of
+    // punctuation. At a line edge it crosses the line break instead.
+    pub fn move_word_left(&self, position: Position) -> Position {
+        let position = self.clamp_position(position);
+        if position.column == 0 {
+            return self.move_left(position);
+        }
+
+        let chars = self.lines[position.line].chars().collect::<Vec<_>>();
+        let mut column = position.column;
+        while column > 0 && chars[column - 1].is_whitespace() {
+            column -= 1;
+        }
+        if let Some(class) = column.checked_sub(1).map(|index| char_class(chars[index])) {
+            while column > 0 && char_class(chars[column - 1]) == class {
+                column -= 1;
+            }
+        }
+        Position {
+            line: position.line,
+            column,
+        }
+    }
+
+    // The run of same-class characters under `position`, or just before it at the
+    // end of a line. A hyphen between word characters joins them, so "twenty-one"
+    // is one word.
+    pub fn word_range_at(&self, position: Position) -> Range<Position> {
+        let position = self.clamp_position(position);
+        let chars = self.lines[position.line].chars().collect::<Vec<_>>();
+        if chars.is_empty() {
+            return position..position;
+        }
+
+        let class_at = |index: usize| {
+            if joins_word(&chars, index) {
+                CharClass::Word
+            } else {
+                char_class(chars[index])
+            }
+        };
+        let index = position.column.min(chars.len() - 1);
+        let class = class_at(index);
+        let mut start = index;
+        while start > 0 && class_at(start - 1) == class {
+            start -= 1;
+        }
+        let mut end = index + 1;
+        while end < chars.len() && class_at(end) == class {
+            end += 1;
+        }
+        Position {
+            line: position.line,
+            column: start,
+        }..Position {
+            line: position.line,
+            column: end,
+        }
+    }
+
+    pub fn move_word_right(&self, position: Position) -> Position {
+        let position = self.clamp_position(position);
+        let chars = self.lines[position.line].chars().collect::<Vec<_>>();
+        if position.column == chars.len() {
+            return self.move_right(position);
+        }
+
+        let mut column = position.column;
+        while column < chars.len() && chars[column].is_whitespace() {
+            column += 1;
+        }
+        if let Some(class) = chars.get(column).copied().map(char_class) {
+            while column < chars.len() && char_class(chars[column]) == class {
+                column += 1;
+            }
+        }
+        Position {
+            line: position.line,
+            column,
+        }
+    }
+
+    // At column 0 this joins with the previous line exactly like `backspace`.
+    pub fn delete_word_left(&mut self, position: Position) -> Position {
+        let position = self.clamp_position(position);
+        let start = self.move_word_left(position);
+        self.delete_range(start, position)
+    }
+
+    // At the end of the final line there is nothing to delete.
+    pub fn delete_word_right(&mut self, position: Position) -> Position {
+        let position = self.clamp_position(position);
+        let end = self.move_word_right(position);
+        self.delete_range(position, end)
+    }
+
+    fn end_position(&self) -> Position {
+        let line = self.line_count() - 1;
+        Position {
+            line,
+            column: self.line_len_chars(line),
+        }
+    }
+
+    // A sentence starts at the first non-space character after a terminator run
+    // that is followed by whitespace, and after any blank line. Line breaks alone
+    // do not end a sentence.
+    fn sentence_starts(&self) -> Vec<Position> {
+        let mut starts = Vec::new();
+        let mut pending = true;
+
+        for (line, text) in self.lines.iter().enumerate() {
+            if text.trim().is_empty() {
+                pending = true;
+                continue;
+            }
+
+            let chars = text.chars().collect::<Vec<_>>();
+            let mut column = 0;
+            while column < chars.len() {
+                let ch = chars[column];
+                if pending && !ch.is_whitespace() {
+                    starts.push(Position { line, column });
+                    pending = false;
+                }
+
+                if !SENTENCE_TERMINATORS.contains(&ch) {
+                    column += 1;
+                    continue;
+                }
+
+                let run_start = column;
+                while column < chars.len()
+                    && (SENTENCE_TERMINATORS.contains(&chars[column])
+                        || SENTENCE_CLOSERS.contains(&chars[column]))
+                {
+                    column += 1;
+                }
+                if chars.get(column).is_none_or(|next| next.is_whitespace()) {
+                    pending = ends_sentence(&chars, run_start, column);
+                }
+            }
+        }
+
+        starts
+    }
+
+    // Line ranges are half-open and clamped to the document; empty ranges are no-ops.
+    pub fn reverse_lines(&mut self, start: usize, end: usize) -> Position {
+        let range = self.clamp_line_range(start, end);
+        self.record_lines_replaced(range.clone());
+        self.lines[range.clone()].reverse();
+        self.line_ids[range.clone()].reverse();
+        self.line_start(range.start)
+    }
+
+    pub fn sort_lines(&mut self, start: usize, end: usize) -> Position {
+        let range = self.clamp_line_range(start, end);
+        self.record_lines_replaced(range.clone());
+        let mut sorted = self.lines[range.clone()]
+            .iter()
+            .cloned()
+            .zip(self.line_ids[range.clone()].iter().copied())
+            .collect::<Vec<_>>();
+        sorted.sort_by(|left, right| left.0.cmp(&right.0));
+        for (offset, (text, id)) in sorted.into_iter().enumerate() {
+            self.lines[range.start + offset] = text;
+            self.line_ids[range.start + offset] = id;
+        }
+        self.line_start(range.start)
+    }
+
+    // Returns the caret on the line's new position so it follows the moved text.
+    pub fn swap_lines(&mut self, a: usize, b: usize) -> Position {
+        let last = self.line_count() - 1;
+        let (a, b) = (a.min(last), b.min(last));
+        if a != b {
+            self.record_lines_replaced(a.min(b)..a.max(b) + 1);
+            self.lines.swap(a, b);
+            self.line_ids.swap(a, b);
+        }
+        Position { line: b, column: 0 }
+    }
+
+    // Both return the line the moved text ends up on; at a boundary it stays put.
+    pub fn move_line_up(&mut self, line: usize) -> usize {
+        let line = line.min(self.line_count() - 1);
+        if line == 0 {
+            return line;
+        }
+        self.swap_lines(line, line - 1).line
+    }
+
+    pub fn move_line_down(&mut self, line: usize) -> usize {
+        let line = line.min(self.line_count() - 1);
+        if line + 1 == self.line_count() {
+            return line;
+        }
+        self.swap_lines(line, line + 1).line
+    }
+
+    pub fn line_id(&self, line: usize) -> Option<LineId> {
+        self.line_ids.get(line).copied()
+    }
+
+    pub fn line_for_id(&self, id: LineId) -> Option<usize> {
+        self.line_ids.iter().position(|candidate| *candidate == id)
+    }
+
+    fn insert_line_ids(&mut self, at: usize, count: usize) {
+        let start = self.next_line_id;
+        self.next_line_id += count as u64;
+        self.line_ids
+            .splice(at..at, (start..self.next_line_id).map(LineId));
+    }
+
+    fn line_start(&self, line: usize) -> Position {
+        Position {
+            line: line.min(self.line_count() - 1),
+            column: 0,
+        }
+    }
+
+    fn clamp_line_range(&self, start: usize, end: usize) -> Range<usize> {
+        let end = end.min(self.line_count());
+        start.min(end)..end
+    }
+
+    fn record_lines_replaced(&mut self, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let last = range.end - 1;
+        let end = Position {
+            line: last,
+            column: self.line_len_chars(last),
+        };
+        self.record_change(
+            ChangeKind::Replace,
+            Position {
+                line: range.start,
+                column: 0,
+            },
+            end,
+        );
+    }
 }
 
 fn char_count(input: &str) -> usize {
@@ -262,6 +1161,66 @@ fn position_after(left: Position, right: Position) -> bool {
     left.line > right.line || (left.line == right.line && left.column > right.column)
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Word,
+    Punctuation,
+    Whitespace,
+}
+
+fn joins_word(chars: &[char], index: usize) -> bool {
+    chars[index] == '-'
+        && index
+            .checked_sub(1)
+            .is_some_and(|before| char_class(chars[before]) == CharClass::Word)
+        && chars
+            .get(index + 1)
+            .is_some_and(|after| char_class(*after) == CharClass::Word)
+}
+
+fn char_class(ch: char) -> CharClass {
+    if ch.is_whitespace() {
+        CharClass::Whitespace
+    } else if ch.is_alphanumeric() || ch == '_' || ch == '\'' {
+        CharClass::Word
+    } else {
+        CharClass::Punctuation
+    }
+}
+
+// `chars[run_start..run_end]` is a terminator run followed by whitespace or end of line.
+fn ends_sentence(chars: &[char], run_start: usize, run_end: usize) -> bool {
+    let run = &chars[run_start..run_end];
+    let next_word_lowercase = chars[run_end..]
+        .iter()
+        .find(|ch| !ch.is_whitespace())
+        .is_some_and(|ch| ch.is_lowercase());
+
+    // "Wait... then" continues the sentence; "Wait... Then" starts a new one.
+    if run.iter().filter(|ch| **ch == '.').count() > 1 {
+        return !next_word_lowercase;
+    }
+
+    if run == ['.'] {
+        let word = chars[..run_start]
+            .iter()
+            .rev()
+            .take_while(|ch| !ch.is_whitespace())
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect::<String>()
+            .to_lowercase();
+        let word = word.trim_start_matches(|ch: char| !ch.is_alphanumeric());
+        let single_initial = word.chars().count() == 1 && word.chars().all(char::is_alphabetic);
+        if ABBREVIATIONS.contains(&word) || single_initial {
+            return false;
+        }
+    }
+
+    true
+}
+
 fn char_to_byte_index(input: &str, column: usize) -> usize {
     if column == 0 {
         return 0;
@@ -278,6 +1237,123 @@ fn char_to_byte_index(input: &str, column: usize) -> usize {
 mod tests {
     use super::*;
 
+    fn type_text(doc: &mut Document, mut cursor: Position, text: &str) -> Position {
+        for ch in text.chars() {
+            let snapshot = doc.edit_snapshot(cursor);
+            let (next, group) = if ch == '\n' {
+                (doc.insert_newline(cursor), None)
+            } else {
+                (doc.insert_char(cursor, ch), Some(UndoGroupKind::Typing))
+            };
+            doc.push_undo(snapshot, next, group);
+            cursor = next;
+        }
+        cursor
+    }
+
+    fn backspace_once(doc: &mut Document, cursor: Position) -> Position {
+        let snapshot = doc.edit_snapshot(cursor);
+        let group = (cursor.column > 0).then_some(UndoGroupKind::Deleting);
+        let next = doc.backspace(cursor);
+        doc.push_undo(snapshot, next, group);
+        next
+    }
+
+    #[test]
+    fn typed_run_undoes_as_one_step_and_redoes_to_its_end() {
+        let mut doc = Document::from_text("INT. ROOM");
+        let start = Position { line: 0, column: 9 };
+        let end = type_text(&mut doc, start, " - DAY");
+        assert_eq!(doc.undo_depth(), 1);
+
+        assert_eq!(doc.undo(), Some(start));
+        assert_eq!(doc.to_text(), "INT. ROOM");
+        assert_eq!(doc.undo(), None);
+
+        assert_eq!(doc.redo(), Some(end));
+        assert_eq!(doc.to_text(), "INT. ROOM - DAY");
+        assert_eq!(doc.redo(), None);
+    }
+
+    #[test]
+    fn newline_and_line_joining_backspace_start_new_undo_steps() {
+        let mut doc = Document::from_text("");
+        let cursor = type_text(&mut doc, Position::default(), "BOB\nHi");
+        assert_eq!(doc.to_text(), "BOB\nHi");
+
+        let cursor = backspace_once(&mut doc, cursor);
+        let cursor = backspace_once(&mut doc, cursor);
+        let joined = backspace_once(&mut doc, cursor);
+        assert_eq!(joined, Position { line: 0, column: 3 });
+        assert_eq!(doc.to_text(), "BOB");
+
+        // The join, then both same-line deletes, then "Hi", the newline and "BOB".
+        let steps = [
+            ("BOB\n", Position { line: 1, column: 0 }),
+            ("BOB\nHi", Position { line: 1, column: 2 }),
+            ("BOB\n", Position { line: 1, column: 0 }),
+            ("BOB", Position { line: 0, column: 3 }),
+            ("", Position::default()),
+        ];
+        for (text, caret) in steps {
+            assert_eq!(doc.undo(), Some(caret));
+            assert_eq!(doc.to_text(), text);
+        }
+        assert_eq!(doc.undo(), None);
+    }
+
+    #[test]
+    fn typing_elsewhere_or_a_new_edit_ends_the_undo_group() {
+        let mut doc = Document::from_text("ab");
+        type_text(&mut doc, Position { line: 0, column: 2 }, "c");
+        // The caret moved back before typing again, so this is a new step.
+        type_text(&mut doc, Position { line: 0, column: 0 }, "x");
+        assert_eq!(doc.to_text(), "xabc");
+
+        assert_eq!(doc.undo(), Some(Position::default()));
+        assert_eq!(doc.to_text(), "abc");
+
+        // A fresh edit after an undo drops the redo step.
+        type_text(&mut doc, Position { line: 0, column: 3 }, "d");
+        assert_eq!(doc.redo(), None);
+        assert_eq!(doc.undo(), Some(Position { line: 0, column: 3 }));
+        assert_eq!(doc.to_text(), "abc");
+    }
+
+    #[test]
+    fn clones_keep_history_and_text_only_copies_drop_it() {
+        let mut doc = Document::from_text("Hello");
+        assert_eq!(doc.undo(), None);
+        type_text(&mut doc, Position { line: 0, column: 5 }, "!");
+
+        let mut copy = doc.clone();
+        assert_eq!(copy.undo(), Some(Position { line: 0, column: 5 }));
+        assert_eq!(copy.to_text(), "Hello");
+
+        let mut text_only = doc.without_history();
+        assert_eq!(text_only, doc);
+        assert_eq!(text_only.undo(), None);
+
+        let mut reloaded = Document::from_text(&doc.to_text());
+        assert_eq!(reloaded.undo(), None);
+        assert_eq!(doc.undo(), Some(Position { line: 0, column: 5 }));
+    }
+
+    #[test]
+    fn undo_restores_line_ids_with_the_text() {
+        let mut doc = Document::from_text("one\ntwo");
+        let ids = doc.line_ids.clone();
+        let snapshot = doc.edit_snapshot(Position::default());
+        doc.insert_newline(Position::default());
+        doc.push_undo(snapshot, Position { line: 1, column: 0 }, None);
+
+        doc.undo();
+        assert_eq!(doc.line_ids, ids);
+        doc.redo();
+        assert_eq!(doc.line_count(), 3);
+        assert_eq!(doc.line_ids.len(), 3);
+    }
+
     #[test]
     fn insert_and_backspace_roundtrip() {
         let mut doc = Document::new();
@@ -337,4 +1413,736 @@ mod tests {
         assert_eq!(doc.line_count(), 1);
         assert_eq!(doc.line(0), Some("abef"));
     }
+
+    #[test]
+    fn delete_range_from_mid_line_to_document_end() {
+        let mut doc = Document::from_text("INT. ROOM\nMARY\nHello there.");
+        let end = Position {
+            line: 2,
+            column: doc.line_len_chars(2),
+        };
+        let cursor = doc.delete_range(Position { line: 1, column: 2 }, end);
+
+        assert_eq!(cursor, Position { line: 1, column: 2 });
+        assert_eq!(doc.lines(), ["INT. ROOM", "MA"]);
+    }
+
+    #[test]
+    fn text_in_range_spans_lines_and_roundtrips_through_insert() {
+        let mut doc = Document::from_text("BOB\nWhere are you?\n\nMARY\nHere.");
+        let start = Position { line: 1, column: 6 };
+        let end = Position { line: 3, column: 2 };
+        let text = doc.text_in_range(end, start);
+
+        assert_eq!(text, "are you?\n\nMA");
+        assert_eq!(
+            doc.text_in_range(start, Position { line: 1, column: 9 }),
+            "are"
+        );
+
+        let cursor = doc.delete_range(start, end);
+        doc.insert_text_bulk(cursor, &text);
+        assert_eq!(doc.lines(), ["BOB", "Where are you?", "", "MARY", "Here."]);
+    }
+
+    #[test]
+    fn delete_word_left_skips_spaces_and_joins_at_line_start() {
+        let mut doc = Document::from_text("BOB\nDon't move...  now");
+        let cursor = doc.delete_word_left(Position {
+            line: 1,
+            column: 18,
+        });
+        assert_eq!(
+            cursor,
+            Position {
+                line: 1,
+                column: 15
+            }
+        );
+        assert_eq!(doc.line(1), Some("Don't move...  "));
+
+        let cursor = doc.delete_word_left(cursor);
+        assert_eq!(doc.line(1), Some("Don't move"));
+        let cursor = doc.delete_word_left(cursor);
+        assert_eq!(doc.line(1), Some("Don't "));
+        let cursor = doc.delete_word_left(cursor);
+        assert_eq!(cursor, Position { line: 1, column: 0 });
+        assert_eq!(doc.line(1), Some(""));
+
+        let cursor = doc.delete_word_left(cursor);
+        assert_eq!(cursor, Position { line: 0, column: 3 });
+        assert_eq!(doc.lines(), ["BOB"]);
+        assert_eq!(
+            doc.delete_word_left(Position::default()),
+            Position::default()
+        );
+    }
+
+    #[test]
+    fn delete_word_right_joins_lines_and_stops_at_document_end() {
+        let mut doc = Document::from_text("Hello,  world\nBye");
+        let cursor = doc.delete_word_right(Position { line: 0, column: 5 });
+        assert_eq!(cursor, Position { line: 0, column: 5 });
+        assert_eq!(doc.line(0), Some("Hello  world"));
+
+        let cursor = doc.delete_word_right(cursor);
+        assert_eq!(doc.line(0), Some("Hello"));
+        doc.delete_word_right(cursor);
+        assert_eq!(doc.lines(), ["HelloBye"]);
+
+        let end = Position { line: 0, column: 8 };
+        assert_eq!(doc.delete_word_right(end), end);
+        assert_eq!(doc.lines(), ["HelloBye"]);
+    }
+
+    #[test]
+    fn delete_line_keeps_index_and_moves_up_from_last_line() {
+        let mut doc = Document::from_text("INT. ROOM\nMARY\nHello.");
+        assert_eq!(doc.delete_line(1), Position { line: 1, column: 0 });
+        assert_eq!(doc.lines(), ["INT. ROOM", "Hello."]);
+
+        assert_eq!(doc.delete_line(1), Position { line: 0, column: 0 });
+        assert_eq!(doc.lines(), ["INT. ROOM"]);
+
+        assert_eq!(doc.delete_line(0), Position::default());
+        assert_eq!(doc.lines(), [""]);
+        assert!(doc.is_empty());
+    }
+
+    #[test]
+    fn duplicate_line_inserts_copy_below_and_keeps_the_column() {
+        let mut doc = Document::from_text("MARY\nHello.\n");
+        assert_eq!(
+            doc.duplicate_line(Position { line: 1, column: 4 }),
+            Position { line: 2, column: 4 }
+        );
+        assert_eq!(doc.lines(), ["MARY", "Hello.", "Hello.", ""]);
+        assert_ne!(doc.line_id(1), doc.line_id(2));
+
+        assert_eq!(
+            doc.duplicate_line(Position { line: 3, column: 0 }),
+            Position { line: 4, column: 0 }
+        );
+        assert_eq!(doc.lines(), ["MARY", "Hello.", "Hello.", "", ""]);
+    }
+
+    #[test]
+    fn move_line_up_and_down_stop_at_document_edges() {
+        let mut doc = Document::from_text("Beat one.\nBeat two.\nBeat three.");
+        assert_eq!(doc.move_line_up(0), 0);
+        assert_eq!(doc.move_line_down(2), 2);
+        assert_eq!(doc.lines(), ["Beat one.", "Beat two.", "Beat three."]);
+
+        assert_eq!(doc.move_line_down(0), 1);
+        assert_eq!(doc.lines(), ["Beat two.", "Beat one.", "Beat three."]);
+        assert_eq!(doc.move_line_up(2), 1);
+        assert_eq!(doc.lines(), ["Beat two.", "Beat three.", "Beat one."]);
+    }
+
+    #[test]
+    fn join_lines_rejoins_wrapped_dialogue() {
+        let mut doc = Document::from_text("BOB\nI was going to   \n   say something.");
+        let cursor = doc.join_lines(1);
+
+        assert_eq!(
+            cursor,
+            Position {
+                line: 1,
+                column: 14
+            }
+        );
+        assert_eq!(doc.line_count(), 2);
+        assert_eq!(doc.line(1), Some("I was going to say something."));
+    }
+
+    #[test]
+    fn join_lines_with_empty_next_line_adds_no_space() {
+        let mut doc = Document::from_text("Some action\n\nMore action");
+        let cursor = doc.join_lines(0);
+
+        assert_eq!(
+            cursor,
+            Position {
+                line: 0,
+                column: 11
+            }
+        );
+        assert_eq!(doc.lines(), ["Some action", "More action"]);
+    }
+
+    #[test]
+    fn join_lines_on_last_line_is_noop() {
+        let mut doc = Document::from_text("INT. ROOM\nSome action");
+        let cursor = doc.join_lines(1);
+
+        assert_eq!(
+            cursor,
+            Position {
+                line: 1,
+                column: 11
+            }
+        );
+        assert_eq!(doc.line_count(), 2);
+        assert_eq!(doc.line(1), Some("Some action"));
+    }
+
+    #[test]
+    fn iter_lines_with_kind_stays_aligned_after_edit() {
+        let mut doc = Document::from_text("INT. ROOM\n\nMARY\nHello.");
+        doc.insert_text(Position { line: 1, column: 0 }, "Rain falls.\n");
+        let parsed = crate::parser::parse_document(&doc);
+
+        let lines = doc.iter_lines_with_kind(&parsed).collect::<Vec<_>>();
+        assert_eq!(lines.len(), doc.line_count());
+        assert_eq!(lines[1], (1, "Rain falls.", &LineKind::Action));
+        assert_eq!(lines[3], (3, "MARY", &LineKind::Character));
+        assert_eq!(lines[4], (4, "Hello.", &LineKind::Dialogue));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "parse is stale")]
+    fn iter_lines_with_kind_rejects_a_stale_parse() {
+        let mut doc = Document::from_text("INT. ROOM\nMARY\nHello.");
+        let parsed = crate::parser::parse_document(&doc);
+        doc.insert_text(Position { line: 0, column: 9 }, "\nRain falls.");
+
+        let _ = doc.iter_lines_with_kind(&parsed).count();
+    }
+
+    #[test]
+    fn insert_text_bulk_matches_per_char_insert() {
+        let pasted = "EXT. PARK - DAY\n\nBirds.\nMARY\nHi.";
+        let mut per_char = Document::from_text("Before after");
+        let mut bulk = per_char.clone();
+
+        let expected = per_char.insert_text(Position { line: 0, column: 7 }, pasted);
+        let cursor = bulk.insert_text_bulk(Position { line: 0, column: 7 }, pasted);
+
+        assert_eq!(cursor, expected);
+        assert_eq!(cursor, Position { line: 4, column: 3 });
+        assert_eq!(bulk, per_char);
+        assert_eq!(bulk.line(4), Some("Hi.after"));
+    }
+
+    #[test]
+    fn insert_text_bulk_handles_large_paste() {
+        let line = "A fairly long line of action that repeats.";
+        let pasted = vec![line; 25_000].join("\n");
+        let mut doc = Document::from_text("start");
+        doc.observe_changes();
+
+        let cursor = doc.insert_text_bulk(Position { line: 0, column: 5 }, &pasted);
+
+        assert_eq!(doc.line_count(), 25_000);
+        assert_eq!(
+            cursor,
+            Position {
+                line: 24_999,
+                column: line.chars().count()
+            }
+        );
+        assert_eq!(doc.take_change_events().len(), 1);
+    }
+
+    #[test]
+    fn insert_lines_inserts_whole_lines_and_appends_at_end() {
+        let mut doc = Document::from_text("INT. A\nAction");
+        let cursor = doc.insert_lines(1, &["One".to_owned(), "Two".to_owned()]);
+
+        assert_eq!(cursor, Position { line: 1, column: 0 });
+        assert_eq!(doc.to_text(), "INT. A\nOne\nTwo\nAction");
+
+        doc.insert_lines(99, &["Tail".to_owned()]);
+        assert_eq!(doc.line(4), Some("Tail"));
+    }
+
+    #[test]
+    fn empty_document_edits_stay_at_origin() {
+        let mut doc = Document::new();
+        let origin = Position::default();
+
+        assert!(doc.is_empty());
+        assert_eq!(doc.clamp_position(Position { line: 4, column: 9 }), origin);
+        assert_eq!(doc.move_left(origin), origin);
+        assert_eq!(doc.move_right(origin), origin);
+        assert_eq!(doc.move_up(origin, 3), origin);
+        assert_eq!(doc.move_down(origin, 3), origin);
+        assert_eq!(doc.backspace(origin), origin);
+        assert_eq!(doc.delete(origin), origin);
+        assert_eq!(
+            doc.delete_range(origin, Position { line: 2, column: 2 }),
+            origin
+        );
+        assert_eq!(doc.join_lines(0), origin);
+        assert!(doc.is_empty());
+        assert_eq!(doc.line_count(), 1);
+    }
+
+    #[test]
+    fn change_events_are_only_recorded_when_observed() {
+        let mut doc = Document::from_text("abc");
+        doc.insert_text(Position { line: 0, column: 3 }, "def");
+
+        assert!(doc.take_change_events().is_empty());
+    }
+
+    #[test]
+    fn change_events_report_insert_and_delete_ranges() {
+        let mut doc = Document::from_text("abc\ndef");
+        doc.observe_changes();
+
+        let cursor = doc.insert_text(Position { line: 0, column: 1 }, "X\nY");
+        doc.backspace(cursor);
+        doc.delete_range(
+            Position { line: 0, column: 0 },
+            Position { line: 2, column: 1 },
+        );
+
+        assert_eq!(
+            doc.take_change_events(),
+            vec![
+                ChangeEvent {
+                    range: Position { line: 0, column: 1 }..Position { line: 1, column: 1 },
+                    kind: ChangeKind::Insert,
+                },
+                ChangeEvent {
+                    range: Position { line: 1, column: 0 }..Position { line: 1, column: 1 },
+                    kind: ChangeKind::Delete,
+                },
+                ChangeEvent {
+                    range: Position { line: 0, column: 0 }..Position { line: 2, column: 1 },
+                    kind: ChangeKind::Delete,
+                },
+            ]
+        );
+        assert!(doc.take_change_events().is_empty());
+    }
+
+    #[test]
+    fn reverse_lines_handles_single_line_and_full_document() {
+        let mut doc = Document::from_text("one\ntwo\nthree");
+
+        assert_eq!(doc.reverse_lines(1, 2), Position { line: 1, column: 0 });
+        assert_eq!(doc.to_text(), "one\ntwo\nthree");
+
+        assert_eq!(doc.reverse_lines(0, 99), Position { line: 0, column: 0 });
+        assert_eq!(doc.to_text(), "three\ntwo\none");
+
+        let mut empty = Document::new();
+        assert_eq!(empty.reverse_lines(3, 1), Position { line: 0, column: 0 });
+        assert_eq!(empty.line_count(), 1);
+    }
+
+    #[test]
+    fn sort_lines_sorts_only_the_clamped_range() {
+        let mut doc = Document::from_text("zeta\ngamma\nalpha\nbeta");
+
+        assert_eq!(doc.sort_lines(1, 3), Position { line: 1, column: 0 });
+        assert_eq!(doc.to_text(), "zeta\nalpha\ngamma\nbeta");
+
+        doc.sort_lines(0, usize::MAX);
+        assert_eq!(doc.to_text(), "alpha\nbeta\ngamma\nzeta");
+
+        let mut single = Document::from_text("only");
+        single.sort_lines(0, 1);
+        assert_eq!(single.to_text(), "only");
+    }
+
+    #[test]
+    fn edit_at_positions_keeps_every_caret_on_its_own_text() {
+        let mut doc = Document::from_text("BOB\nHi BOB.\nBOB");
+        let carets = [
+            Position { line: 1, column: 6 },
+            Position { line: 0, column: 3 },
+            Position { line: 1, column: 6 },
+            Position { line: 2, column: 3 },
+        ];
+
+        let moved = doc.edit_at_positions(&carets, |doc, position| doc.insert_text(position, "BY"));
+        assert_eq!(doc.to_text(), "BOBBY\nHi BOBBY.\nBOBBY");
+        assert_eq!(
+            moved,
+            vec![
+                Position { line: 1, column: 8 },
+                Position { line: 0, column: 5 },
+                Position { line: 1, column: 8 },
+                Position { line: 2, column: 5 },
+            ]
+        );
+
+        let moved =
+            doc.edit_at_positions(&moved[1..], |doc, position| doc.insert_newline(position));
+        assert_eq!(doc.to_text(), "BOBBY\n\nHi BOBBY\n.\nBOBBY\n");
+        assert_eq!(
+            moved,
+            vec![
+                Position { line: 1, column: 0 },
+                Position { line: 3, column: 0 },
+                Position { line: 5, column: 0 },
+            ]
+        );
+
+        let moved = doc.edit_at_positions(&moved, |doc, position| doc.backspace(position));
+        assert_eq!(doc.to_text(), "BOBBY\nHi BOBBY.\nBOBBY");
+        assert_eq!(
+            moved,
+            vec![
+                Position { line: 0, column: 5 },
+                Position { line: 1, column: 8 },
+                Position { line: 2, column: 5 },
+            ]
+        );
+    }
+
+    #[test]
+    fn overwrite_char_replaces_until_the_line_end() {
+        let mut doc = Document::from_text("cät\nx");
+        doc.observe_changes();
+
+        let mut cursor = doc.overwrite_char(Position { line: 0, column: 1 }, 'o');
+        cursor = doc.overwrite_char(cursor, 'w');
+        cursor = doc.overwrite_char(cursor, 's');
+
+        assert_eq!(cursor, Position { line: 0, column: 4 });
+        assert_eq!(doc.to_text(), "cows\nx");
+        assert_eq!(
+            doc.take_change_events()
+                .into_iter()
+                .map(|event| event.kind)
+                .collect::<Vec<_>>(),
+            vec![ChangeKind::Replace, ChangeKind::Replace, ChangeKind::Insert]
+        );
+    }
+
+    #[test]
+    fn swap_lines_clamps_and_records_one_replace() {
+        let mut doc = Document::from_text("first\nsecond\nthird");
+        doc.observe_changes();
+
+        assert_eq!(doc.swap_lines(0, 9), Position { line: 2, column: 0 });
+        assert_eq!(doc.to_text(), "third\nsecond\nfirst");
+        assert_eq!(
+            doc.take_change_events(),
+            vec![ChangeEvent {
+                range: Position { line: 0, column: 0 }..Position { line: 2, column: 5 },
+                kind: ChangeKind::Replace,
+            }]
+        );
+
+        assert_eq!(doc.swap_lines(1, 1), Position { line: 1, column: 0 });
+        assert!(doc.take_change_events().is_empty());
+        assert_eq!(doc.line_count(), 3);
+    }
+
+    #[test]
+    fn ends_sentence_before_needs_a_terminator_and_a_space() {
+        let doc = Document::from_text("He runs. She said \"Go!\" Then Mr. Smith\nWait.");
+        let at = |line, column| Position { line, column };
+
+        assert!(doc.ends_sentence_before(at(0, 9)));
+        assert!(!doc.ends_sentence_before(at(0, 8)));
+        assert!(!doc.ends_sentence_before(at(0, 13)));
+        assert!(doc.ends_sentence_before(at(0, 24)));
+        assert!(!doc.ends_sentence_before(at(0, 33)));
+        assert!(!doc.ends_sentence_before(at(1, 0)));
+    }
+
+    #[test]
+    fn uppercase_line_records_a_replace_only_when_it_changes() {
+        let mut doc = Document::from_text("@mary (v.o.)\nDialogue");
+        doc.observe_changes();
+
+        assert!(doc.uppercase_line(0));
+        assert_eq!(doc.to_text(), "@MARY (V.O.)\nDialogue");
+        assert_eq!(
+            doc.take_change_events(),
+            vec![ChangeEvent {
+                range: Position { line: 0, column: 0 }..Position {
+                    line: 0,
+                    column: 12
+                },
+                kind: ChangeKind::Replace,
+            }]
+        );
+
+        assert!(!doc.uppercase_line(0));
+        assert!(doc.take_change_events().is_empty());
+    }
+
+    // Each line's kind with the `(start, end)` columns of its note spans.
+    fn kinds_and_notes(doc: &Document) -> Vec<(LineKind, Vec<(usize, usize)>)> {
+        crate::parser::parse_document(doc)
+            .into_iter()
+            .map(|line| {
+                let notes = crate::model::note_ranges(&line.raw)
+                    .into_iter()
+                    .map(|range| (range.start, range.end))
+                    .collect();
+                (line.kind, notes)
+            })
+            .collect()
+    }
+
+    // Line joins must leave note and boneyard delimiters byte-for-byte intact so the
+    // span parser still finds the same `[[`/`]]` and `/*`/`*/` pairs after the edit.
+    #[test]
+    fn joining_lines_inside_multi_line_note_keeps_delimiters() {
+        let mut doc = Document::from_text("Action [[first\nsecond]] after");
+
+        let cursor = doc.backspace(Position { line: 1, column: 0 });
+        assert_eq!(
+            cursor,
+            Position {
+                line: 0,
+                column: 14
+            }
+        );
+        assert_eq!(doc.to_text(), "Action [[firstsecond]] after");
+        assert_eq!(kinds_and_notes(&doc), [(LineKind::Action, vec![(6, 22)])]);
+
+        let mut doc = Document::from_text("Action [[first\nsecond]] after");
+        doc.delete(Position {
+            line: 0,
+            column: 14,
+        });
+        assert_eq!(doc.to_text(), "Action [[firstsecond]] after");
+        assert_eq!(kinds_and_notes(&doc), [(LineKind::Action, vec![(6, 22)])]);
+    }
+
+    #[test]
+    fn joining_lines_around_boneyard_block_keeps_delimiters() {
+        let mut doc = Document::from_text("Before\n/*\ncut line\n*/\nAfter");
+
+        let cursor = doc.backspace(Position { line: 1, column: 0 });
+        assert_eq!(cursor, Position { line: 0, column: 6 });
+        doc.delete(Position { line: 1, column: 8 });
+        assert_eq!(doc.to_text(), "Before/*\ncut line*/\nAfter");
+        assert_eq!(
+            kinds_and_notes(&doc),
+            [
+                (LineKind::Action, vec![]),
+                (LineKind::Boneyard, vec![]),
+                (LineKind::Action, vec![]),
+            ]
+        );
+
+        // Backspacing the opener's second char only removes that char.
+        let cursor = doc.backspace(Position { line: 0, column: 8 });
+        assert_eq!(doc.line(0), Some("Before/"));
+        assert_eq!(cursor, Position { line: 0, column: 7 });
+        assert!(
+            kinds_and_notes(&doc)
+                .iter()
+                .all(|(kind, _)| *kind == LineKind::Action)
+        );
+    }
+
+    #[test]
+    fn joining_adjacent_lone_brackets_does_not_drop_text() {
+        let mut doc = Document::from_text("[\n[note]]");
+
+        doc.backspace(Position { line: 1, column: 0 });
+        assert_eq!(doc.to_text(), "[[note]]");
+        assert_eq!(doc.line_count(), 1);
+        assert_eq!(kinds_and_notes(&doc), [(LineKind::Action, vec![(0, 8)])]);
+    }
+
+    #[test]
+    fn bom_is_stripped_on_load_and_restored_on_save() {
+        let path =
+            std::env::temp_dir().join(format!("basscript-bom-{}.fountain", std::process::id()));
+        fs::write(&path, "\u{feff}INT. ROOM - DAY\nAction").expect("write bom file");
+
+        let doc = Document::load(&path).expect("load bom file");
+        assert!(doc.has_bom());
+        assert_eq!(doc.line(0), Some("INT. ROOM - DAY"));
+        assert_eq!(doc.to_text(), "INT. ROOM - DAY\nAction");
+
+        doc.save(&path).expect("save bom file");
+        let saved = fs::read(&path).expect("read saved file");
+        let _ = fs::remove_file(&path);
+        assert_eq!(&saved[..3], b"\xef\xbb\xbf");
+        assert_eq!(&saved[3..], b"INT. ROOM - DAY\nAction");
+    }
+
+    #[test]
+    fn save_without_original_bom_writes_none() {
+        let doc = Document::from_text("Action");
+        assert!(!doc.has_bom());
+
+        let path =
+            std::env::temp_dir().join(format!("basscript-no-bom-{}.fountain", std::process::id()));
+        doc.save(&path).expect("save file");
+        let saved = fs::read(&path).expect("read saved file");
+        let _ = fs::remove_file(&path);
+        assert_eq!(saved, b"Action");
+    }
+
+    #[test]
+    fn sentence_motion_within_multi_sentence_action() {
+        let doc =
+            Document::from_text("He runs. Mr. Smith waits... then sighs! \"Why?\" Nobody knows.");
+        let forward = |column| {
+            doc.move_sentence_forward(Position { line: 0, column })
+                .column
+        };
+        let backward = |column| {
+            doc.move_sentence_backward(Position { line: 0, column })
+                .column
+        };
+
+        assert_eq!(forward(0), 9);
+        assert_eq!(forward(9), 40);
+        assert_eq!(forward(40), 47);
+        assert_eq!(forward(47), 60);
+        assert_eq!(backward(60), 47);
+        assert_eq!(backward(45), 40);
+        assert_eq!(backward(9), 0);
+        assert_eq!(backward(0), 0);
+    }
+
+    #[test]
+    fn sentence_motion_spans_line_breaks_and_paragraphs() {
+        let doc = Document::from_text("The door\nopens. A hand\nreaches in\n\nSilence");
+
+        assert_eq!(
+            doc.move_sentence_forward(Position { line: 0, column: 2 }),
+            Position { line: 1, column: 7 }
+        );
+        assert_eq!(
+            doc.move_sentence_forward(Position { line: 1, column: 7 }),
+            Position { line: 4, column: 0 }
+        );
+        assert_eq!(
+            doc.move_sentence_backward(Position { line: 2, column: 4 }),
+            Position { line: 1, column: 7 }
+        );
+        assert_eq!(
+            doc.move_sentence_backward(Position { line: 1, column: 3 }),
+            Position { line: 0, column: 0 }
+        );
+        assert_eq!(
+            doc.move_sentence_forward(Position { line: 4, column: 0 }),
+            Position { line: 4, column: 7 }
+        );
+    }
+
+    #[test]
+    fn line_ids_follow_lines_across_splits_and_inserts() {
+        let mut doc = Document::from_text("alpha\nbeta");
+        let alpha = doc.line_id(0).expect("alpha id");
+        let beta = doc.line_id(1).expect("beta id");
+
+        doc.insert_newline(Position { line: 0, column: 2 });
+        assert_eq!(doc.line_id(0), Some(alpha));
+        assert_eq!(doc.line_for_id(beta), Some(2));
+        let tail = doc.line_id(1).expect("split tail id");
+        assert!(tail != alpha && tail != beta);
+
+        // Even at column 0 the split leaves the id on the first half.
+        doc.insert_text_bulk(Position { line: 0, column: 0 }, "x\ny\n");
+        assert_eq!(doc.line_for_id(alpha), Some(0));
+        assert_eq!(doc.line_for_id(beta), Some(4));
+        assert_eq!(doc.line_ids.len(), doc.line_count());
+    }
+
+    #[test]
+    fn line_ids_keep_survivor_on_join_and_drop_removed_lines() {
+        let mut doc = Document::from_text("one\ntwo\nthree\nfour");
+        let ids = (0..4)
+            .map(|line| doc.line_id(line).expect("id"))
+            .collect::<Vec<_>>();
+
+        doc.backspace(Position { line: 1, column: 0 });
+        assert_eq!(doc.line_id(0), Some(ids[0]));
+        assert_eq!(doc.line_for_id(ids[1]), None);
+
+        doc.delete(Position { line: 1, column: 5 });
+        assert_eq!(doc.line_id(1), Some(ids[2]));
+        assert_eq!(doc.line_for_id(ids[3]), None);
+
+        doc.insert_lines(2, &["five".to_string(), "six".to_string()]);
+        doc.delete_range(
+            Position { line: 0, column: 1 },
+            Position { line: 2, column: 1 },
+        );
+        assert_eq!(doc.line_id(0), Some(ids[0]));
+        assert_eq!(doc.line_for_id(ids[2]), None);
+        assert_eq!(doc.line_ids.len(), doc.line_count());
+    }
+
+    #[test]
+    fn line_ids_move_with_reordered_lines() {
+        let mut doc = Document::from_text("c\na\nb");
+        let ids = (0..3)
+            .map(|line| doc.line_id(line).expect("id"))
+            .collect::<Vec<_>>();
+
+        doc.sort_lines(0, 3);
+        assert_eq!(doc.line_for_id(ids[1]), Some(0));
+        assert_eq!(doc.line_for_id(ids[0]), Some(2));
+
+        doc.swap_lines(0, 2);
+        assert_eq!(doc.line_for_id(ids[1]), Some(2));
+
+        doc.reverse_lines(0, 3);
+        assert_eq!(doc.line_for_id(ids[1]), Some(0));
+
+        doc.join_lines(0);
+        assert_eq!(doc.line_id(0), Some(ids[1]));
+        assert_eq!(doc.line_ids.len(), doc.line_count());
+    }
+
+    #[test]
+    fn trim_trailing_whitespace_skips_kept_lines() {
+        let mut doc = Document::from_text("BOB  \nHi.  \n\t\nDone");
+        doc.observe_changes();
+
+        assert_eq!(doc.trim_trailing_whitespace(|line| line == 1), 2);
+        assert_eq!(doc.lines(), ["BOB", "Hi.  ", "", "Done"]);
+        assert_eq!(
+            doc.take_change_events()[0],
+            ChangeEvent {
+                range: Position { line: 0, column: 3 }..Position { line: 0, column: 5 },
+                kind: ChangeKind::Delete,
+            }
+        );
+    }
+
+    #[test]
+    fn dedent_removes_at_most_width_leading_spaces() {
+        let mut doc = Document::from_text("Hi.\n      Deep\n \tMixed");
+
+        assert_eq!(doc.indent_line(0, 4), 4);
+        assert_eq!(doc.dedent_line(1, 4), 4);
+        assert_eq!(doc.dedent_line(2, 4), 1);
+        assert_eq!(doc.dedent_line(2, 4), 0);
+        assert_eq!(doc.lines(), ["    Hi.", "  Deep", "\tMixed"]);
+    }
+
+    #[test]
+    fn first_non_whitespace_column_counts_leading_whitespace() {
+        let doc = Document::from_text("  \tBOB\nHi.\n   ");
+
+        assert_eq!(doc.first_non_whitespace_column(0), 3);
+        assert_eq!(doc.first_non_whitespace_column(1), 0);
+        assert_eq!(doc.first_non_whitespace_column(2), 3);
+        assert_eq!(doc.first_non_whitespace_column(9), 0);
+    }
+
+    #[test]
+    fn word_range_at_keeps_hyphenated_words_together() {
+        let doc = Document::from_text("A twenty-one -- gun salute.");
+        let columns = |column: usize| {
+            let range = doc.word_range_at(Position { line: 0, column });
+            range.start.column..range.end.column
+        };
+
+        assert_eq!(columns(5), 2..12);
+        assert_eq!(columns(10), 2..12);
+        assert_eq!(columns(13), 13..15);
+        assert_eq!(columns(27), 26..27);
+        assert_eq!(columns(1), 1..2);
+    }
 }
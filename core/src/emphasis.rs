@@ -0,0 +1,213 @@
+const ITALIC_MARKER: char = '*';
+const UNDERLINE_MARKER: char = '_';
+const ESCAPE: char = '\\';
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Emphasis {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+impl Emphasis {
+    pub fn is_plain(self) -> bool {
+        self == Self::default()
+    }
+}
+
+// A stretch of visible text sharing one emphasis. `start_column` is the char
+// column in the raw line where the stretch begins.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmphasisRun {
+    pub start_column: usize,
+    pub text: String,
+    pub emphasis: Emphasis,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Delimiter {
+    Italic,
+    Bold,
+    BoldItalic,
+    Underline,
+}
+
+impl Delimiter {
+    fn len(self) -> usize {
+        match self {
+            Self::Italic | Self::Underline => 1,
+            Self::Bold => 2,
+            Self::BoldItalic => 3,
+        }
+    }
+
+    fn apply(self, emphasis: &mut Emphasis) {
+        match self {
+            Self::Italic => emphasis.italic = true,
+            Self::Bold => emphasis.bold = true,
+            Self::BoldItalic => {
+                emphasis.bold = true;
+                emphasis.italic = true;
+            }
+            Self::Underline => emphasis.underline = true,
+        }
+    }
+}
+
+fn delimiter_at(chars: &[char], column: usize) -> Option<Delimiter> {
+    match chars[column] {
+        UNDERLINE_MARKER => Some(Delimiter::Underline),
+        ITALIC_MARKER => {
+            let stars = chars[column..]
+                .iter()
+                .take_while(|ch| **ch == ITALIC_MARKER)
+                .count();
+            Some(match stars {
+                1 => Delimiter::Italic,
+                2 => Delimiter::Bold,
+                _ => Delimiter::BoldItalic,
+            })
+        }
+        _ => None,
+    }
+}
+
+// Per raw column: the emphasis of a visible char, or None for a marker that the
+// styled rendering hides. Emphasis never spans lines. An opener must touch the
+// text after it and a closer the text before it; unmatched markers and markers
+// escaped with a backslash stay literal.
+pub fn emphasis_columns(raw: &str) -> Vec<Option<Emphasis>> {
+    let chars = raw.chars().collect::<Vec<_>>();
+    let mut hidden = vec![false; chars.len()];
+    let mut styles = vec![Emphasis::default(); chars.len()];
+    let mut open: Vec<(Delimiter, usize)> = Vec::new();
+    let mut column = 0;
+
+    while column < chars.len() {
+        if chars[column] == ESCAPE
+            && chars
+                .get(column + 1)
+                .is_some_and(|next| matches!(*next, ITALIC_MARKER | UNDERLINE_MARKER))
+        {
+            hidden[column] = true;
+            column += 2;
+            continue;
+        }
+
+        let Some(delimiter) = delimiter_at(&chars, column) else {
+            column += 1;
+            continue;
+        };
+        let end = column + delimiter.len();
+        let closes_text = column > 0 && !chars[column - 1].is_whitespace();
+        if closes_text
+            && let Some(index) = open.iter().rposition(|(opener, _)| *opener == delimiter)
+        {
+            let start = open[index].1;
+            open.truncate(index);
+            hidden[start..start + delimiter.len()].fill(true);
+            hidden[column..end].fill(true);
+            for style in &mut styles[start + delimiter.len()..column] {
+                delimiter.apply(style);
+            }
+        } else if chars.get(end).is_some_and(|next| !next.is_whitespace()) {
+            open.push((delimiter, column));
+        }
+        column = end;
+    }
+
+    styles
+        .into_iter()
+        .zip(hidden)
+        .map(|(style, hidden)| (!hidden).then_some(style))
+        .collect()
+}
+
+// The visible text of a line split wherever its emphasis changes or a hidden
+// marker sits between two stretches.
+pub fn emphasis_runs(raw: &str) -> Vec<EmphasisRun> {
+    let mut runs: Vec<EmphasisRun> = Vec::new();
+    let mut previous_visible = false;
+
+    for (column, (ch, style)) in raw.chars().zip(emphasis_columns(raw)).enumerate() {
+        let Some(emphasis) = style else {
+            previous_visible = false;
+            continue;
+        };
+        match runs.last_mut() {
+            Some(run) if previous_visible && run.emphasis == emphasis => run.text.push(ch),
+            _ => runs.push(EmphasisRun {
+                start_column: column,
+                text: ch.to_string(),
+                emphasis,
+            }),
+        }
+        previous_visible = true;
+    }
+
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styled(bold: bool, italic: bool, underline: bool) -> Emphasis {
+        Emphasis {
+            bold,
+            italic,
+            underline,
+        }
+    }
+
+    fn summary(raw: &str) -> Vec<(usize, String, Emphasis)> {
+        emphasis_runs(raw)
+            .into_iter()
+            .map(|run| (run.start_column, run.text, run.emphasis))
+            .collect()
+    }
+
+    #[test]
+    fn splits_bold_italic_and_underline_runs() {
+        assert_eq!(
+            summary("A *soft* and **loud** _line_."),
+            vec![
+                (0, "A ".to_owned(), Emphasis::default()),
+                (3, "soft".to_owned(), styled(false, true, false)),
+                (8, " and ".to_owned(), Emphasis::default()),
+                (15, "loud".to_owned(), styled(true, false, false)),
+                (21, " ".to_owned(), Emphasis::default()),
+                (23, "line".to_owned(), styled(false, false, true)),
+                (28, ".".to_owned(), Emphasis::default()),
+            ]
+        );
+        assert_eq!(
+            summary("***both*** _**mixed**_"),
+            vec![
+                (3, "both".to_owned(), styled(true, true, false)),
+                (10, " ".to_owned(), Emphasis::default()),
+                (14, "mixed".to_owned(), styled(true, false, true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn leaves_unmatched_spaced_and_escaped_markers_literal() {
+        assert_eq!(
+            summary("2 * 3 = 6 and a *dangling marker"),
+            vec![(
+                0,
+                "2 * 3 = 6 and a *dangling marker".to_owned(),
+                Emphasis::default()
+            )]
+        );
+        assert_eq!(
+            summary(r"\*not italic\*"),
+            vec![
+                (1, "*not italic".to_owned(), Emphasis::default()),
+                (13, "*".to_owned(), Emphasis::default()),
+            ]
+        );
+        assert!(emphasis_columns("plain").iter().all(Option::is_some));
+    }
+}
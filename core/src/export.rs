@@ -0,0 +1,567 @@
+use crate::emphasis::emphasis_columns;
+use crate::model::{
+    DualDialogue, LineKind, NOTE_CLOSE, NOTE_OPEN, ParseOptions, ParsedLine, SYNOPSIS_MARKER,
+};
+use crate::parser::{DUAL_DIALOGUE_MARKER, pin_kinds};
+use crate::wrap::wrap_paragraph;
+
+const BEFORE_FIRST_SCENE: &str = "(Before first scene)";
+const DEFAULT_DIALOGUE_WIDTH: usize = 35;
+
+// How the plain-text export lays out speeches: dialogue is hard-wrapped to
+// `dialogue_width` columns under its indent, and overlong words are only broken
+// with a hyphen when `hyphenate_dialogue` is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlainTextOptions {
+    pub dialogue_width: usize,
+    pub hyphenate_dialogue: bool,
+}
+
+impl Default for PlainTextOptions {
+    fn default() -> Self {
+        Self {
+            dialogue_width: DEFAULT_DIALOGUE_WIDTH,
+            hyphenate_dialogue: false,
+        }
+    }
+}
+
+// The colors `export_html` paints with, as CSS values. The editor fills it from
+// its theme and element colors so the page looks like the processed pane: the
+// script on `paper` over `background`. Kinds missing from `line_colors` use
+// `text`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HtmlPalette {
+    pub background: String,
+    pub paper: String,
+    pub text: String,
+    pub line_colors: Vec<(LineKind, String)>,
+}
+
+impl HtmlPalette {
+    fn line_color(&self, kind: &LineKind) -> &str {
+        self.line_colors
+            .iter()
+            .find(|(line_kind, _)| line_kind == kind)
+            .map_or(&self.text, |(_, color)| color)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum RevisionItem {
+    Note { line: usize, text: String },
+    Synopsis { line: usize, text: String },
+}
+
+// Plain-text list of every `[[note]]` and `= synopsis`, grouped under the scene
+// heading that contains it. Notes may span several lines; they are reported on
+// the line where they open. Groups are titled with the processed heading, so
+// notes and scene numbers on the heading line don't leak into the title.
+pub fn export_revision_notes(parsed: &[ParsedLine], title: &str) -> String {
+    let mut groups: Vec<(String, Vec<RevisionItem>)> = Vec::new();
+    let mut heading = BEFORE_FIRST_SCENE.to_string();
+    let mut open_note: Option<(usize, String)> = None;
+
+    for (index, line) in parsed.iter().enumerate() {
+        if line.kind == LineKind::SceneHeading && open_note.is_none() {
+            heading = line.processed_text().trim().to_string();
+        }
+
+        let mut items = Vec::new();
+        if open_note.is_none()
+            && let Some(text) = synopsis_text(&line.raw)
+        {
+            items.push(RevisionItem::Synopsis { line: index, text });
+        } else {
+            collect_notes(&line.raw, index, &mut open_note, &mut items);
+        }
+
+        if items.is_empty() {
+            continue;
+        }
+        match groups.last_mut() {
+            Some((current, group)) if *current == heading => group.extend(items),
+            _ => groups.push((heading.clone(), items)),
+        }
+    }
+
+    let mut output = format!("{title} - Revision notes\n");
+    if groups.is_empty() {
+        output.push_str("\nNo notes or synopses.\n");
+        return output;
+    }
+
+    for (heading, items) in groups {
+        output.push('\n');
+        output.push_str(&heading);
+        output.push('\n');
+        for item in items {
+            let entry = match item {
+                RevisionItem::Note { line, text } => format!("  Line {}: {text}\n", line + 1),
+                RevisionItem::Synopsis { line, text } => {
+                    format!("  Line {} synopsis: {text}\n", line + 1)
+                }
+            };
+            output.push_str(&entry);
+        }
+    }
+    output
+}
+
+// The whole script as the processed pane shows it: markers stripped, notes
+// dropped and casing applied. Layout indents are dropped so the file reparses
+// the same way, and centered text keeps its `>` `<` pair for that reason too.
+// Title page lines stay as written. Boneyard lines are left out entirely.
+// A line whose processed text would reparse as another kind gets its forcing
+// marker back, and paired cues keep their `^`.
+pub fn export_processed_fountain(parsed: &[ParsedLine]) -> String {
+    let lines = parsed
+        .iter()
+        .filter(|line| line.kind != LineKind::Boneyard)
+        .map(|line| (&line.kind, processed_fountain_line(line)))
+        .collect();
+    pin_kinds(lines, &ParseOptions::default()).join("\n")
+}
+
+fn processed_fountain_line(line: &ParsedLine) -> String {
+    if line.kind == LineKind::TitlePage {
+        return line.raw.clone();
+    }
+    let text = line.processed_text();
+    let text = text.trim();
+    match (&line.kind, line.dual_dialogue) {
+        (LineKind::Centered, _) => format!(">{text}<"),
+        (LineKind::Character, Some(DualDialogue::Right)) => {
+            format!("{text} {DUAL_DIALOGUE_MARKER}")
+        }
+        _ => text.to_string(),
+    }
+}
+
+// The script laid out like a printed page in plain text: processed text with its
+// indents, emphasis markers hidden and blank lines kept. Sections, synopses and
+// boneyard lines don't print; page breaks become blank lines. Dialogue is
+// wrapped to the speech column as laid out by `options`.
+pub fn export_plain_text(parsed: &[ParsedLine], options: &PlainTextOptions) -> String {
+    let mut lines = Vec::with_capacity(parsed.len());
+    for line in parsed {
+        let text = match line.kind {
+            LineKind::Boneyard | LineKind::Section | LineKind::Synopsis => continue,
+            LineKind::PageBreak => String::new(),
+            _ => {
+                let text = line.processed_text();
+                text.chars()
+                    .zip(emphasis_columns(&text))
+                    .filter_map(|(ch, column)| column.map(|_| ch))
+                    .collect::<String>()
+            }
+        };
+        if line.kind == LineKind::Dialogue && !text.trim().is_empty() {
+            let indent = " ".repeat(line.indent_width());
+            let wrapped = wrap_paragraph(&text, options.dialogue_width, options.hyphenate_dialogue);
+            lines.extend(wrapped.into_iter().map(|row| format!("{indent}{row}")));
+            continue;
+        }
+        lines.push(text.trim_end().to_string());
+    }
+    let mut output = lines.join("\n");
+    output.push('\n');
+    output
+}
+
+// A standalone read-only page. Every line becomes a `<div>` named after its kind,
+// with inline styles for its `palette` color, the editor's font variants and
+// `indent_width` columns; empty lines stay as blank rows.
+pub fn export_html(parsed: &[ParsedLine], title: &str, palette: &HtmlPalette) -> String {
+    let mut output = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n\
+         <body style=\"font-family: 'Courier Prime', 'Courier New', monospace; \
+         white-space: pre-wrap; margin: 0; padding: 2em 0; background: {};\">\n\
+         <main style=\"max-width: 62ch; margin: 0 auto; padding: 2em 4ch; background: {};\">\n",
+        escape_html(title),
+        palette.background,
+        palette.paper
+    );
+    for line in parsed.iter().filter(|line| line.kind != LineKind::Boneyard) {
+        if line.kind == LineKind::PageBreak {
+            output.push_str("<hr class=\"page-break\" style=\"break-after: page;\">\n");
+            continue;
+        }
+        let (class, font) = html_line_style(&line.kind);
+        let text = line.processed_text();
+        let text = text.trim();
+        if text.is_empty() {
+            output.push_str("<div class=\"empty\">&nbsp;</div>\n");
+            continue;
+        }
+
+        let mut style = format!("color: {};", palette.line_color(&line.kind));
+        if matches!(line.kind, LineKind::Centered | LineKind::TitlePage) {
+            style.push_str(" text-align: center;");
+        } else if line.indent_width() > 0 {
+            style.push_str(&format!(" margin-left: {}ch;", line.indent_width()));
+        }
+        if !font.is_empty() {
+            style.push(' ');
+            style.push_str(font);
+        }
+        output.push_str(&format!(
+            "<div class=\"{class}\" style=\"{style}\">{}</div>\n",
+            escape_html(text)
+        ));
+    }
+    output.push_str("</main>\n</body>\n</html>\n");
+    output
+}
+
+// Same font variants as the editor's `fountain_line_style` and
+// `markdown_line_style`.
+fn html_line_style(kind: &LineKind) -> (&'static str, &'static str) {
+    const BOLD: &str = "font-weight: bold;";
+    const ITALIC: &str = "font-style: italic;";
+    match kind {
+        LineKind::Empty => ("empty", ""),
+        LineKind::SceneHeading => ("scene-heading", BOLD),
+        LineKind::Action => ("action", ""),
+        LineKind::Character => ("character", BOLD),
+        LineKind::Dialogue => ("dialogue", ""),
+        LineKind::Parenthetical => ("parenthetical", ITALIC),
+        LineKind::Transition => ("transition", "font-weight: bold; font-style: italic;"),
+        LineKind::Centered => ("centered", ""),
+        LineKind::Lyric => ("lyric", ITALIC),
+        LineKind::TitlePage => ("title-page", ""),
+        LineKind::PageBreak => ("page-break", ""),
+        LineKind::Boneyard => ("boneyard", ""),
+        LineKind::Section => ("section", BOLD),
+        LineKind::Synopsis => ("synopsis", ITALIC),
+        LineKind::MarkdownHeading => ("markdown-heading", BOLD),
+        LineKind::MarkdownListItem => ("markdown-list-item", ""),
+        LineKind::MarkdownQuote => ("markdown-quote", ITALIC),
+        LineKind::MarkdownCodeFence => ("markdown-code-fence", BOLD),
+        LineKind::MarkdownCode => ("markdown-code", ""),
+        LineKind::MarkdownRule => ("markdown-rule", BOLD),
+        LineKind::MarkdownParagraph => ("markdown-paragraph", ""),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+// `===` and longer runs are page breaks, not synopses.
+fn synopsis_text(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    let text = trimmed.strip_prefix(SYNOPSIS_MARKER)?;
+    if text.starts_with(SYNOPSIS_MARKER) {
+        return None;
+    }
+    Some(text.trim().to_string())
+}
+
+fn collect_notes(
+    raw: &str,
+    index: usize,
+    open_note: &mut Option<(usize, String)>,
+    items: &mut Vec<RevisionItem>,
+) {
+    let mut rest = raw;
+    loop {
+        if let Some((start_line, text)) = open_note.as_mut() {
+            let Some(end) = rest.find(NOTE_CLOSE) else {
+                push_note_text(text, rest);
+                return;
+            };
+            push_note_text(text, &rest[..end]);
+            items.push(RevisionItem::Note {
+                line: *start_line,
+                text: std::mem::take(text),
+            });
+            *open_note = None;
+            rest = &rest[end + NOTE_CLOSE.len()..];
+        }
+
+        let Some(start) = rest.find(NOTE_OPEN) else {
+            return;
+        };
+        *open_note = Some((index, String::new()));
+        rest = &rest[start + NOTE_OPEN.len()..];
+    }
+}
+
+fn push_note_text(text: &mut String, fragment: &str) {
+    let fragment = fragment.trim();
+    if fragment.is_empty() {
+        return;
+    }
+    if !text.is_empty() {
+        text.push(' ');
+    }
+    text.push_str(fragment);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::Document;
+    use crate::parser::parse_document;
+
+    #[test]
+    fn notes_are_grouped_under_their_scene_heading() {
+        let document = Document::from_text(
+            "= Setup the heist.\n\nINT. BANK - DAY\n\nThe vault opens. [[Too quiet?]]\n\n\
+             BOB\nNow. [[punch up\nthis line]]\n\n===\n\nEXT. ROOF - NIGHT\n\n\
+             = The escape.\nWind howls. [[check weather]] [[and props]]",
+        );
+
+        assert_eq!(
+            export_revision_notes(&parse_document(&document), "Heist"),
+            "Heist - Revision notes\n\
+             \n\
+             (Before first scene)\n  Line 1 synopsis: Setup the heist.\n\
+             \n\
+             INT. BANK - DAY\n  Line 5: Too quiet?\n  Line 8: punch up this line\n\
+             \n\
+             EXT. ROOF - NIGHT\n  Line 15 synopsis: The escape.\n  \
+             Line 16: check weather\n  Line 16: and props\n"
+        );
+    }
+
+    #[test]
+    fn notes_on_the_heading_line_are_kept_and_left_out_of_the_title() {
+        let document =
+            Document::from_text("INT. BANK - DAY #12# [[move to night?]]\n\nThe vault opens.");
+
+        assert_eq!(
+            export_revision_notes(&parse_document(&document), "Heist"),
+            "Heist - Revision notes\n\nINT. BANK - DAY\n  Line 1: move to night?\n"
+        );
+    }
+
+    #[test]
+    fn export_without_notes_says_so() {
+        let document = Document::from_text("INT. BANK - DAY\n\nNothing to flag.");
+
+        assert_eq!(
+            export_revision_notes(&parse_document(&document), "Heist"),
+            "Heist - Revision notes\n\nNo notes or synopses.\n"
+        );
+    }
+
+    #[test]
+    fn processed_export_round_trips_through_a_document() {
+        let document = Document::from_text(
+            ".int. bank - day\n\n@mcTavish\nGo. [[too short?]]\n~Sing it.\n\n\
+             /* cut\nthis */\n\n>THE END<",
+        );
+        let parsed = parse_document(&document);
+        let exported = export_processed_fountain(&parsed);
+
+        assert_eq!(
+            exported,
+            "INT. BANK - DAY\n\nMCTAVISH\nGo.\n~Sing it.\n\n\n>THE END<"
+        );
+        assert_eq!(
+            kinds(&parse_document(&Document::from_text(&exported))),
+            kinds(&parsed)
+        );
+    }
+
+    #[test]
+    fn processed_export_keeps_markers_the_heuristic_needs() {
+        let document = Document::from_text(
+            "!SHOTS RING OUT\nThe crowd scatters.\n\n.flashback\n\n@mcdonalds\nTwo burgers.\n\n\
+             BOB\nHi.\n\nSTEEL ^\nHello.\n\n>fade out\n\n!BRICK\nwalks in.",
+        );
+        let parsed = parse_document(&document);
+        let exported = export_processed_fountain(&parsed);
+        let reparsed = parse_document(&Document::from_text(&exported));
+
+        assert_eq!(kinds(&reparsed), kinds(&parsed));
+        assert_eq!(
+            reparsed
+                .iter()
+                .map(|line| line.dual_dialogue)
+                .collect::<Vec<_>>(),
+            parsed
+                .iter()
+                .map(|line| line.dual_dialogue)
+                .collect::<Vec<_>>()
+        );
+        assert!(exported.contains("!SHOTS RING OUT\n"));
+        assert!(exported.contains("STEEL ^\n"));
+        assert!(!exported.contains("!The crowd"));
+    }
+
+    #[test]
+    fn processed_export_keeps_an_all_caps_dialogue_line_out_of_the_cue() {
+        let parsed = parse_document(&Document::from_text("BOB\n!BOOM"));
+        let exported = export_processed_fountain(&parsed);
+
+        assert_eq!(exported, "BOB\n!BOOM");
+        assert_eq!(
+            kinds(&parse_document(&Document::from_text(&exported))),
+            [LineKind::Character, LineKind::Dialogue]
+        );
+    }
+
+    #[test]
+    fn processed_export_reparses_to_the_same_kinds_for_every_short_script() {
+        let pieces = [
+            "",
+            "BOB",
+            "!BOOM",
+            "Hi there.",
+            "(quietly)",
+            "INT. HOUSE - DAY",
+            ".flashback",
+            "CUT TO:",
+            ">fade out",
+            ">THE END<",
+            "@mcclane",
+            "STEEL ^",
+            "~la la",
+            "!SHOTS RING OUT",
+            "# Act one",
+            "= She leaves.",
+            "===",
+            "Go. [[too short?]]",
+        ];
+
+        for a in pieces {
+            for b in pieces {
+                for c in pieces {
+                    for d in ["", "BOB", "!BOOM", "She runs."] {
+                        let text = [a, b, c, d].join("\n");
+                        let parsed = parse_document(&Document::from_text(&text));
+                        let exported = export_processed_fountain(&parsed);
+                        let reparsed = parse_document(&Document::from_text(&exported));
+                        assert_eq!(
+                            kinds(&reparsed),
+                            kinds(&parsed),
+                            "{text:?} exported as {exported:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn processed_export_pins_a_long_script_in_one_walk() {
+        let scene =
+            "INT. HOUSE - DAY\n\n!BOOM\n\nBOB\n!STOP RIGHT THERE\nPlease.\n\n.flashback\n\n";
+        let text = scene.repeat(2_000);
+        let parsed = parse_document(&Document::from_text(&text));
+        let exported = export_processed_fountain(&parsed);
+        let reparsed = parse_document(&Document::from_text(&exported));
+
+        assert_eq!(kinds(&reparsed), kinds(&parsed));
+        assert_eq!(exported.matches("\n!BOOM\n").count(), 2_000);
+        assert_eq!(exported.matches("\n!STOP RIGHT THERE\n").count(), 2_000);
+        assert_eq!(exported.matches("\n.FLASHBACK\n").count(), 2_000);
+    }
+
+    fn kinds(parsed: &[ParsedLine]) -> Vec<LineKind> {
+        parsed
+            .iter()
+            .map(|line| line.kind.clone())
+            .filter(|kind| *kind != LineKind::Boneyard)
+            .collect()
+    }
+
+    #[test]
+    fn plain_text_export_lays_out_the_page_without_markers() {
+        let document = Document::from_text(
+            "# Act one\n.int. bank - day\n= Setup.\n\n@mcTavish\n(low)\n*Go.* [[beat]]\n\n\
+             ===\n>THE END<",
+        );
+        let exported = export_plain_text(&parse_document(&document), &PlainTextOptions::default());
+        let pad = |kind: LineKind| " ".repeat(kind.indent_width());
+
+        assert_eq!(
+            exported,
+            format!(
+                "{}INT. BANK - DAY\n\n{}MCTAVISH\n{}(low)\n{}Go.\n\n\n{}THE END\n",
+                pad(LineKind::SceneHeading),
+                pad(LineKind::Character),
+                pad(LineKind::Parenthetical),
+                pad(LineKind::Dialogue),
+                " ".repeat(27),
+            )
+        );
+    }
+
+    #[test]
+    fn plain_text_export_wraps_dialogue_and_hyphenates_only_when_asked() {
+        let document = Document::from_text("BOB\nThe antidisestablishmentarianism crowd.");
+        let parsed = parse_document(&document);
+        let indent = " ".repeat(LineKind::Dialogue.indent_width());
+        let speech = |exported: String| {
+            exported
+                .lines()
+                .skip(1)
+                .map(|row| row.strip_prefix(&indent).unwrap_or(row).to_string())
+                .collect::<Vec<_>>()
+        };
+        let options = PlainTextOptions {
+            dialogue_width: 12,
+            hyphenate_dialogue: false,
+        };
+
+        assert_eq!(
+            speech(export_plain_text(&parsed, &options)),
+            ["The", "antidisestablishmentarianism", "crowd."]
+        );
+        assert_eq!(
+            speech(export_plain_text(
+                &parsed,
+                &PlainTextOptions {
+                    hyphenate_dialogue: true,
+                    ..options
+                },
+            )),
+            ["The antidis-", "establishme-", "ntarianism", "crowd."]
+        );
+    }
+
+    #[test]
+    fn html_export_escapes_text_and_keeps_blank_lines() {
+        let document = Document::from_text("INT. BAR & GRILL - DAY\n\nBOB\n(quietly)\nA < B.");
+        let palette = HtmlPalette {
+            background: "#222".to_string(),
+            paper: "#fff".to_string(),
+            text: "#111".to_string(),
+            line_colors: vec![
+                (LineKind::SceneHeading, "#1a1a1f".to_string()),
+                (LineKind::Character, "#8a2be2".to_string()),
+            ],
+        };
+        let html = export_html(&parse_document(&document), "Tom & Jerry", &palette);
+
+        assert!(html.contains("<title>Tom &amp; Jerry</title>"));
+        assert!(html.contains("background: #222;\">\n<main"));
+        assert!(html.contains("padding: 2em 4ch; background: #fff;\">\n"));
+        assert!(html.contains(
+            "<div class=\"scene-heading\" style=\"color: #1a1a1f; margin-left: 2ch; \
+             font-weight: bold;\">INT. BAR &amp; GRILL - DAY</div>\n\
+             <div class=\"empty\">&nbsp;</div>\n\
+             <div class=\"character\" style=\"color: #8a2be2; margin-left: 24ch; \
+             font-weight: bold;\">BOB</div>\n"
+        ));
+        assert!(html.contains(
+            "<div class=\"parenthetical\" style=\"color: #111; margin-left: 18ch; \
+             font-style: italic;\">(quietly)</div>"
+        ));
+        assert!(html.contains(">A &lt; B.</div>\n</main>"));
+    }
+}
@@ -1,9 +1,23 @@
 pub mod buffer;
+pub mod emphasis;
+pub mod export;
 pub mod links;
 pub mod model;
+pub mod outline;
+pub mod pagination;
 pub mod parser;
+pub mod reclassify;
+pub mod recovery;
+pub mod search;
+pub mod stats;
+pub mod wrap;
 
-pub use buffer::Document;
+pub use buffer::{ChangeEvent, ChangeKind, Document, EditSnapshot, LineId, UndoGroupKind};
+pub use emphasis::{Emphasis, EmphasisRun, emphasis_columns, emphasis_runs};
+pub use export::{
+    HtmlPalette, PlainTextOptions, export_html, export_plain_text, export_processed_fountain,
+    export_revision_notes,
+};
 pub use links::{
     EntityCatalog, EntityDocument, EntityFrontMatter, EntityScaffold, EntitySuggestion,
     LinkDisplayText, LinkError, MentionResolution, ResolutionSource, ResolvedEntity, ScriptLink,
@@ -11,5 +25,29 @@ pub use links::{
     UnresolvedReason, extract_script_links, is_valid_target_key, render_script_link_text,
     scaffold_entity, script_link_contains_visible_column, script_link_visible_column_range,
 };
-pub use model::{Cursor, DocumentFormat, DocumentPath, LineKind, ParsedLine, Position};
-pub use parser::{parse_document, parse_document_with_format};
+pub use model::{
+    Cursor, DocumentFormat, DocumentPath, DualDialogue, IndentWidths, LineKind, ParseOptions,
+    ParsedLine, Position, Selection, TitlePage, note_ranges, strip_notes,
+};
+pub use outline::{
+    OutlineEntry, heading_level, next_different_kind_line, next_scene_heading_line,
+    outline_entries, previous_different_kind_line, previous_scene_heading_line, scene_range,
+    structural_range,
+};
+pub use pagination::{
+    CHAR_WIDTH_POINTS, LINE_HEIGHT_POINTS, Page, PageLayout, PageMargins, PageSize,
+    keep_with_next_rows, paginate, starts_new_page,
+};
+pub use parser::{
+    ChunkedReparse, ReparseOutcome, begin_chunked_reparse, centered_text, dual_dialogue_cue_text,
+    forced_marker_len, is_page_break, is_transition, parse_document, parse_document_with_format,
+    parse_document_with_options, reparse_from, scene_number, split_character_extension, title_page,
+    title_page_len, title_page_value, toggle_character_dialogue_marker,
+};
+pub use reclassify::{MarkerEdit, apply_marker_edits, propose_forced_markers};
+pub use recovery::RecoveryState;
+pub use search::{
+    ReplaceOutcome, find_all, find_all_ignoring_case, replace_all, replace_all_ignoring_case,
+};
+pub use stats::{ScriptStats, document_stats};
+pub use wrap::wrap_paragraph;
@@ -1,14 +1,28 @@
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
+use serde::{Deserialize, Serialize};
+
 use crate::links::{ScriptLink, render_script_link_text};
+use crate::parser::{
+    centered_text, dual_dialogue_cue_text, forced_marker_len, scene_number,
+    split_character_extension, title_page_value,
+};
 
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+// Printable columns of an action line, used to center `> TEXT <`.
+const CENTERED_PAGE_COLUMNS: usize = 61;
+pub(crate) const SECTION_MARKER: char = '#';
+pub(crate) const SYNOPSIS_MARKER: char = '=';
+pub(crate) const NOTE_OPEN: &str = "[[";
+pub(crate) const NOTE_CLOSE: &str = "]]";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Position {
     pub line: usize,
     pub column: usize,
 }
 
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Cursor {
     pub position: Position,
     pub preferred_column: usize,
@@ -21,6 +35,24 @@ impl Cursor {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Selection {
+    pub anchor: Position,
+    pub head: Position,
+}
+
+impl Selection {
+    // Start/end in document order, or `None` when the selection is collapsed.
+    pub fn range(&self) -> Option<(Position, Position)> {
+        let key = |position: Position| (position.line, position.column);
+        match key(self.anchor).cmp(&key(self.head)) {
+            std::cmp::Ordering::Less => Some((self.anchor, self.head)),
+            std::cmp::Ordering::Greater => Some((self.head, self.anchor)),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum LineKind {
     Empty,
@@ -30,6 +62,13 @@ pub enum LineKind {
     Dialogue,
     Parenthetical,
     Transition,
+    Centered,
+    Lyric,
+    TitlePage,
+    PageBreak,
+    Boneyard,
+    Section,
+    Synopsis,
     MarkdownHeading,
     MarkdownListItem,
     MarkdownQuote,
@@ -39,24 +78,202 @@ pub enum LineKind {
     MarkdownParagraph,
 }
 
+impl LineKind {
+    // The indent under the default `IndentWidths`.
+    pub fn indent_width(&self) -> usize {
+        IndentWidths::default().for_kind(self)
+    }
+}
+
+// Processed-pane indents, in columns, for the kinds that have one. Formatting
+// standards disagree on these, so they are part of the parse options.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndentWidths {
+    pub scene_heading: usize,
+    pub character: usize,
+    pub dialogue: usize,
+    pub parenthetical: usize,
+    pub transition: usize,
+    pub synopsis: usize,
+}
+
+impl Default for IndentWidths {
+    fn default() -> Self {
+        Self {
+            scene_heading: 2,
+            character: 24,
+            dialogue: 12,
+            parenthetical: 18,
+            transition: 40,
+            synopsis: 4,
+        }
+    }
+}
+
+impl IndentWidths {
+    pub fn for_kind(&self, kind: &LineKind) -> usize {
+        match kind {
+            LineKind::SceneHeading => self.scene_heading,
+            LineKind::Character => self.character,
+            // Lyrics are mostly sung inside a speech, so they sit with the dialogue.
+            LineKind::Dialogue | LineKind::Lyric => self.dialogue,
+            LineKind::Parenthetical => self.parenthetical,
+            LineKind::Transition => self.transition,
+            LineKind::Synopsis => self.synopsis,
+            LineKind::Action
+            | LineKind::Centered
+            | LineKind::TitlePage
+            | LineKind::PageBreak
+            | LineKind::Boneyard
+            | LineKind::Section
+            | LineKind::MarkdownHeading
+            | LineKind::MarkdownListItem
+            | LineKind::MarkdownQuote
+            | LineKind::MarkdownCodeFence
+            | LineKind::MarkdownCode
+            | LineKind::MarkdownRule
+            | LineKind::MarkdownParagraph
+            | LineKind::Empty => 0,
+        }
+    }
+}
+
+// The `Key: value` block a Fountain script can open with. Keys keep the spelling
+// they were written in; a value continued on indented lines is joined with `\n`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TitlePage {
+    pub entries: Vec<(String, String)>,
+}
+
+impl TitlePage {
+    // The value of the first entry whose key matches, ignoring case.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(entry_key, _)| entry_key.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.get("title").filter(|title| !title.is_empty())
+    }
+}
+
+// Which column a Character/Dialogue block occupies when two cues speak at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DualDialogue {
+    Left,
+    Right,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DocumentFormat {
     Fountain,
     Markdown,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    pub scene_heading_requires_content: bool,
+    // A single blank line inside a speech keeps the dialogue block open; two end it.
+    pub dialogue_spans_single_blank: bool,
+    pub all_action: bool,
+    pub indent_widths: IndentWidths,
+}
+
+impl ParseOptions {
+    // Plain `.txt` notes default to skipping the screenplay heuristics.
+    pub fn for_path(path: impl AsRef<Path>) -> Self {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+        Self {
+            all_action: extension.as_deref() == Some("txt"),
+            ..Self::default()
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ParsedLine {
     pub kind: LineKind,
     pub raw: String,
     pub script_links: Vec<ScriptLink>,
     pub markdown_heading_level: Option<u8>,
+    pub dual_dialogue: Option<DualDialogue>,
+    // Set by the parser from `ParseOptions::indent_widths`.
+    pub indent: usize,
 }
 
 impl ParsedLine {
+    // Number of leading `#` on a Fountain section line; 0 for every other kind.
+    pub fn section_depth(&self) -> usize {
+        if self.kind != LineKind::Section {
+            return 0;
+        }
+        self.raw
+            .trim_start()
+            .chars()
+            .take_while(|ch| *ch == SECTION_MARKER)
+            .count()
+    }
+
+    // `V.O.` for a `SARAH (V.O.)` cue, as written.
+    pub fn character_extension(&self) -> Option<String> {
+        if self.kind != LineKind::Character {
+            return None;
+        }
+        let noted = strip_notes(&self.raw);
+        let cue = noted
+            .chars()
+            .skip(forced_marker_len(&noted))
+            .collect::<String>();
+        let cue = dual_dialogue_cue_text(&cue).unwrap_or(&cue);
+        split_character_extension(cue).map(|(_, extension)| extension.to_owned())
+    }
+
+    // `12A` for an `INT. HOUSE - DAY #12A#` heading.
+    pub fn scene_number(&self) -> Option<String> {
+        if self.kind != LineKind::SceneHeading {
+            return None;
+        }
+        scene_number(&strip_notes(&self.raw)).map(|(_, number)| number.to_owned())
+    }
+
+    pub fn has_note(&self) -> bool {
+        !note_ranges(&self.raw).is_empty()
+    }
+
     pub fn processed_text(&self) -> String {
         let indent = " ".repeat(self.indent_width());
-        let visible_text = render_script_link_text(&self.raw).text;
+        let noted = strip_notes(&self.raw);
+        let stripped = noted
+            .chars()
+            .skip(forced_marker_len(&noted))
+            .collect::<String>();
+        // A `^` cue loses its marker even when there is no block above to pair with.
+        let raw = match self.kind {
+            LineKind::Character => dual_dialogue_cue_text(&stripped).unwrap_or(&stripped),
+            LineKind::SceneHeading => scene_number(&stripped)
+                .and_then(|(column, _)| stripped.char_indices().nth(column))
+                .map_or(stripped.as_str(), |(end, _)| &stripped[..end]),
+            _ => &stripped,
+        };
+        let centered = match self.kind {
+            LineKind::Centered => centered_text(raw).map(|(_, inner)| inner),
+            LineKind::TitlePage => Some(title_page_value(raw).1),
+            _ => None,
+        };
+        if let Some(inner) = centered {
+            let text = render_script_link_text(inner).text;
+            let padding = CENTERED_PAGE_COLUMNS.saturating_sub(text.chars().count()) / 2;
+            return format!("{}{text}", " ".repeat(padding));
+        }
+
+        let visible_text = render_script_link_text(raw).text;
 
         match self.kind {
             LineKind::SceneHeading | LineKind::Transition | LineKind::Character => {
@@ -71,23 +288,53 @@ impl ParsedLine {
     }
 
     pub fn indent_width(&self) -> usize {
-        match self.kind {
-            LineKind::SceneHeading => 2,
-            LineKind::Action => 0,
-            LineKind::Character => 24,
-            LineKind::Dialogue => 12,
-            LineKind::Parenthetical => 18,
-            LineKind::Transition => 40,
-            LineKind::MarkdownHeading => 0,
-            LineKind::MarkdownListItem => 0,
-            LineKind::MarkdownQuote => 0,
-            LineKind::MarkdownCodeFence => 0,
-            LineKind::MarkdownCode => 0,
-            LineKind::MarkdownRule => 0,
-            LineKind::MarkdownParagraph => 0,
-            LineKind::Empty => 0,
+        self.indent
+    }
+}
+
+// Char ranges of every closed `[[note]]` on the line. A note also takes one space
+// next to it, so stripping it never leaves a double or leading space behind.
+pub fn note_ranges(raw: &str) -> Vec<Range<usize>> {
+    let chars = raw.chars().collect::<Vec<_>>();
+    let open = NOTE_OPEN.chars().collect::<Vec<_>>();
+    let close = NOTE_CLOSE.chars().collect::<Vec<_>>();
+    let mut ranges = Vec::new();
+    let mut column = 0;
+
+    while column + open.len() <= chars.len() {
+        if chars[column..].starts_with(&open) {
+            let body_start = column + open.len();
+            let Some(close_offset) = (body_start..=chars.len().saturating_sub(close.len()))
+                .find(|index| chars[*index..].starts_with(&close))
+            else {
+                break;
+            };
+            let mut end = close_offset + close.len();
+            let mut start = column;
+            if column > 0 && chars[column - 1] == ' ' {
+                if chars.get(end).is_none_or(|next| *next == ' ') {
+                    start -= 1;
+                }
+            } else if column == 0 && chars.get(end) == Some(&' ') {
+                end += 1;
+            }
+            ranges.push(start..end);
+            column = end;
+        } else {
+            column += 1;
         }
     }
+
+    ranges
+}
+
+pub fn strip_notes(raw: &str) -> String {
+    let ranges = note_ranges(raw);
+    raw.chars()
+        .enumerate()
+        .filter(|(column, _)| !ranges.iter().any(|range| range.contains(column)))
+        .map(|(_, ch)| ch)
+        .collect()
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -118,3 +365,50 @@ impl DocumentFormat {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_are_stripped_from_processed_text_only() {
+        let line = ParsedLine {
+            kind: LineKind::Dialogue,
+            raw: "I [[too soft?]] never [[cut]] left.[[ending]]".to_owned(),
+            script_links: Vec::new(),
+            markdown_heading_level: None,
+            dual_dialogue: None,
+            indent: LineKind::Dialogue.indent_width(),
+        };
+
+        assert!(line.has_note());
+        assert_eq!(line.processed_text().trim(), "I never left.");
+        assert_eq!(note_ranges(&line.raw), [1..15, 21..29, 35..45]);
+        assert_eq!(strip_notes("[[check]] Then he runs."), "Then he runs.");
+        assert_eq!(strip_notes("[[unclosed note"), "[[unclosed note");
+    }
+
+    #[test]
+    fn selection_range_is_normalized_across_lines() {
+        let at = |line, column| Position { line, column };
+        let backwards = Selection {
+            anchor: at(4, 2),
+            head: at(1, 7),
+        };
+        let same_line = Selection {
+            anchor: at(3, 1),
+            head: at(3, 5),
+        };
+
+        assert_eq!(backwards.range(), Some((at(1, 7), at(4, 2))));
+        assert_eq!(same_line.range(), Some((at(3, 1), at(3, 5))));
+        assert_eq!(
+            Selection {
+                anchor: at(3, 1),
+                head: at(3, 1),
+            }
+            .range(),
+            None
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use std::ops::Range;
+
+use crate::model::{LineKind, ParsedLine, SECTION_MARKER};
+
+const SCENE_HEADING_LEVEL: u8 = 7;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutlineEntry {
+    pub line: usize,
+    // Sections indent by `section_depth() - 1`; scenes sit one level inside the
+    // section above them.
+    pub depth: usize,
+    pub label: String,
+}
+
+// Fountain sections nest like Markdown headings and always sit above scenes.
+pub fn heading_level(line: &ParsedLine) -> Option<u8> {
+    match line.kind {
+        LineKind::MarkdownHeading => Some(line.markdown_heading_level.unwrap_or(1)),
+        LineKind::Section => {
+            let depth = line
+                .section_depth()
+                .min(usize::from(SCENE_HEADING_LEVEL - 1));
+            Some(u8::try_from(depth).unwrap_or(1))
+        }
+        LineKind::SceneHeading => Some(SCENE_HEADING_LEVEL),
+        _ => None,
+    }
+}
+
+pub fn structural_range(parsed: &[ParsedLine], line: usize) -> Option<Range<usize>> {
+    let line = line.min(parsed.len().checked_sub(1)?);
+    let (start, level) = (0..=line)
+        .rev()
+        .find_map(|index| heading_level(&parsed[index]).map(|level| (index, level)))?;
+    let end = parsed[start + 1..]
+        .iter()
+        .position(|candidate| heading_level(candidate).is_some_and(|next| next <= level))
+        .map_or(parsed.len(), |offset| start + 1 + offset);
+
+    Some(start..end)
+}
+
+pub fn scene_range(parsed: &[ParsedLine], line: usize) -> Option<Range<usize>> {
+    let line = line.min(parsed.len().checked_sub(1)?);
+    let start = (0..=line)
+        .rev()
+        .find(|index| parsed[*index].kind == LineKind::SceneHeading)?;
+    let end = parsed[start + 1..]
+        .iter()
+        .position(|candidate| candidate.kind == LineKind::SceneHeading)
+        .map_or(parsed.len(), |offset| start + 1 + offset);
+
+    Some(start..end)
+}
+
+// Blank lines never count as a different element, otherwise every jump would
+// stop on the separator right after the current block.
+pub fn next_different_kind_line(parsed: &[ParsedLine], line: usize) -> Option<usize> {
+    let current = &parsed.get(line)?.kind;
+    (line + 1..parsed.len()).find(|index| is_different_element(&parsed[*index].kind, current))
+}
+
+pub fn previous_different_kind_line(parsed: &[ParsedLine], line: usize) -> Option<usize> {
+    let line = line.min(parsed.len().checked_sub(1)?);
+    let current = &parsed[line].kind;
+    (0..line)
+        .rev()
+        .find(|index| is_different_element(&parsed[*index].kind, current))
+}
+
+// Scene headings and Fountain sections in document order.
+pub fn outline_entries(parsed: &[ParsedLine]) -> Vec<OutlineEntry> {
+    let mut entries = Vec::new();
+    let mut scene_depth = 0;
+
+    for (line, parsed_line) in parsed.iter().enumerate() {
+        let depth = match parsed_line.kind {
+            LineKind::Section => {
+                scene_depth = parsed_line.section_depth();
+                scene_depth.saturating_sub(1)
+            }
+            LineKind::SceneHeading => scene_depth,
+            _ => continue,
+        };
+        let text = parsed_line.processed_text();
+        let label = text
+            .trim()
+            .trim_start_matches(SECTION_MARKER)
+            .trim()
+            .to_owned();
+        entries.push(OutlineEntry { line, depth, label });
+    }
+
+    entries
+}
+
+pub fn next_scene_heading_line(parsed: &[ParsedLine], line: usize) -> Option<usize> {
+    (line.saturating_add(1)..parsed.len())
+        .find(|index| parsed[*index].kind == LineKind::SceneHeading)
+}
+
+pub fn previous_scene_heading_line(parsed: &[ParsedLine], line: usize) -> Option<usize> {
+    (0..line.min(parsed.len()))
+        .rev()
+        .find(|index| parsed[*index].kind == LineKind::SceneHeading)
+}
+
+fn is_different_element(kind: &LineKind, current: &LineKind) -> bool {
+    *kind != LineKind::Empty && kind != current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::Document;
+    use crate::model::DocumentFormat;
+    use crate::parser::parse_document_with_format;
+
+    #[test]
+    fn scene_range_ends_at_next_scene_heading() {
+        let doc = Document::from_text("INT. ROOM - DAY\nAction.\n\nEXT. YARD - NIGHT\nMore.");
+        let parsed = parse_document_with_format(&doc, DocumentFormat::Fountain);
+
+        assert_eq!(structural_range(&parsed, 1), Some(0..3));
+        assert_eq!(structural_range(&parsed, 4), Some(3..5));
+    }
+
+    #[test]
+    fn nested_section_range_respects_heading_levels() {
+        let doc = Document::from_text("# Act\n## One\nText\n## Two\nText\n# Next");
+        let parsed = parse_document_with_format(&doc, DocumentFormat::Markdown);
+
+        assert_eq!(structural_range(&parsed, 0), Some(0..5));
+        assert_eq!(structural_range(&parsed, 2), Some(1..3));
+        assert_eq!(structural_range(&parsed, 4), Some(3..5));
+        assert_eq!(structural_range(&parsed, 5), Some(5..6));
+    }
+
+    #[test]
+    fn fountain_sections_contain_their_scenes() {
+        let doc = Document::from_text("# Act one\nINT. A\nAction\n## Beat\nINT. B\n# Act two");
+        let parsed = parse_document_with_format(&doc, DocumentFormat::Fountain);
+
+        assert_eq!(structural_range(&parsed, 0), Some(0..5));
+        assert_eq!(structural_range(&parsed, 3), Some(3..5));
+        assert_eq!(structural_range(&parsed, 2), Some(1..3));
+    }
+
+    #[test]
+    fn scene_range_keeps_trailing_blank_lines_and_reaches_eof() {
+        let doc = Document::from_text("Cold open\nINT. A\nAction\n\n\nINT. B\nEnd");
+        let parsed = parse_document_with_format(&doc, DocumentFormat::Fountain);
+
+        assert_eq!(scene_range(&parsed, 0), None);
+        assert_eq!(scene_range(&parsed, 3), Some(1..5));
+        assert_eq!(scene_range(&parsed, 6), Some(5..7));
+    }
+
+    #[test]
+    fn text_before_first_heading_has_no_range() {
+        let doc = Document::from_text("Loose action\nINT. ROOM - DAY");
+        let parsed = parse_document_with_format(&doc, DocumentFormat::Fountain);
+
+        assert_eq!(structural_range(&parsed, 0), None);
+    }
+
+    #[test]
+    fn different_kind_jumps_skip_blank_lines_and_stop_at_ends() {
+        let doc = Document::from_text("INT. A\n\nMARY\nHello.\nStill me.\n\nShe leaves.");
+        let parsed = parse_document_with_format(&doc, DocumentFormat::Fountain);
+
+        assert_eq!(next_different_kind_line(&parsed, 0), Some(2));
+        assert_eq!(next_different_kind_line(&parsed, 3), Some(6));
+        assert_eq!(next_different_kind_line(&parsed, 6), None);
+        assert_eq!(previous_different_kind_line(&parsed, 4), Some(2));
+        assert_eq!(previous_different_kind_line(&parsed, 5), Some(4));
+        assert_eq!(previous_different_kind_line(&parsed, 0), None);
+    }
+
+    #[test]
+    fn empty_document_has_no_ranges() {
+        let parsed = parse_document_with_format(&Document::new(), DocumentFormat::Fountain);
+
+        assert_eq!(structural_range(&parsed, 0), None);
+        assert_eq!(scene_range(&parsed, 0), None);
+        assert_eq!(structural_range(&[], 0), None);
+        assert_eq!(scene_range(&[], 0), None);
+    }
+
+    #[test]
+    fn scene_heading_jumps_skip_the_current_heading_and_stop_at_ends() {
+        let doc = Document::from_text("Cold open\nINT. A\nAction\n\nINT. B\nEnd");
+        let parsed = parse_document_with_format(&doc, DocumentFormat::Fountain);
+
+        assert_eq!(next_scene_heading_line(&parsed, 0), Some(1));
+        assert_eq!(next_scene_heading_line(&parsed, 1), Some(4));
+        assert_eq!(next_scene_heading_line(&parsed, 4), None);
+        assert_eq!(previous_scene_heading_line(&parsed, 5), Some(4));
+        assert_eq!(previous_scene_heading_line(&parsed, 4), Some(1));
+        assert_eq!(previous_scene_heading_line(&parsed, 1), None);
+    }
+
+    #[test]
+    fn outline_nests_scenes_under_their_sections() {
+        let doc =
+            Document::from_text("INT. A\n# Act one\nINT. B\n## Beat\nint. c\nAction\n# Act two");
+        let parsed = parse_document_with_format(&doc, DocumentFormat::Fountain);
+        let entries = outline_entries(&parsed)
+            .into_iter()
+            .map(|entry| (entry.line, entry.depth, entry.label))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            entries,
+            [
+                (0, 0, "INT. A".to_owned()),
+                (1, 0, "Act one".to_owned()),
+                (2, 1, "INT. B".to_owned()),
+                (3, 1, "Beat".to_owned()),
+                (4, 2, "INT. C".to_owned()),
+                (6, 0, "Act two".to_owned()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use std::ops::Range;
+
+use crate::model::{LineKind, ParsedLine};
+use crate::wrap::wrap_paragraph;
+
+const POINTS_PER_INCH: f32 = 72.0;
+const MM_PER_INCH: f32 = 25.4;
+// 12pt Courier prints six lines and ten characters to the inch.
+pub const LINE_HEIGHT_POINTS: f32 = 12.0;
+pub const CHAR_WIDTH_POINTS: f32 = 7.2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PageSize {
+    A4,
+    UsLetter,
+}
+
+impl PageSize {
+    pub const ALL: [PageSize; 2] = [PageSize::A4, PageSize::UsLetter];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PageSize::A4 => "A4",
+            PageSize::UsLetter => "US Letter",
+        }
+    }
+
+    pub fn settings_key(self) -> &'static str {
+        match self {
+            PageSize::A4 => "a4",
+            PageSize::UsLetter => "us_letter",
+        }
+    }
+
+    pub fn from_settings_key(key: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|size| size.settings_key().eq_ignore_ascii_case(key.trim()))
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            PageSize::A4 => PageSize::UsLetter,
+            PageSize::UsLetter => PageSize::A4,
+        }
+    }
+
+    pub fn width_points(self) -> f32 {
+        match self {
+            PageSize::A4 => 210.0 / MM_PER_INCH * POINTS_PER_INCH,
+            PageSize::UsLetter => 8.5 * POINTS_PER_INCH,
+        }
+    }
+
+    pub fn height_points(self) -> f32 {
+        match self {
+            PageSize::A4 => 297.0 / MM_PER_INCH * POINTS_PER_INCH,
+            PageSize::UsLetter => 11.0 * POINTS_PER_INCH,
+        }
+    }
+}
+
+// Distances from each paper edge to the text block, in points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PageMargins {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl PageMargins {
+    // An inch and a half on the bound edge and an inch everywhere else.
+    pub const SCREENPLAY: PageMargins = PageMargins {
+        left: 1.5 * POINTS_PER_INCH,
+        right: POINTS_PER_INCH,
+        top: POINTS_PER_INCH,
+        bottom: POINTS_PER_INCH,
+    };
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PageLayout {
+    pub size: PageSize,
+    pub margins: PageMargins,
+}
+
+impl PageLayout {
+    pub fn screenplay(size: PageSize) -> Self {
+        Self {
+            size,
+            margins: PageMargins::SCREENPLAY,
+        }
+    }
+
+    pub fn text_width_points(&self) -> f32 {
+        (self.size.width_points() - self.margins.left - self.margins.right).max(0.0)
+    }
+
+    pub fn text_height_points(&self) -> f32 {
+        (self.size.height_points() - self.margins.top - self.margins.bottom).max(0.0)
+    }
+
+    // Whole printed rows that fit between the top and bottom margins.
+    pub fn lines_per_page(&self) -> usize {
+        ((self.text_height_points() / LINE_HEIGHT_POINTS) + 1e-4)
+            .floor()
+            .max(1.0) as usize
+    }
+
+    // Courier columns that fit between the left and right margins.
+    pub fn columns(&self) -> usize {
+        ((self.text_width_points() / CHAR_WIDTH_POINTS) + 1e-4)
+            .floor()
+            .max(1.0) as usize
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Page {
+    pub number: usize,
+    pub lines: Range<usize>,
+    pub rows: usize,
+}
+
+// Rows of what follows that must share a page with this line: a scene heading
+// keeps its blank line and the first line of action, a cue its first speech row.
+pub fn keep_with_next_rows(kind: &LineKind) -> usize {
+    match kind {
+        LineKind::SceneHeading => 2,
+        LineKind::Character => 1,
+        _ => 0,
+    }
+}
+
+// Whether a line of `line_rows` rows, arriving with `rows_on_page` rows already
+// used, opens the next page instead of stranding its keep-with-next rows at the
+// bottom of this one. The processed pane applies this to its own wrapped rows, so
+// the pane and `paginate` break pages by the same rule.
+pub fn starts_new_page(
+    kind: &LineKind,
+    line_rows: usize,
+    rows_on_page: usize,
+    lines_per_page: usize,
+) -> bool {
+    let needed = (line_rows + keep_with_next_rows(kind)).min(lines_per_page);
+    rows_on_page > 0 && rows_on_page + needed > lines_per_page
+}
+
+// Printed rows of a line, wrapped at the text block width less its indent.
+fn printed_rows(line: &ParsedLine, layout: &PageLayout) -> usize {
+    match line.kind {
+        LineKind::TitlePage | LineKind::PageBreak | LineKind::Boneyard => 0,
+        _ => {
+            let width = layout.columns().saturating_sub(line.indent_width());
+            wrap_paragraph(line.processed_text().trim(), width, false).len()
+        }
+    }
+}
+
+// Groups the script into numbered pages of whole source lines. The title page
+// is left out, `===` starts a new page, blank lines never open a page, and a
+// line that would strand its `keep_with_next_rows` moves over with them. Page
+// height and line width come from `layout`.
+pub fn paginate(parsed: &[ParsedLine], layout: &PageLayout) -> Vec<Page> {
+    let lines_per_page = layout.lines_per_page();
+    let mut pages = Vec::new();
+    let mut page_start = 0;
+    let mut rows = 0;
+
+    for (index, line) in parsed.iter().enumerate() {
+        if line.kind == LineKind::PageBreak {
+            if rows > 0 {
+                close_page(&mut pages, page_start..index, rows);
+                rows = 0;
+            }
+            continue;
+        }
+
+        let line_rows = printed_rows(line, layout);
+        if line_rows == 0 {
+            continue;
+        }
+        if starts_new_page(&line.kind, line_rows, rows, lines_per_page) {
+            close_page(&mut pages, page_start..index, rows);
+            rows = 0;
+        }
+        if rows == 0 {
+            if line.kind == LineKind::Empty {
+                continue;
+            }
+            page_start = index;
+        }
+        rows += line_rows;
+    }
+
+    if rows > 0 {
+        close_page(&mut pages, page_start..parsed.len(), rows);
+    }
+    pages
+}
+
+fn close_page(pages: &mut Vec<Page>, lines: Range<usize>, rows: usize) {
+    pages.push(Page {
+        number: pages.len() + 1,
+        lines,
+        rows,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::Document;
+    use crate::model::DocumentFormat;
+    use crate::parser::parse_document_with_format;
+
+    const LETTER: PageLayout = PageLayout {
+        size: PageSize::UsLetter,
+        margins: PageMargins::SCREENPLAY,
+    };
+
+    fn pages_in(text: &str, layout: &PageLayout) -> Vec<Page> {
+        let doc = Document::from_text(text);
+        let parsed = parse_document_with_format(&doc, DocumentFormat::Fountain);
+        paginate(&parsed, layout)
+    }
+
+    fn pages_of(text: &str) -> Vec<Page> {
+        pages_in(text, &LETTER)
+    }
+
+    fn action_lines(count: usize) -> String {
+        (0..count).map(|index| format!("Beat {index}.\n")).collect()
+    }
+
+    #[test]
+    fn fills_pages_and_skips_the_title_page_and_leading_blanks() {
+        let pages = pages_of(&format!("Title: Heist\n\n\n{}", action_lines(60)));
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].number, 1);
+        assert_eq!(pages[0].lines, 3..57);
+        assert_eq!(pages[0].rows, LETTER.lines_per_page());
+        assert_eq!(pages[1].lines, 57..64);
+    }
+
+    #[test]
+    fn scene_headings_and_cues_are_not_left_at_the_bottom() {
+        let heading = pages_of(&format!(
+            "{}\nINT. BANK - DAY\n\nThey run.",
+            action_lines(51)
+        ));
+        assert_eq!(heading[0].lines, 0..52);
+        assert_eq!(heading[1].lines, 52..55);
+
+        let cue = pages_of(&format!("{}\nBOB\nHello.", action_lines(52)));
+        assert_eq!(cue[0].rows, 53);
+        assert_eq!(cue[1].lines, 53..55);
+    }
+
+    #[test]
+    fn page_break_markers_start_a_new_page() {
+        let pages = pages_of("One.\n\n===\n\nTwo.");
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].lines, 0..2);
+        assert_eq!(pages[1].lines, 4..5);
+    }
+
+    #[test]
+    fn letter_and_a4_break_pages_at_different_lines() {
+        let a4 = PageLayout::screenplay(PageSize::A4);
+        assert_eq!(LETTER.lines_per_page(), 54);
+        assert_eq!(a4.lines_per_page(), 58);
+
+        let script = action_lines(120);
+        let letter_pages = pages_in(&script, &LETTER);
+        let a4_pages = pages_in(&script, &a4);
+        assert_eq!(letter_pages[0].lines, 0..54);
+        assert_eq!(a4_pages[0].lines, 0..58);
+        assert_eq!(letter_pages.len(), 3);
+        assert_eq!(a4_pages.len(), 3);
+        assert_eq!(letter_pages[2].lines, 108..121);
+        assert_eq!(a4_pages[2].lines, 116..121);
+    }
+}
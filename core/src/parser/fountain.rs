@@ -1,75 +1,820 @@
+use std::ops::Range;
+
 use crate::buffer::Document;
-use crate::model::LineKind;
+use crate::model::{
+    DualDialogue, LineKind, ParseOptions, ParsedLine, SECTION_MARKER, SYNOPSIS_MARKER, TitlePage,
+};
 
+use super::ReparseOutcome;
 use super::shared::parsed_line;
 
-pub(super) fn parse(document: &Document) -> Vec<crate::model::ParsedLine> {
+const FORCED_CHARACTER_MARKER: char = '@';
+const FORCED_NON_CHARACTER_MARKER: char = '!';
+const FORCED_SCENE_HEADING_MARKER: char = '.';
+const FORCED_TRANSITION_MARKER: char = '>';
+const LYRIC_MARKER: char = '~';
+pub(crate) const DUAL_DIALOGUE_MARKER: char = '^';
+const CENTERED_OPEN_MARKER: char = '>';
+const CENTERED_CLOSE_MARKER: char = '<';
+const BONEYARD_OPEN: &str = "/*";
+const BONEYARD_CLOSE: &str = "*/";
+const SCENE_NUMBER_MARKER: char = '#';
+const TITLE_PAGE_SEPARATOR: char = ':';
+const PAGE_BREAK_MIN_MARKERS: usize = 3;
+// Transitions that do not end in `TO:`.
+const TRANSITION_LINES: [&str; 5] = [
+    "FADE IN:",
+    "FADE OUT.",
+    "FADE OUT:",
+    "FADE TO BLACK.",
+    "CUT TO BLACK.",
+];
+const INTERCUT_TRANSITION: &str = "INTERCUT";
+// A title page has to open with one of these, so a script starting on
+// `FADE IN:` stays part of the script. Later keys in the block can be anything.
+const TITLE_PAGE_OPENING_KEYS: [&str; 8] = [
+    "title",
+    "credit",
+    "author",
+    "authors",
+    "source",
+    "draft date",
+    "date",
+    "contact",
+];
+
+#[cfg(test)]
+fn parse(document: &Document) -> Vec<crate::model::ParsedLine> {
+    parse_with_options(document, &ParseOptions::default())
+}
+
+pub(super) fn parse_with_options(
+    document: &Document,
+    options: &ParseOptions,
+) -> Vec<crate::model::ParsedLine> {
     let mut parsed = Vec::with_capacity(document.line_count());
-    let mut previous_kind = LineKind::Empty;
+    let mut flow = FlowContext::new();
+    let mut in_boneyard = false;
+    let title_page_lines = title_page_len(document.lines());
 
-    for raw in document.lines() {
-        let kind = classify_line(raw, &previous_kind);
-        previous_kind = kind.clone();
+    for (line, raw) in document.lines().iter().enumerate() {
+        if line < title_page_lines {
+            parsed.push(parsed_line(raw, LineKind::TitlePage, None));
+            continue;
+        }
+
+        let (hidden, still_open) = scan_boneyard(raw, in_boneyard);
+        in_boneyard = still_open;
+        // Boneyard lines are invisible to the surrounding flow, so a cue above one
+        // still owns the dialogue below it.
+        if hidden {
+            parsed.push(parsed_line(raw, LineKind::Boneyard, None));
+            continue;
+        }
+
+        let kind = classify_line(raw, flow.previous_kind(raw, options), options);
+        flow.push(&kind);
         parsed.push(parsed_line(raw, kind, None));
     }
 
+    mark_dual_dialogue(&mut parsed);
     parsed
 }
 
-fn classify_line(raw: &str, previous_kind: &LineKind) -> LineKind {
+// The lines above that `classify_line` depends on. Title page and boneyard lines
+// are skipped, as they never reach the flow.
+#[derive(Debug)]
+struct FlowContext {
+    previous_kind: LineKind,
+    blank_run: usize,
+    kind_before_blanks: LineKind,
+}
+
+impl FlowContext {
+    fn new() -> Self {
+        Self {
+            previous_kind: LineKind::Empty,
+            blank_run: 0,
+            kind_before_blanks: LineKind::Empty,
+        }
+    }
+
+    // Seeds the context from already parsed lines ending just above a restart.
+    fn before(parsed: &[ParsedLine]) -> Self {
+        let mut flow = Self::new();
+        let mut kinds = parsed
+            .iter()
+            .rev()
+            .map(|line| &line.kind)
+            .filter(|kind| !matches!(kind, LineKind::Boneyard | LineKind::TitlePage))
+            .take(2)
+            .collect::<Vec<_>>();
+        kinds.reverse();
+        for kind in kinds {
+            flow.push(kind);
+        }
+        flow
+    }
+
+    fn push(&mut self, kind: &LineKind) {
+        if *kind == LineKind::Empty {
+            self.blank_run += 1;
+        } else {
+            self.blank_run = 0;
+            self.kind_before_blanks = kind.clone();
+        }
+        self.previous_kind = kind.clone();
+    }
+
+    // A speech seen through a single blank line, or just the line above. A `!`
+    // line after the blank is action, so it never looks through.
+    fn previous_kind(&self, raw: &str, options: &ParseOptions) -> &LineKind {
+        if options.dialogue_spans_single_blank
+            && self.blank_run == 1
+            && forced_marker(raw.trim()) != Some(FORCED_NON_CHARACTER_MARKER)
+            && matches!(
+                self.kind_before_blanks,
+                LineKind::Dialogue | LineKind::Parenthetical | LineKind::Lyric
+            )
+        {
+            &self.kind_before_blanks
+        } else {
+            &self.previous_kind
+        }
+    }
+}
+
+// Reclassifies from the edited line until a line below the edit comes out as
+// before, then keeps the old tail: a kind only depends on the line above it, or
+// on the two above it when a blank line may continue a speech.
+pub(super) fn reparse_from(
+    parsed: &mut Vec<ParsedLine>,
+    document: &Document,
+    options: &ParseOptions,
+    first_line: usize,
+) -> ReparseOutcome {
+    let lines = document.lines();
+    let RestartBounds {
+        start,
+        edit_end,
+        old_edit_end,
+        title_page_lines,
+    } = restart_bounds(parsed, lines, first_line);
+    let mut flow = FlowContext::before(&parsed[..start]);
+    let mut in_boneyard = false;
+    let mut reparsed = Vec::new();
+    let mut old_end = parsed.len();
+
+    for (line, raw) in lines.iter().enumerate().skip(start) {
+        let (hidden, still_open) = scan_boneyard(raw, in_boneyard);
+        in_boneyard = still_open && line >= title_page_lines;
+        let kind = if line < title_page_lines {
+            LineKind::TitlePage
+        } else if hidden {
+            LineKind::Boneyard
+        } else {
+            classify_line(raw, flow.previous_kind(raw, options), options)
+        };
+        if !hidden && line >= title_page_lines {
+            flow.push(&kind);
+        }
+        reparsed.push(parsed_line(raw, kind, None));
+
+        let old_line = (line >= edit_end).then(|| line - edit_end + old_edit_end);
+        if let Some(old_line) = old_line
+            && converges(&parsed[old_line], &reparsed[reparsed.len() - 1], options)
+        {
+            old_end = old_line + 1;
+            break;
+        }
+    }
+
+    let is_boneyard = |line: &ParsedLine| line.kind == LineKind::Boneyard;
+    let reparsed_len = reparsed.len();
+    let replaced = parsed.splice(start..old_end, reparsed).collect::<Vec<_>>();
+    let replacement = &parsed[start..start + reparsed_len];
+    let touched_boneyard = replaced.iter().chain(replacement).any(is_boneyard);
+    let boneyard_changed = touched_boneyard
+        && replaced
+            .iter()
+            .map(is_boneyard)
+            .ne(replacement.iter().map(is_boneyard));
+
+    // Dual dialogue pairs can span the edit, so they are re-marked everywhere.
+    for line in parsed.iter_mut() {
+        line.dual_dialogue = None;
+    }
+    mark_dual_dialogue(parsed);
+
+    ReparseOutcome {
+        reparsed: start..start + reparsed_len,
+        boneyard_changed,
+    }
+}
+
+struct RestartBounds {
+    start: usize,
+    // Lines `first_line..edit_end` replaced the old `first_line..old_edit_end`.
+    edit_end: usize,
+    old_edit_end: usize,
+    title_page_lines: usize,
+}
+
+fn restart_bounds(parsed: &[ParsedLine], lines: &[String], first_line: usize) -> RestartBounds {
+    let first_line = first_line.min(parsed.len()).min(lines.len());
+    // Lines below the edit are unchanged, only shifted by its line count delta.
+    let unchanged_tail = lines[first_line..]
+        .iter()
+        .rev()
+        .zip(parsed[first_line..].iter().rev())
+        .take_while(|(raw, old)| **raw == old.raw)
+        .count();
+
+    // Restart below a line that cannot leave a boneyard open, or from the top when
+    // the edit may have started, ended or reshaped the title page.
+    let title_page_lines = title_page_len(lines);
+    let old_title_page_lines = parsed
+        .iter()
+        .take_while(|line| line.kind == LineKind::TitlePage)
+        .count();
+    let start = if first_line <= title_page_lines.max(old_title_page_lines) {
+        0
+    } else {
+        parsed[..first_line]
+            .iter()
+            .rposition(settles)
+            .map_or(0, |line| line + 1)
+    };
+
+    RestartBounds {
+        start,
+        edit_end: lines.len() - unchanged_tail,
+        old_edit_end: parsed.len() - unchanged_tail,
+        title_page_lines,
+    }
+}
+
+fn settles(line: &ParsedLine) -> bool {
+    line.kind != LineKind::Boneyard && !touches_boneyard(&line.raw)
+}
+
+// Whether a line below the edit came out as before, so everything after it can
+// be kept.
+fn converges(old: &ParsedLine, new: &ParsedLine, options: &ParseOptions) -> bool {
+    old.kind == new.kind
+        && settles(old)
+        && (!options.dialogue_spans_single_blank || old.kind != LineKind::Empty)
+}
+
+// `reparse_from` spread over several calls for edits too large for one frame.
+// `begin` swaps the edited lines for unclassified action lines, so `parsed`
+// lines up with the document while the edit is worked through; a further edit
+// before `step` reports the reparse finished must be passed to `extend`.
+#[derive(Debug)]
+pub(super) struct PendingReparse {
+    start: usize,
+    next_line: usize,
+    edit_end: usize,
+    title_page_lines: usize,
+    flow: FlowContext,
+    in_boneyard: bool,
+    boneyard_changed: bool,
+}
+
+impl PendingReparse {
+    pub(super) fn begin(
+        parsed: &mut Vec<ParsedLine>,
+        document: &Document,
+        first_line: usize,
+    ) -> (Self, Range<usize>) {
+        let lines = document.lines();
+        let first_line = first_line.min(parsed.len()).min(lines.len());
+        let bounds = restart_bounds(parsed, lines, first_line);
+        let placeholders = lines[first_line..bounds.edit_end]
+            .iter()
+            .map(|raw| ParsedLine {
+                indent: LineKind::Action.indent_width(),
+                kind: LineKind::Action,
+                raw: raw.clone(),
+                script_links: Vec::new(),
+                markdown_heading_level: None,
+                dual_dialogue: None,
+            });
+        let boneyard_changed = parsed
+            .splice(first_line..bounds.old_edit_end, placeholders)
+            .any(|line| line.kind == LineKind::Boneyard);
+        let pending = Self {
+            start: bounds.start,
+            next_line: bounds.start,
+            edit_end: bounds.edit_end,
+            title_page_lines: bounds.title_page_lines,
+            flow: FlowContext::before(&parsed[..bounds.start]),
+            in_boneyard: false,
+            boneyard_changed,
+        };
+        (pending, first_line..bounds.edit_end)
+    }
+
+    // Folds an edit made before this reparse finished into it. The reparse starts
+    // again from the edit or from where it had got to, whichever comes first, and
+    // still runs through every line it had not reached.
+    pub(super) fn extend(
+        &mut self,
+        parsed: &mut Vec<ParsedLine>,
+        document: &Document,
+        first_line: usize,
+    ) -> Range<usize> {
+        let line_delta = document.line_count() as isize - parsed.len() as isize;
+        let pending_end = if first_line < self.edit_end {
+            self.edit_end
+                .saturating_add_signed(line_delta)
+                .max(first_line)
+        } else {
+            self.edit_end
+        };
+        let (restarted, placeholders) =
+            Self::begin(parsed, document, first_line.min(self.next_line));
+        *self = Self {
+            start: self.start.min(restarted.start),
+            edit_end: restarted
+                .edit_end
+                .max(pending_end)
+                .min(document.line_count()),
+            boneyard_changed: self.boneyard_changed || restarted.boneyard_changed,
+            ..restarted
+        };
+        placeholders
+    }
+
+    // Classifies up to `max_lines` more lines. The outcome covers every line the
+    // whole reparse touched and is only returned once it has finished.
+    pub(super) fn step(
+        &mut self,
+        parsed: &mut [ParsedLine],
+        document: &Document,
+        options: &ParseOptions,
+        max_lines: usize,
+    ) -> (Range<usize>, Option<ReparseOutcome>) {
+        let lines = document.lines();
+        let step_start = self.next_line;
+        let step_end = step_start.saturating_add(max_lines.max(1)).min(lines.len());
+        let mut finished = step_end == lines.len();
+
+        for (line, raw) in lines.iter().enumerate().take(step_end).skip(step_start) {
+            let (hidden, still_open) = scan_boneyard(raw, self.in_boneyard);
+            self.in_boneyard = still_open && line >= self.title_page_lines;
+            let kind = if line < self.title_page_lines {
+                LineKind::TitlePage
+            } else if hidden {
+                LineKind::Boneyard
+            } else {
+                classify_line(raw, self.flow.previous_kind(raw, options), options)
+            };
+            if !hidden && line >= self.title_page_lines {
+                self.flow.push(&kind);
+            }
+            let old = std::mem::replace(&mut parsed[line], parsed_line(raw, kind, None));
+            self.boneyard_changed |=
+                (old.kind == LineKind::Boneyard) != (parsed[line].kind == LineKind::Boneyard);
+            self.next_line = line + 1;
+
+            if line >= self.edit_end && converges(&old, &parsed[line], options) {
+                finished = true;
+                break;
+            }
+        }
+
+        let classified = step_start..self.next_line;
+        if !finished {
+            return (classified, None);
+        }
+        for line in parsed.iter_mut() {
+            line.dual_dialogue = None;
+        }
+        mark_dual_dialogue(parsed);
+        let outcome = ReparseOutcome {
+            reparsed: self.start..self.next_line,
+            boneyard_changed: self.boneyard_changed,
+        };
+        (classified, Some(outcome))
+    }
+}
+
+fn touches_boneyard(raw: &str) -> bool {
+    raw.contains(BONEYARD_OPEN) || raw.contains(BONEYARD_CLOSE)
+}
+
+// Whether every visible character of `raw` sits inside `/* ... */` (touching a
+// boneyard at all), and whether a boneyard is still open at the end of the line.
+fn scan_boneyard(raw: &str, mut open: bool) -> (bool, bool) {
+    let mut touched = open;
+    let mut visible_outside = false;
+    let mut rest = raw;
+
+    while !rest.is_empty() {
+        if open {
+            let Some(end) = rest.find(BONEYARD_CLOSE) else {
+                break;
+            };
+            rest = &rest[end + BONEYARD_CLOSE.len()..];
+            open = false;
+        } else {
+            let Some(start) = rest.find(BONEYARD_OPEN) else {
+                visible_outside |= !rest.trim().is_empty();
+                break;
+            };
+            visible_outside |= !rest[..start].trim().is_empty();
+            rest = &rest[start + BONEYARD_OPEN.len()..];
+            open = true;
+            touched = true;
+        }
+    }
+
+    (touched && !visible_outside, open)
+}
+
+// A cue ending in `^` speaks alongside the Character block just above it, which
+// becomes the left column. Blank lines between the two blocks are allowed.
+fn mark_dual_dialogue(parsed: &mut [ParsedLine]) {
+    for cue in 0..parsed.len() {
+        if parsed[cue].kind != LineKind::Character
+            || dual_dialogue_cue_text(&parsed[cue].raw).is_none()
+        {
+            continue;
+        }
+
+        let Some(left_end) = parsed[..cue]
+            .iter()
+            .rposition(|line| line.kind != LineKind::Empty)
+        else {
+            continue;
+        };
+        let left_start = parsed[..=left_end]
+            .iter()
+            .rposition(|line| !in_dialogue_context(&line.kind))
+            .map_or(0, |index| index + 1);
+        if left_start > left_end
+            || parsed[left_start].kind != LineKind::Character
+            || parsed[left_start].dual_dialogue.is_some()
+        {
+            continue;
+        }
+
+        let right_end = parsed[cue + 1..]
+            .iter()
+            .position(|line| {
+                !matches!(
+                    line.kind,
+                    LineKind::Dialogue | LineKind::Parenthetical | LineKind::Lyric
+                )
+            })
+            .map_or(parsed.len(), |offset| cue + 1 + offset);
+        for line in &mut parsed[left_start..=left_end] {
+            line.dual_dialogue = Some(DualDialogue::Left);
+        }
+        for line in &mut parsed[cue..right_end] {
+            line.dual_dialogue = Some(DualDialogue::Right);
+        }
+    }
+}
+
+// A line of three or more `=` forces a page break; one `=` opens a synopsis.
+pub fn is_page_break(raw: &str) -> bool {
+    let trimmed = raw.trim();
+    trimmed.chars().count() >= PAGE_BREAK_MIN_MARKERS
+        && trimmed.chars().all(|ch| ch == SYNOPSIS_MARKER)
+}
+
+// `> TEXT <` as the char column where TEXT starts and TEXT itself, trimmed.
+pub fn centered_text(raw: &str) -> Option<(usize, &str)> {
+    let leading = raw.chars().take_while(|ch| ch.is_whitespace()).count();
+    let inner = raw
+        .trim()
+        .strip_prefix(CENTERED_OPEN_MARKER)?
+        .strip_suffix(CENTERED_CLOSE_MARKER)?;
+    let padding = inner.chars().take_while(|ch| ch.is_whitespace()).count();
+    Some((leading + 1 + padding, inner.trim()))
+}
+
+// Leading lines that form the title page: `Key: value` lines, with values
+// optionally continued on indented lines, up to the first blank line. 0 when the
+// script does not open with a known key.
+pub fn title_page_len(lines: &[String]) -> usize {
+    let opens_title_page = lines
+        .first()
+        .and_then(|first| title_page_key(first))
+        .is_some_and(|key| {
+            TITLE_PAGE_OPENING_KEYS
+                .iter()
+                .any(|opening| opening.eq_ignore_ascii_case(key))
+        });
+    if !opens_title_page {
+        return 0;
+    }
+
+    let len = lines
+        .iter()
+        .position(|raw| raw.trim().is_empty())
+        .unwrap_or(lines.len());
+    let well_formed = lines[..len]
+        .iter()
+        .all(|raw| title_page_key(raw).is_some() || is_title_page_continuation(raw));
+    if well_formed { len } else { 0 }
+}
+
+// The entries of the leading title page block, if the script has one.
+pub fn title_page(lines: &[String]) -> Option<TitlePage> {
+    let len = title_page_len(lines);
+    if len == 0 {
+        return None;
+    }
+
+    let mut entries = Vec::<(String, String)>::new();
+    for raw in &lines[..len] {
+        let (_, value) = title_page_value(raw);
+        match title_page_key(raw) {
+            Some(key) => entries.push((key.to_owned(), value.to_owned())),
+            None => {
+                if let Some((_, existing)) = entries.last_mut() {
+                    if !existing.is_empty() {
+                        existing.push('\n');
+                    }
+                    existing.push_str(value);
+                }
+            }
+        }
+    }
+    Some(TitlePage { entries })
+}
+
+// A title page line as the char column where its value starts and the value
+// itself, trimmed. Continuation lines are all value.
+pub fn title_page_value(raw: &str) -> (usize, &str) {
+    let value = match title_page_key(raw) {
+        Some(_) => raw
+            .split_once(TITLE_PAGE_SEPARATOR)
+            .map_or("", |(_, value)| value),
+        None => raw,
+    };
+    let column = raw.chars().count() - value.chars().count()
+        + value.chars().take_while(|ch| ch.is_whitespace()).count();
+    (column, value.trim())
+}
+
+// `Draft date` for `Draft date: 1 May`. Keys are words of letters starting at
+// the first column.
+fn title_page_key(raw: &str) -> Option<&str> {
+    let (key, _) = raw.split_once(TITLE_PAGE_SEPARATOR)?;
+    let valid = key.starts_with(|ch: char| ch.is_alphabetic())
+        && key.chars().all(|ch| ch.is_alphabetic() || ch == ' ');
+    valid.then(|| key.trim_end())
+}
+
+fn is_title_page_continuation(raw: &str) -> bool {
+    raw.starts_with("   ") || raw.starts_with('\t')
+}
+
+// `INT. HOUSE - DAY #12A#` as the char column where the heading text ends and
+// the number between the markers.
+pub fn scene_number(raw: &str) -> Option<(usize, &str)> {
+    let inner = raw.trim_end().strip_suffix(SCENE_NUMBER_MARKER)?;
+    let open = inner.rfind(SCENE_NUMBER_MARKER)?;
+    let number = &inner[open + 1..];
+    let heading = inner[..open].trim_end();
+    let valid = !number.is_empty()
+        && number
+            .chars()
+            .all(|ch| ch.is_alphanumeric() || ch == '.' || ch == '-');
+    (valid && !heading.trim().is_empty()).then(|| (heading.chars().count(), number))
+}
+
+// The cue text without its trailing `^`, or `None` when the line has no marker.
+pub fn dual_dialogue_cue_text(raw: &str) -> Option<&str> {
+    raw.trim_end()
+        .strip_suffix(DUAL_DIALOGUE_MARKER)
+        .map(str::trim_end)
+}
+
+// Splits `SARAH (V.O.)` into the name and the extension inside the trailing
+// parentheses. A lone parenthetical has no name and is not split.
+pub fn split_character_extension(cue: &str) -> Option<(&str, &str)> {
+    let cue = cue.trim();
+    let inner = cue.strip_suffix(')')?;
+    let open = inner.rfind('(')?;
+    let name = inner[..open].trim_end();
+    (!name.is_empty()).then(|| (name, inner[open + 1..].trim()))
+}
+
+// How `raw` would classify in place of line `index`, with the lines above it as
+// already parsed.
+pub(crate) fn classify_in_place(
+    parsed: &[ParsedLine],
+    index: usize,
+    raw: &str,
+    options: &ParseOptions,
+) -> LineKind {
+    let flow = FlowContext::before(&parsed[..index]);
+    classify_line(raw, flow.previous_kind(raw, options), options)
+}
+
+// Writes each line back as `text`, putting its forcing marker in front wherever
+// the text alone would parse as another kind under the lines already written
+// above it. Title page lines stay out of the flow, as in a parse.
+pub(crate) fn pin_kinds(lines: Vec<(&LineKind, String)>, options: &ParseOptions) -> Vec<String> {
+    let mut flow = FlowContext::new();
+    let mut written = Vec::with_capacity(lines.len());
+    for (kind, text) in lines {
+        if *kind == LineKind::TitlePage {
+            written.push(text);
+            continue;
+        }
+
+        let mut parsed_as = classify_line(&text, flow.previous_kind(&text, options), options);
+        let mut text = text;
+        if parsed_as != *kind
+            && !text.is_empty()
+            && let Some(marker) = forcing_marker(kind)
+        {
+            text = format!("{marker}{text}");
+            parsed_as = classify_line(&text, flow.previous_kind(&text, options), options);
+        }
+        flow.push(&parsed_as);
+        written.push(text);
+    }
+    written
+}
+
+fn classify_line(raw: &str, previous_kind: &LineKind, options: &ParseOptions) -> LineKind {
     let trimmed = raw.trim();
 
     if trimmed.is_empty() {
         return LineKind::Empty;
     }
 
-    if is_scene_heading(trimmed) {
-        return LineKind::SceneHeading;
+    if options.all_action {
+        return LineKind::Action;
+    }
+
+    // Checked before forced markers: `>` alone forces a transition.
+    if centered_text(trimmed).is_some() {
+        return LineKind::Centered;
+    }
+
+    if trimmed.starts_with(SECTION_MARKER) {
+        return LineKind::Section;
+    }
+
+    if is_page_break(trimmed) {
+        return LineKind::PageBreak;
+    }
+
+    // `==` is neither a page break nor a synopsis.
+    if let Some(rest) = trimmed.strip_prefix(SYNOPSIS_MARKER)
+        && !rest.starts_with(SYNOPSIS_MARKER)
+    {
+        return LineKind::Synopsis;
+    }
+
+    match forced_marker(trimmed) {
+        Some(FORCED_CHARACTER_MARKER) => return LineKind::Character,
+        Some(FORCED_NON_CHARACTER_MARKER) => {
+            return if in_dialogue_context(previous_kind) {
+                LineKind::Dialogue
+            } else {
+                LineKind::Action
+            };
+        }
+        Some(FORCED_SCENE_HEADING_MARKER) => return LineKind::SceneHeading,
+        Some(FORCED_TRANSITION_MARKER) => return LineKind::Transition,
+        Some(LYRIC_MARKER) => return LineKind::Lyric,
+        _ => {}
+    }
+
+    if is_scene_heading(trimmed, false) {
+        // A bare `INT.` can stay action until the location is typed.
+        let heading = scene_number(trimmed)
+            .and_then(|(column, _)| trimmed.char_indices().nth(column))
+            .map_or(trimmed, |(end, _)| &trimmed[..end]);
+        return if is_scene_heading(heading, options.scene_heading_requires_content) {
+            LineKind::SceneHeading
+        } else {
+            LineKind::Action
+        };
     }
 
     if is_transition(trimmed) {
         return LineKind::Transition;
     }
 
-    if is_character(trimmed) {
+    if is_character(dual_dialogue_cue_text(trimmed).unwrap_or(trimmed)) {
         return LineKind::Character;
     }
 
-    if is_parenthetical(trimmed)
-        && matches!(
-            previous_kind,
-            LineKind::Character | LineKind::Dialogue | LineKind::Parenthetical
-        )
-    {
+    if is_parenthetical(trimmed) && in_dialogue_context(previous_kind) {
         return LineKind::Parenthetical;
     }
 
-    if matches!(
-        previous_kind,
-        LineKind::Character | LineKind::Dialogue | LineKind::Parenthetical
-    ) {
+    if in_dialogue_context(previous_kind) {
         return LineKind::Dialogue;
     }
 
     LineKind::Action
 }
 
-fn is_scene_heading(line: &str) -> bool {
+// A `~` lyric only ever sits in a speech or between blank lines, so it can carry
+// the dialogue context on.
+fn in_dialogue_context(previous_kind: &LineKind) -> bool {
+    matches!(
+        previous_kind,
+        LineKind::Character | LineKind::Dialogue | LineKind::Parenthetical | LineKind::Lyric
+    )
+}
+
+// `.` only forces a heading when followed by a letter or digit so ellipses stay
+// action, and `>text<` is centered text rather than a transition.
+fn forced_marker(trimmed: &str) -> Option<char> {
+    let mut chars = trimmed.chars();
+    let marker = chars.next()?;
+    match marker {
+        FORCED_CHARACTER_MARKER | FORCED_NON_CHARACTER_MARKER | LYRIC_MARKER => Some(marker),
+        FORCED_SCENE_HEADING_MARKER => chars
+            .next()
+            .is_some_and(char::is_alphanumeric)
+            .then_some(marker),
+        FORCED_TRANSITION_MARKER => (!trimmed.ends_with('<')).then_some(marker),
+        _ => None,
+    }
+}
+
+// The leading marker that pins a line to `kind` whatever the heuristic says.
+// `!` only rules out a cue, so a dialogue line keeps its kind from the speech
+// it sits in.
+pub fn forcing_marker(kind: &LineKind) -> Option<char> {
+    match kind {
+        LineKind::SceneHeading => Some(FORCED_SCENE_HEADING_MARKER),
+        LineKind::Action | LineKind::Dialogue => Some(FORCED_NON_CHARACTER_MARKER),
+        LineKind::Character => Some(FORCED_CHARACTER_MARKER),
+        LineKind::Transition => Some(FORCED_TRANSITION_MARKER),
+        LineKind::Lyric => Some(LYRIC_MARKER),
+        _ => None,
+    }
+}
+
+pub fn forced_marker_len(raw: &str) -> usize {
+    let leading = raw.chars().take_while(|ch| ch.is_whitespace()).count();
+    forced_marker(raw.trim()).map_or(0, |_| leading + 1)
+}
+
+pub fn toggle_character_dialogue_marker(raw: &str, kind: &LineKind) -> String {
+    let marker_len = forced_marker_len(raw);
+    let unmarked = if marker_len > 0 {
+        let leading: String = raw.chars().take(marker_len - 1).collect();
+        let rest: String = raw.chars().skip(marker_len).collect();
+        format!("{leading}{rest}")
+    } else {
+        raw.to_owned()
+    };
+    let unmarked_cue = unmarked.trim();
+    let heuristic_character =
+        is_character(dual_dialogue_cue_text(unmarked_cue).unwrap_or(unmarked_cue));
+
+    match (kind == &LineKind::Character, heuristic_character) {
+        (true, true) => format!("{FORCED_NON_CHARACTER_MARKER}{unmarked}"),
+        (false, false) => format!("{FORCED_CHARACTER_MARKER}{unmarked}"),
+        _ => unmarked,
+    }
+}
+
+fn is_scene_heading(line: &str, requires_content: bool) -> bool {
     let upper = line.trim_start().to_uppercase();
     ["INT.", "EXT.", "EST.", "INT/EXT.", "I/E."]
         .iter()
-        .any(|prefix| upper.starts_with(prefix))
+        .any(|prefix| {
+            upper
+                .strip_prefix(prefix)
+                .is_some_and(|rest| !requires_content || !rest.trim().is_empty())
+        })
 }
 
-fn is_transition(line: &str) -> bool {
-    let upper = line.to_uppercase();
-    upper.ends_with(" TO:")
-        || upper == "CUT TO:"
-        || upper == "FADE OUT."
-        || upper == "FADE TO BLACK."
+// All caps only, so a "cut to:" spoken in dialogue stays dialogue.
+pub fn is_transition(line: &str) -> bool {
+    let line = line.trim();
+    if line.chars().any(char::is_lowercase) {
+        return false;
+    }
+    if line.ends_with(" TO:") || TRANSITION_LINES.contains(&line) {
+        return true;
+    }
+
+    // `INTERCUT`, `INTERCUT:` and `INTERCUT WITH:`.
+    line.strip_prefix(INTERCUT_TRANSITION).is_some_and(|rest| {
+        rest.is_empty() || (rest.starts_with([' ', ':']) && rest.ends_with(':'))
+    })
 }
 
 fn is_character(line: &str) -> bool {
+    // The extension may be lowercase or long, so only the name is checked.
+    let line = split_character_extension(line).map_or(line, |(name, _)| name);
     if line.chars().count() > 32 {
         return false;
     }
@@ -96,6 +841,7 @@ fn is_parenthetical(line: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::Position;
 
     #[test]
     fn classifies_basic_fountain_subset() {
@@ -121,4 +867,474 @@ mod tests {
         assert_eq!(parsed[0].kind, LineKind::SceneHeading);
         assert_eq!(parsed[1].kind, LineKind::Action);
     }
+
+    #[test]
+    fn bare_scene_prefix_respects_trailing_content_option() {
+        let doc = Document::from_text("INT.\n\nINT. ROOM\n\nEXT.   ");
+        let strict = ParseOptions {
+            scene_heading_requires_content: true,
+            ..ParseOptions::default()
+        };
+
+        let lenient = parse(&doc);
+        assert_eq!(lenient[0].kind, LineKind::SceneHeading);
+        assert_eq!(lenient[2].kind, LineKind::SceneHeading);
+        assert_eq!(lenient[4].kind, LineKind::SceneHeading);
+
+        let parsed = parse_with_options(&doc, &strict);
+        assert_eq!(parsed[0].kind, LineKind::Action);
+        assert_eq!(parsed[2].kind, LineKind::SceneHeading);
+        assert_eq!(parsed[4].kind, LineKind::Action);
+    }
+
+    #[test]
+    fn txt_files_parse_every_line_as_action() {
+        let doc =
+            Document::from_text("INT. HOUSE - DAY\n\nBOB\nHello.\n(beat)\n\nCUT TO:\n@McCLANE");
+        let parsed = parse_with_options(&doc, &ParseOptions::for_path("notes/todo.TXT"));
+
+        assert!(!ParseOptions::for_path("script.fountain").all_action);
+        assert!(parsed.iter().all(|line| match line.raw.trim() {
+            "" => line.kind == LineKind::Empty,
+            _ => line.kind == LineKind::Action,
+        }));
+    }
+
+    #[test]
+    fn forced_markers_override_character_heuristic() {
+        let doc = Document::from_text(
+            "@McCLANE
+Yippee.
+
+BOB
+!NO WAY
+!LOUD NOISES",
+        );
+        let parsed = parse(&doc);
+
+        assert_eq!(parsed[0].kind, LineKind::Character);
+        assert_eq!(parsed[1].kind, LineKind::Dialogue);
+        assert_eq!(parsed[4].kind, LineKind::Dialogue);
+        assert_eq!(parsed[5].kind, LineKind::Dialogue);
+        assert_eq!(
+            classify_line("!LOUD NOISES", &LineKind::Empty, &ParseOptions::default()),
+            LineKind::Action
+        );
+    }
+
+    #[test]
+    fn all_caps_transitions_without_a_to_ending() {
+        let doc = Document::from_text(
+            "FADE IN:\n\nINT. HOUSE - DAY\n\nBOB\nThen cut to:\n\nDISSOLVE TO:\n\n\
+             EXT. YARD - DAY\n\nINTERCUT WITH:\n\nSMASH CUT TO:\n\nFADE OUT.",
+        );
+        let parsed = parse(&doc);
+
+        assert_eq!(title_page_len(doc.lines()), 0);
+        assert_eq!(parsed[0].kind, LineKind::Transition);
+        assert_eq!(parsed[5].kind, LineKind::Dialogue);
+        assert_eq!(parsed[7].kind, LineKind::Transition);
+        assert_eq!(parsed[9].kind, LineKind::SceneHeading);
+        assert_eq!(parsed[11].kind, LineKind::Transition);
+        assert_eq!(parsed[13].kind, LineKind::Transition);
+        assert_eq!(parsed[15].kind, LineKind::Transition);
+        assert!(is_transition("MATCH CUT TO:") && is_transition("INTERCUT"));
+        assert!(!is_transition("He turns to:") && !is_transition("INTERCUTS"));
+    }
+
+    #[test]
+    fn forced_scene_heading_and_transition_markers() {
+        let doc = Document::from_text(".FLASHBACK\n\n...and then\n\n>Smash cut to:\n\n>THE END<");
+        let parsed = parse(&doc);
+
+        assert_eq!(parsed[0].kind, LineKind::SceneHeading);
+        assert_eq!(parsed[2].kind, LineKind::Action);
+        assert_eq!(parsed[4].kind, LineKind::Transition);
+        assert_ne!(parsed[6].kind, LineKind::Transition);
+        assert_eq!(forced_marker_len(".FLASHBACK"), 1);
+        assert_eq!(forced_marker_len("...and then"), 0);
+        assert_eq!(forced_marker_len(">THE END<"), 0);
+    }
+
+    #[test]
+    fn scene_numbers_are_parsed_out_of_headings() {
+        let doc = Document::from_text(
+            "INT. HOUSE - DAY #12A#\n\nEXT. YARD #1.1#  \n\nINT. #3#\n\nBOB #4#\n\n\
+             INT. LAB #a b#",
+        );
+        let parsed = parse_with_options(
+            &doc,
+            &ParseOptions {
+                scene_heading_requires_content: true,
+                ..ParseOptions::default()
+            },
+        );
+
+        assert_eq!(parsed[0].kind, LineKind::SceneHeading);
+        assert_eq!(parsed[0].scene_number().as_deref(), Some("12A"));
+        assert_eq!(parsed[0].processed_text().trim(), "INT. HOUSE - DAY");
+        assert_eq!(parsed[2].scene_number().as_deref(), Some("1.1"));
+        assert_eq!(parsed[4].kind, LineKind::Action);
+        assert_eq!(parsed[6].scene_number(), None);
+        assert_eq!(parsed[8].scene_number(), None);
+        assert_eq!(scene_number("INT. HOUSE #7#"), Some((10, "7")));
+    }
+
+    #[test]
+    fn character_cues_keep_their_extensions() {
+        let doc = Document::from_text(
+            "SARAH (V.O.)\nHello.\n\nTOM (cont'd)\nStill here.\n\nBOB\n(smiling)\nHi.",
+        );
+        let parsed = parse(&doc);
+
+        assert_eq!(parsed[0].kind, LineKind::Character);
+        assert_eq!(parsed[0].character_extension().as_deref(), Some("V.O."));
+        assert_eq!(parsed[3].kind, LineKind::Character);
+        assert_eq!(parsed[3].character_extension().as_deref(), Some("cont'd"));
+        assert_eq!(parsed[3].processed_text().trim(), "TOM (CONT'D)");
+        assert_eq!(parsed[6].character_extension(), None);
+        assert_eq!(parsed[7].kind, LineKind::Parenthetical);
+        assert_eq!(parsed[7].character_extension(), None);
+        assert_eq!(split_character_extension("(smiling)"), None);
+    }
+
+    #[test]
+    fn lines_after_a_parenthetical_return_to_dialogue() {
+        let mut document = Document::from_text("BOB\nHello.\n(beat)\nBye.\n\nShe leaves.");
+        let mut parsed = parse(&document);
+        assert_eq!(parsed[2].kind, LineKind::Parenthetical);
+        assert_eq!(parsed[3].kind, LineKind::Dialogue);
+        assert_eq!(parsed[5].kind, LineKind::Action);
+
+        // Enter after the parenthetical, then typing, stays in the block.
+        let options = ParseOptions::default();
+        let next = document.insert_newline(Position { line: 2, column: 6 });
+        document.insert_text(next, "(quietly)");
+        let next = document.insert_newline(Position { line: 3, column: 9 });
+        document.insert_text(next, "Really.");
+        reparse_from(&mut parsed, &document, &options, 2);
+        assert_eq!(parsed, parse(&document));
+        assert_eq!(parsed[3].kind, LineKind::Parenthetical);
+        assert_eq!(parsed[4].kind, LineKind::Dialogue);
+        assert_eq!(parsed[5].kind, LineKind::Dialogue);
+    }
+
+    #[test]
+    fn one_blank_line_keeps_a_speech_open_when_enabled() {
+        let doc = Document::from_text("BOB\nHello.\n\nStill me.\n(beat)\n\nBye.\n\n\nShe leaves.");
+        let spanning = ParseOptions {
+            dialogue_spans_single_blank: true,
+            ..ParseOptions::default()
+        };
+
+        let standard = parse(&doc);
+        assert_eq!(standard[3].kind, LineKind::Action);
+        assert_eq!(standard[6].kind, LineKind::Action);
+
+        let parsed = parse_with_options(&doc, &spanning);
+        assert_eq!(parsed[2].kind, LineKind::Empty);
+        assert_eq!(parsed[3].kind, LineKind::Dialogue);
+        assert_eq!(parsed[4].kind, LineKind::Parenthetical);
+        assert_eq!(parsed[6].kind, LineKind::Dialogue);
+        assert_eq!(parsed[9].kind, LineKind::Action);
+    }
+
+    #[test]
+    fn forced_action_after_one_blank_ends_the_speech() {
+        let mut document = Document::from_text("BOB\nHello.\n\n!BOOM\nStill me.");
+        let options = ParseOptions {
+            dialogue_spans_single_blank: true,
+            ..ParseOptions::default()
+        };
+        let mut parsed = parse_with_options(&document, &options);
+        assert_eq!(parsed[3].kind, LineKind::Action);
+        assert_eq!(parsed[4].kind, LineKind::Action);
+
+        // Dropping the marker lets the speech carry on through the blank.
+        document.backspace(Position { line: 3, column: 1 });
+        reparse_from(&mut parsed, &document, &options, 3);
+        assert_eq!(parsed, parse_with_options(&document, &options));
+        assert_eq!(parsed[3].kind, LineKind::Character);
+
+        document.insert_char(Position { line: 3, column: 0 }, '!');
+        reparse_from(&mut parsed, &document, &options, 3);
+        assert_eq!(parsed, parse_with_options(&document, &options));
+        assert_eq!(parsed[3].kind, LineKind::Action);
+        assert_eq!(parsed[4].kind, LineKind::Action);
+    }
+
+    #[test]
+    fn two_blank_lines_end_a_speech_and_reparse_agrees() {
+        let mut document = Document::from_text("BOB\nHello.\n\nStill me.\n\nAnd more.");
+        let options = ParseOptions {
+            dialogue_spans_single_blank: true,
+            ..ParseOptions::default()
+        };
+        let mut parsed = parse_with_options(&document, &options);
+        assert_eq!(parsed[3].kind, LineKind::Dialogue);
+        assert_eq!(parsed[5].kind, LineKind::Dialogue);
+
+        // A second blank below the first speech line ends the block there.
+        document.insert_newline(Position { line: 2, column: 0 });
+        reparse_from(&mut parsed, &document, &options, 2);
+        assert_eq!(parsed, parse_with_options(&document, &options));
+        assert_eq!(parsed[4].kind, LineKind::Action);
+        assert_eq!(parsed[6].kind, LineKind::Action);
+
+        document.backspace(Position { line: 3, column: 0 });
+        reparse_from(&mut parsed, &document, &options, 2);
+        assert_eq!(parsed, parse_with_options(&document, &options));
+        assert_eq!(parsed[5].kind, LineKind::Dialogue);
+    }
+
+    #[test]
+    fn forced_markers_are_stripped_from_processed_text() {
+        let doc =
+            Document::from_text("@mcdonalds\nTwo burgers.\n\n.flashback\n\n!EXPLOSION\n\n...and");
+        let parsed = parse(&doc);
+
+        assert_eq!(parsed[0].kind, LineKind::Character);
+        assert_eq!(parsed[0].processed_text().trim(), "MCDONALDS");
+        assert_eq!(parsed[1].kind, LineKind::Dialogue);
+        assert_eq!(parsed[3].processed_text().trim(), "FLASHBACK");
+        assert_eq!(parsed[5].kind, LineKind::Action);
+        assert_eq!(parsed[5].processed_text(), "EXPLOSION");
+        assert_eq!(parsed[7].kind, LineKind::Action);
+        assert_eq!(parsed[7].processed_text(), "...and");
+    }
+
+    #[test]
+    fn tilde_lines_are_lyrics_even_inside_a_speech() {
+        let doc =
+            Document::from_text("BOB\n~Happy birthday to you\n~ to you\n(beat)\nAnyway.\n\n~La la");
+        let parsed = parse(&doc);
+
+        assert_eq!(parsed[1].kind, LineKind::Lyric);
+        assert_eq!(parsed[2].kind, LineKind::Lyric);
+        assert_eq!(parsed[3].kind, LineKind::Parenthetical);
+        assert_eq!(parsed[4].kind, LineKind::Dialogue);
+        assert_eq!(parsed[6].kind, LineKind::Lyric);
+        assert_eq!(parsed[1].indent_width(), LineKind::Dialogue.indent_width());
+        assert_eq!(parsed[1].processed_text().trim(), "Happy birthday to you");
+        assert_eq!(forced_marker_len("  ~La la"), 3);
+    }
+
+    #[test]
+    fn toggles_character_dialogue_markers_round_trip() {
+        let forced_dialogue = toggle_character_dialogue_marker("NO WAY", &LineKind::Character);
+        assert_eq!(forced_dialogue, "!NO WAY");
+        assert_eq!(
+            toggle_character_dialogue_marker(&forced_dialogue, &LineKind::Dialogue),
+            "NO WAY"
+        );
+
+        let forced_character = toggle_character_dialogue_marker("McClane", &LineKind::Dialogue);
+        assert_eq!(forced_character, "@McClane");
+        assert_eq!(
+            toggle_character_dialogue_marker(&forced_character, &LineKind::Character),
+            "McClane"
+        );
+    }
+
+    #[test]
+    fn caret_cue_pairs_with_the_block_above_as_dual_dialogue() {
+        let doc = Document::from_text(
+            "INT. BAR - NIGHT\n\nBRICK\nScrew retirement.\n\nSTEEL ^\n(grinning)\n\
+             Screw retirement.\n\nThey drink.",
+        );
+        let parsed = parse(&doc);
+        let sides = parsed
+            .iter()
+            .map(|line| line.dual_dialogue)
+            .collect::<Vec<_>>();
+
+        let left = Some(DualDialogue::Left);
+        let right = Some(DualDialogue::Right);
+        assert_eq!(
+            sides,
+            [
+                None, None, left, left, None, right, right, right, None, None
+            ]
+        );
+        assert_eq!(parsed[5].kind, LineKind::Character);
+        assert_eq!(parsed[6].kind, LineKind::Parenthetical);
+        assert_eq!(parsed[5].processed_text().trim(), "STEEL");
+    }
+
+    #[test]
+    fn caret_cue_without_a_block_above_stays_single() {
+        let doc = Document::from_text("The door opens.\n\nSTEEL ^\nHello?");
+        let parsed = parse(&doc);
+
+        assert_eq!(parsed[2].kind, LineKind::Character);
+        assert!(parsed.iter().all(|line| line.dual_dialogue.is_none()));
+        assert_eq!(parsed[2].processed_text().trim(), "STEEL");
+    }
+
+    #[test]
+    fn caret_is_stripped_from_an_unpaired_cue_after_a_heading() {
+        let doc = Document::from_text("INT. BAR - NIGHT\n\nBOB ^\nHello?");
+        let parsed = parse(&doc);
+
+        assert_eq!(parsed[2].kind, LineKind::Character);
+        assert_eq!(parsed[2].dual_dialogue, None);
+        assert_eq!(parsed[2].processed_text().trim(), "BOB");
+    }
+
+    #[test]
+    fn centered_text_is_not_a_transition_and_is_padded() {
+        let doc = Document::from_text("> THE END <\n>CUT TO BLACK\n>x<");
+        let parsed = parse(&doc);
+
+        assert_eq!(parsed[0].kind, LineKind::Centered);
+        assert_eq!(parsed[1].kind, LineKind::Transition);
+        assert_eq!(parsed[2].kind, LineKind::Centered);
+        assert_eq!(centered_text(&parsed[0].raw), Some((2, "THE END")));
+        assert_eq!(
+            parsed[0].processed_text(),
+            format!("{}THE END", " ".repeat(27))
+        );
+    }
+
+    #[test]
+    fn boneyard_hides_whole_lines_across_line_breaks() {
+        let doc = Document::from_text(
+            "BOB\n/* cut this\n\nMARY\nstill cut */\nHello.\nSee /* aside */ here.\n/* gone */",
+        );
+        let parsed = parse(&doc);
+        let kinds = parsed
+            .iter()
+            .map(|line| line.kind.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            [
+                LineKind::Character,
+                LineKind::Boneyard,
+                LineKind::Boneyard,
+                LineKind::Boneyard,
+                LineKind::Boneyard,
+                LineKind::Dialogue,
+                LineKind::Dialogue,
+                LineKind::Boneyard,
+            ]
+        );
+    }
+
+    #[test]
+    fn sections_and_synopses_are_their_own_kinds() {
+        let doc = Document::from_text("# ACT ONE\n## The heist\n= They plan it.\n===\nINT. BANK");
+        let parsed = parse(&doc);
+
+        assert_eq!(parsed[0].kind, LineKind::Section);
+        assert_eq!(parsed[0].section_depth(), 1);
+        assert_eq!(parsed[1].section_depth(), 2);
+        assert_eq!(parsed[2].kind, LineKind::Synopsis);
+        assert_eq!(parsed[2].section_depth(), 0);
+        assert_eq!(parsed[3].kind, LineKind::PageBreak);
+        assert_eq!(parsed[4].kind, LineKind::SceneHeading);
+    }
+
+    #[test]
+    fn page_breaks_need_three_or_more_equals_signs() {
+        let doc = Document::from_text(
+            "==
+
+  =====  
+
+=== end
+
+/*
+===
+*/",
+        );
+        let parsed = parse(&doc);
+
+        assert_eq!(parsed[0].kind, LineKind::Action);
+        assert_eq!(parsed[2].kind, LineKind::PageBreak);
+        assert_ne!(parsed[4].kind, LineKind::PageBreak);
+        assert_eq!(parsed[7].kind, LineKind::Boneyard);
+        assert!(is_page_break("===") && !is_page_break("= synopsis"));
+    }
+
+    #[test]
+    fn reparse_from_an_edit_far_down_matches_a_full_parse() {
+        let scene = "INT. ROOM - DAY\n\nBOB\nHello.\n(beat)\nBye.\n\nShe leaves.\n\nCUT TO:\n\n";
+        let mut document = Document::from_text(&scene.repeat(500));
+        let mut parsed = parse(&document);
+        let options = ParseOptions::default();
+        let at = |line, column| Position { line, column };
+        let line = 4_006;
+        assert_eq!(document.line(line), Some("BOB"));
+
+        // Lowercasing the cue turns the dialogue below it into action.
+        document.delete_range(at(line, 0), at(line, 3));
+        document.insert_text(at(line, 0), "bob");
+        let outcome = reparse_from(&mut parsed, &document, &options, line);
+        assert_eq!(parsed, parse(&document));
+        assert!(outcome.reparsed.len() < 10);
+        assert!(!outcome.boneyard_changed);
+
+        // Opening a boneyard hides everything below it.
+        document.insert_text_bulk(at(line, 0), "/*\n");
+        let outcome = reparse_from(&mut parsed, &document, &options, line);
+        assert_eq!(parsed, parse(&document));
+        assert!(outcome.boneyard_changed);
+
+        document.delete_range(at(line, 0), at(line + 1, 0));
+        reparse_from(&mut parsed, &document, &options, line);
+        assert_eq!(parsed, parse(&document));
+
+        // A dual dialogue marker pairs with the cue above the edit.
+        let cue = line + 16;
+        let cue_len = document.line_len_chars(cue);
+        assert_eq!(document.line(cue), Some("She leaves."));
+        document.delete_range(at(cue, 0), at(cue, cue_len));
+        document.insert_text(at(cue, 0), "ALICE ^");
+        reparse_from(&mut parsed, &document, &options, cue);
+        assert_eq!(parsed, parse(&document));
+        assert_eq!(parsed[cue - 5].dual_dialogue, Some(DualDialogue::Left));
+    }
+
+    #[test]
+    fn title_page_block_parses_until_the_first_blank_line() {
+        let mut document = Document::from_text(
+            "Title: Big Fish\nCredit: written by\nAuthor: John August\nContact:\n   \
+             Agency\n   Los Angeles\n\nFADE IN:\n\nINT. HOUSE - DAY",
+        );
+        let mut parsed = parse(&document);
+        assert!(
+            parsed[..6]
+                .iter()
+                .all(|line| line.kind == LineKind::TitlePage)
+        );
+        assert_eq!(parsed[6].kind, LineKind::Empty);
+        assert_ne!(parsed[7].kind, LineKind::TitlePage);
+        assert_eq!(parsed[9].kind, LineKind::SceneHeading);
+        assert_eq!(parsed[0].processed_text().trim(), "Big Fish");
+        assert_eq!(title_page_value("Title:  Big Fish"), (8, "Big Fish"));
+
+        let page = title_page(document.lines()).unwrap();
+        assert_eq!(page.get("author"), Some("John August"));
+        assert_eq!(page.get("Contact"), Some("Agency\nLos Angeles"));
+        assert_eq!(document.title().as_deref(), Some("Big Fish"));
+
+        // A script opening on `FADE IN:` has no title page.
+        let untitled = Document::from_text("FADE IN:\n\nINT. HOUSE - DAY");
+        assert_eq!(title_page_len(untitled.lines()), 0);
+        assert_eq!(untitled.title(), None);
+
+        // Breaking the first key turns the block back into ordinary lines.
+        let options = ParseOptions::default();
+        document.insert_char(Position { line: 0, column: 5 }, 'x');
+        reparse_from(&mut parsed, &document, &options, 0);
+        assert_eq!(parsed, parse(&document));
+        assert_eq!(parsed[0].kind, LineKind::Action);
+
+        document.backspace(Position { line: 0, column: 6 });
+        reparse_from(&mut parsed, &document, &options, 0);
+        assert_eq!(parsed, parse(&document));
+        assert_eq!(parsed[3].kind, LineKind::TitlePage);
+    }
 }
@@ -2,16 +2,317 @@ mod fountain;
 mod markdown;
 mod shared;
 
+use std::ops::Range;
+
 use crate::buffer::Document;
-use crate::model::{DocumentFormat, ParsedLine};
+use crate::model::{DocumentFormat, IndentWidths, ParseOptions, ParsedLine};
+
+pub(crate) use fountain::{DUAL_DIALOGUE_MARKER, classify_in_place, pin_kinds};
+pub use fountain::{
+    centered_text, dual_dialogue_cue_text, forced_marker_len, is_page_break, is_transition,
+    scene_number, split_character_extension, title_page, title_page_len, title_page_value,
+    toggle_character_dialogue_marker,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReparseOutcome {
+    pub reparsed: Range<usize>,
+    pub boneyard_changed: bool,
+}
 
 pub fn parse_document(document: &Document) -> Vec<ParsedLine> {
     parse_document_with_format(document, DocumentFormat::Fountain)
 }
 
 pub fn parse_document_with_format(document: &Document, format: DocumentFormat) -> Vec<ParsedLine> {
-    match format {
-        DocumentFormat::Fountain => fountain::parse(document),
+    parse_document_with_options(document, format, &ParseOptions::default())
+}
+
+pub fn parse_document_with_options(
+    document: &Document,
+    format: DocumentFormat,
+    options: &ParseOptions,
+) -> Vec<ParsedLine> {
+    let mut parsed = match format {
+        DocumentFormat::Fountain => fountain::parse_with_options(document, options),
         DocumentFormat::Markdown => markdown::parse(document),
+    };
+    apply_indent_widths(&mut parsed, &options.indent_widths);
+    parsed
+}
+
+// Brings `parsed` up to date after an edit that left every line above
+// `first_line` untouched. Markdown has no line-to-line state and is parsed whole.
+pub fn reparse_from(
+    parsed: &mut Vec<ParsedLine>,
+    document: &Document,
+    format: DocumentFormat,
+    options: &ParseOptions,
+    first_line: usize,
+) -> ReparseOutcome {
+    let outcome = match format {
+        DocumentFormat::Fountain => fountain::reparse_from(parsed, document, options, first_line),
+        DocumentFormat::Markdown => {
+            *parsed = markdown::parse(document);
+            ReparseOutcome {
+                reparsed: 0..parsed.len(),
+                boneyard_changed: false,
+            }
+        }
+    };
+    apply_indent_widths(
+        &mut parsed[outcome.reparsed.clone()],
+        &options.indent_widths,
+    );
+    outcome
+}
+
+// A reparse handed out in bounded steps, for edits too large to classify in a
+// single frame. Until it finishes, lines it has not reached read as action.
+#[derive(Debug)]
+pub struct ChunkedReparse(ChunkedState);
+
+#[derive(Debug)]
+enum ChunkedState {
+    Fountain(fountain::PendingReparse),
+    Finished(ReparseOutcome),
+}
+
+// Starts a chunked `reparse_from`. `parsed` lines up with `document` again as
+// soon as this returns; any edit before the reparse finishes must go through
+// `ChunkedReparse::extend`.
+pub fn begin_chunked_reparse(
+    parsed: &mut Vec<ParsedLine>,
+    document: &Document,
+    format: DocumentFormat,
+    options: &ParseOptions,
+    first_line: usize,
+) -> ChunkedReparse {
+    match format {
+        DocumentFormat::Fountain => {
+            let (pending, placeholders) =
+                fountain::PendingReparse::begin(parsed, document, first_line);
+            apply_indent_widths(&mut parsed[placeholders], &options.indent_widths);
+            ChunkedReparse(ChunkedState::Fountain(pending))
+        }
+        DocumentFormat::Markdown => {
+            let outcome = reparse_from(parsed, document, format, options, first_line);
+            ChunkedReparse(ChunkedState::Finished(outcome))
+        }
+    }
+}
+
+impl ChunkedReparse {
+    // Takes in an edit made at `first_line` before the reparse finished, instead
+    // of dropping the work done so far for a full reparse.
+    pub fn extend(
+        &mut self,
+        parsed: &mut Vec<ParsedLine>,
+        document: &Document,
+        format: DocumentFormat,
+        options: &ParseOptions,
+        first_line: usize,
+    ) {
+        match &mut self.0 {
+            ChunkedState::Fountain(pending) => {
+                let placeholders = pending.extend(parsed, document, first_line);
+                apply_indent_widths(&mut parsed[placeholders], &options.indent_widths);
+            }
+            ChunkedState::Finished(outcome) => {
+                let again = reparse_from(parsed, document, format, options, first_line);
+                *outcome = ReparseOutcome {
+                    reparsed: outcome.reparsed.start.min(again.reparsed.start)
+                        ..outcome.reparsed.end.max(again.reparsed.end),
+                    boneyard_changed: outcome.boneyard_changed || again.boneyard_changed,
+                };
+            }
+        }
+    }
+
+    // Classifies up to `max_lines` more lines; returns the outcome once done.
+    pub fn step(
+        &mut self,
+        parsed: &mut [ParsedLine],
+        document: &Document,
+        options: &ParseOptions,
+        max_lines: usize,
+    ) -> Option<ReparseOutcome> {
+        match &mut self.0 {
+            ChunkedState::Finished(outcome) => Some(outcome.clone()),
+            ChunkedState::Fountain(pending) => {
+                let (classified, outcome) = pending.step(parsed, document, options, max_lines);
+                apply_indent_widths(&mut parsed[classified], &options.indent_widths);
+                let outcome = outcome?;
+                self.0 = ChunkedState::Finished(outcome.clone());
+                Some(outcome)
+            }
+        }
+    }
+}
+
+fn apply_indent_widths(parsed: &mut [ParsedLine], indent_widths: &IndentWidths) {
+    for line in parsed {
+        line.indent = indent_widths.for_kind(&line.kind);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::model::{LineKind, Position};
+
+    const SCENE: &str = "INT. ROOM - DAY\n\nBOB\nHello there.\n(beat)\nBye.\n\n\
+                         She leaves the room.\n\nCUT TO:\n\n";
+    const PASTE_BYTES: usize = 1 << 20;
+    const CHUNK_LINES: usize = 4_000;
+
+    // A 1MB paste into the middle of a short script, parsed before the paste.
+    fn pasted_megabyte() -> (Document, Vec<ParsedLine>, usize) {
+        let mut document = Document::from_text("FADE IN:\n\nBOB\nHi.\n\n/* cut */\nThe end.");
+        let parsed = parse_document(&document);
+        let line = 4;
+        document.insert_text_bulk(
+            Position { line, column: 0 },
+            &SCENE.repeat(PASTE_BYTES / SCENE.len()),
+        );
+        (document, parsed, line)
+    }
+
+    #[test]
+    fn indent_widths_from_the_options_reach_every_parsed_line() {
+        let options = ParseOptions {
+            indent_widths: IndentWidths {
+                character: 21,
+                ..IndentWidths::default()
+            },
+            ..ParseOptions::default()
+        };
+        let mut document = Document::from_text("INT. HOUSE - DAY\n\nBOB\nHi.");
+        let mut parsed = parse_document_with_options(&document, DocumentFormat::Fountain, &options);
+        assert_eq!(parsed[2].indent_width(), 21);
+        assert_eq!(parsed[2].processed_text(), format!("{}BOB", " ".repeat(21)));
+        assert_eq!(
+            parsed[0].indent_width(),
+            LineKind::SceneHeading.indent_width()
+        );
+
+        document.insert_text(Position { line: 3, column: 3 }, "\n\nANN\nYes.");
+        reparse_from(
+            &mut parsed,
+            &document,
+            DocumentFormat::Fountain,
+            &options,
+            3,
+        );
+        assert_eq!(parsed[5].kind, LineKind::Character);
+        assert_eq!(parsed[5].indent_width(), 21);
+    }
+
+    #[test]
+    fn chunked_reparse_of_a_large_paste_matches_a_full_parse() {
+        let (document, mut parsed, line) = pasted_megabyte();
+        let options = ParseOptions::default();
+        let format = DocumentFormat::Fountain;
+
+        let mut chunked = begin_chunked_reparse(&mut parsed, &document, format, &options, line);
+        assert_eq!(parsed.len(), document.line_count());
+        assert_eq!(parsed[line + 2].kind, LineKind::Action);
+        let mut steps = 1;
+        let outcome = loop {
+            if let Some(outcome) = chunked.step(&mut parsed, &document, &options, CHUNK_LINES) {
+                break outcome;
+            }
+            steps += 1;
+        };
+
+        assert_eq!(parsed, parse_document(&document));
+        assert_eq!(steps, outcome.reparsed.len().div_ceil(CHUNK_LINES));
+        assert!(!outcome.boneyard_changed);
+    }
+
+    #[test]
+    fn chunked_reparse_takes_in_edits_made_before_it_finishes() {
+        let (mut document, mut parsed, line) = pasted_megabyte();
+        let options = ParseOptions::default();
+        let format = DocumentFormat::Fountain;
+        let mut chunked = begin_chunked_reparse(&mut parsed, &document, format, &options, line);
+        let step =
+            |chunked: &mut ChunkedReparse, parsed: &mut Vec<ParsedLine>, document: &Document| {
+                chunked.step(parsed, document, &options, CHUNK_LINES)
+            };
+        assert!(step(&mut chunked, &mut parsed, &document).is_none());
+
+        // Above the lines already classified, then far below them in lines it has
+        // not reached, then joining two lines it has.
+        document.insert_text(Position { line: 2, column: 0 }, "ANN\nWait.\n\n");
+        chunked.extend(&mut parsed, &document, format, &options, 2);
+        assert_eq!(parsed.len(), document.line_count());
+        assert!(step(&mut chunked, &mut parsed, &document).is_none());
+
+        let far = document.line_count() - 100;
+        document.insert_text(
+            Position {
+                line: far,
+                column: 0,
+            },
+            "/* cut\n",
+        );
+        chunked.extend(&mut parsed, &document, format, &options, far);
+        assert!(step(&mut chunked, &mut parsed, &document).is_none());
+
+        document.join_lines(CHUNK_LINES);
+        chunked.extend(&mut parsed, &document, format, &options, CHUNK_LINES);
+        assert_eq!(parsed.len(), document.line_count());
+
+        let mut steps = 0;
+        let outcome = loop {
+            steps += 1;
+            if let Some(outcome) = step(&mut chunked, &mut parsed, &document) {
+                break outcome;
+            }
+        };
+        assert!(steps > 1);
+        assert!(outcome.reparsed.start <= 2);
+        assert!(outcome.boneyard_changed);
+        assert_eq!(parsed, parse_document(&document));
+    }
+
+    // The worst frame of a chunked 1MB paste against reparsing it in one go.
+    // Wall-clock timing is too noisy for the unit suite; run with `--ignored`.
+    #[test]
+    #[ignore = "timing-sensitive"]
+    fn chunked_reparse_keeps_each_step_well_under_a_one_shot_reparse() {
+        let (document, parsed, line) = pasted_megabyte();
+        let options = ParseOptions::default();
+        let format = DocumentFormat::Fountain;
+
+        let mut one_shot = parsed.clone();
+        let started = Instant::now();
+        reparse_from(&mut one_shot, &document, format, &options, line);
+        let one_shot_time = started.elapsed();
+
+        let mut chunked_parsed = parsed;
+        let started = Instant::now();
+        let mut chunked =
+            begin_chunked_reparse(&mut chunked_parsed, &document, format, &options, line);
+        let mut slowest_step = started.elapsed();
+        loop {
+            let started = Instant::now();
+            let done = chunked
+                .step(&mut chunked_parsed, &document, &options, CHUNK_LINES)
+                .is_some();
+            slowest_step = slowest_step.max(started.elapsed());
+            if done {
+                break;
+            }
+        }
+
+        assert_eq!(chunked_parsed, one_shot);
+        assert!(
+            slowest_step < one_shot_time / 3 || slowest_step < Duration::from_millis(2),
+            "slowest step {slowest_step:?} against one-shot {one_shot_time:?}"
+        );
     }
 }
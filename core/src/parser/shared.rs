@@ -7,10 +7,12 @@ pub(super) fn parsed_line(
     markdown_heading_level: Option<u8>,
 ) -> ParsedLine {
     ParsedLine {
+        indent: kind.indent_width(),
         kind,
         raw: raw.to_owned(),
         script_links: extract_script_links(raw),
         markdown_heading_level,
+        dual_dialogue: None,
     }
 }
 
@@ -0,0 +1,204 @@
+use crate::buffer::Document;
+use crate::model::{LineKind, ParseOptions, ParsedLine, Position};
+use crate::parser::{classify_in_place, forced_marker_len};
+
+const SCENE_TIME_SUFFIXES: [&str; 6] = [
+    " - DAY",
+    " - NIGHT",
+    " - MORNING",
+    " - EVENING",
+    " - LATER",
+    " - CONTINUOUS",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarkerEdit {
+    pub line: usize,
+    pub marker: char,
+    pub kind_before: LineKind,
+    pub kind_after: LineKind,
+    pub before: String,
+    pub after: String,
+}
+
+// Dry run over a parsed Fountain document: lists lines whose heuristic
+// classification is ambiguous and the forced marker that would pin them down.
+// Each marked line is classified again under `options`, and a marker that would
+// not produce its kind there is dropped.
+pub fn propose_forced_markers(parsed: &[ParsedLine], options: &ParseOptions) -> Vec<MarkerEdit> {
+    parsed
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| forced_marker_len(&line.raw) == 0)
+        .filter_map(|(index, line)| {
+            let (marker, kind_after) = proposed_marker(parsed, index)?;
+            let column = leading_whitespace(&line.raw);
+            let mut after = line.raw.clone();
+            after.insert(byte_index(&after, column), marker);
+            if classify_in_place(parsed, index, &after, options) != kind_after {
+                return None;
+            }
+            Some(MarkerEdit {
+                line: index,
+                marker,
+                kind_before: line.kind.clone(),
+                kind_after,
+                before: line.raw.clone(),
+                after,
+            })
+        })
+        .collect()
+}
+
+// Applies edits whose line still matches the previewed text; returns how many landed.
+pub fn apply_marker_edits(document: &mut Document, edits: &[MarkerEdit]) -> usize {
+    let mut applied = 0;
+    for edit in edits {
+        if document.line(edit.line) != Some(edit.before.as_str()) {
+            continue;
+        }
+        let column = leading_whitespace(&edit.before);
+        document.insert_char(
+            Position {
+                line: edit.line,
+                column,
+            },
+            edit.marker,
+        );
+        applied += 1;
+    }
+    applied
+}
+
+fn proposed_marker(parsed: &[ParsedLine], index: usize) -> Option<(char, LineKind)> {
+    let line = &parsed[index];
+    let trimmed = line.raw.trim();
+    let next_is_blank = parsed
+        .get(index + 1)
+        .is_none_or(|next| next.kind == LineKind::Empty);
+    let previous_is_blank = index == 0 || parsed[index - 1].kind == LineKind::Empty;
+
+    match line.kind {
+        // A cue with nothing under it is usually a shouted action line.
+        LineKind::Character if next_is_blank => Some(('!', LineKind::Action)),
+        LineKind::Action if previous_is_blank && next_is_blank && is_uppercase(trimmed) => {
+            if SCENE_TIME_SUFFIXES
+                .iter()
+                .any(|suffix| trimmed.ends_with(suffix))
+            {
+                Some(('.', LineKind::SceneHeading))
+            } else if trimmed.ends_with(':') {
+                Some(('>', LineKind::Transition))
+            } else {
+                None
+            }
+        }
+        // Mixed-case names such as "McCLANE" fail the all-caps cue check.
+        LineKind::Action if previous_is_blank && !next_is_blank && is_mixed_case_cue(trimmed) => {
+            Some(('@', LineKind::Character))
+        }
+        _ => None,
+    }
+}
+
+fn is_uppercase(text: &str) -> bool {
+    text.chars().any(char::is_alphabetic) && !text.chars().any(char::is_lowercase)
+}
+
+fn is_mixed_case_cue(text: &str) -> bool {
+    let words = text.split_whitespace().count();
+    let upper = text.chars().filter(|ch| ch.is_uppercase()).count();
+    let lower = text.chars().filter(|ch| ch.is_lowercase()).count();
+    (1..=3).contains(&words) && lower > 0 && upper > lower * 2 && !text.ends_with(['.', '!', '?'])
+}
+
+fn leading_whitespace(raw: &str) -> usize {
+    raw.chars().take_while(|ch| ch.is_whitespace()).count()
+}
+
+fn byte_index(text: &str, column: usize) -> usize {
+    text.char_indices()
+        .nth(column)
+        .map_or(text.len(), |(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::DocumentFormat;
+    use crate::parser::{parse_document, parse_document_with_options};
+
+    fn proposals(text: &str) -> Vec<(usize, char, String)> {
+        let document = Document::from_text(text);
+        propose_forced_markers(&parse_document(&document), &ParseOptions::default())
+            .into_iter()
+            .map(|edit| (edit.line, edit.marker, edit.after))
+            .collect()
+    }
+
+    #[test]
+    fn dry_run_lists_low_confidence_lines() {
+        let text = "BOOM\n\nHe turns to:\n\nBACK AT THE RANCH - NIGHT\n\nSMASH CUT:\n\n\
+                    McCLANE\nYippee.";
+
+        // Prose ending in "to:" is not a transition, so it needs no marker.
+        assert_eq!(
+            proposals(text),
+            vec![
+                (0, '!', "!BOOM".to_string()),
+                (4, '.', ".BACK AT THE RANCH - NIGHT".to_string()),
+                (6, '>', ">SMASH CUT:".to_string()),
+                (8, '@', "@McCLANE".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn dry_run_skips_confident_and_forced_lines() {
+        let text = "INT. HOUSE - DAY\n\nJOHN\nHello.\n\nCUT TO:\n\n!BOOM\n\n  @McCLANE\nHi.";
+
+        assert!(proposals(text).is_empty());
+    }
+
+    #[test]
+    fn applying_edits_reclassifies_and_skips_stale_lines() {
+        let mut document = Document::from_text("BOOM\n\nBACK AT THE RANCH - NIGHT");
+        let edits = propose_forced_markers(&parse_document(&document), &ParseOptions::default());
+        assert_eq!(edits.len(), 2);
+
+        document.insert_text(Position { line: 2, column: 0 }, "X");
+        assert_eq!(apply_marker_edits(&mut document, &edits), 1);
+        assert_eq!(document.line(0), Some("!BOOM"));
+        assert_eq!(parse_document(&document)[0].kind, LineKind::Action);
+    }
+
+    #[test]
+    fn dry_run_checks_each_marker_under_the_active_options() {
+        let document = Document::from_text("BOB\nHi there.\n\nBOOM\n\nShe runs.");
+        let options = ParseOptions {
+            dialogue_spans_single_blank: true,
+            ..ParseOptions::default()
+        };
+        let parsed = parse_document_with_options(&document, DocumentFormat::Fountain, &options);
+        assert_eq!(parsed[3].kind, LineKind::Character);
+
+        let edits = propose_forced_markers(&parsed, &options);
+        assert_eq!(edits.len(), 1);
+        assert_eq!((edits[0].line, edits[0].marker), (3, '!'));
+
+        let mut marked = document.clone();
+        apply_marker_edits(&mut marked, &edits);
+        let reparsed = parse_document_with_options(&marked, DocumentFormat::Fountain, &options);
+        assert_eq!(reparsed[3].kind, edits[0].kind_after);
+    }
+
+    #[test]
+    fn dry_run_drops_markers_that_would_not_produce_their_kind() {
+        // Under the speech, `!` would keep the line as dialogue, not action.
+        let document = Document::from_text("BOB\nHi there.\nBOOM\n\nShe runs.");
+        let parsed = parse_document(&document);
+        assert_eq!(parsed[2].kind, LineKind::Character);
+
+        assert!(propose_forced_markers(&parsed, &ParseOptions::default()).is_empty());
+    }
+}
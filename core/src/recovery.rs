@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use crate::buffer::Document;
+use crate::model::{Cursor, Selection};
+
+// Caret and selection saved beside the autosave so a crash restore lands
+// back where the writer was.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryState {
+    pub cursor: Cursor,
+    pub selection: Option<Selection>,
+}
+
+impl RecoveryState {
+    // The recovered document may be shorter than the one the state was saved
+    // against, so every position is pulled back inside it.
+    pub fn clamped_to(&self, document: &Document) -> Self {
+        let position = document.clamp_position(self.cursor.position);
+        let selection = self
+            .selection
+            .map(|selection| Selection {
+                anchor: document.clamp_position(selection.anchor),
+                head: document.clamp_position(selection.head),
+            })
+            .filter(|selection| selection.anchor != selection.head);
+
+        Self {
+            cursor: Cursor {
+                position,
+                preferred_column: self.cursor.preferred_column,
+            },
+            selection,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Position;
+
+    #[test]
+    fn recovery_state_round_trips_cursor_and_selection() {
+        let state = RecoveryState {
+            cursor: Cursor {
+                position: Position { line: 4, column: 2 },
+                preferred_column: 9,
+            },
+            selection: Some(Selection {
+                anchor: Position { line: 1, column: 0 },
+                head: Position { line: 4, column: 2 },
+            }),
+        };
+
+        let round_trip = |state: &RecoveryState| {
+            let contents = ron::to_string(state).expect("recovery state serializes");
+            ron::from_str::<RecoveryState>(&contents).ok()
+        };
+        assert_eq!(round_trip(&state), Some(state));
+
+        let no_selection = RecoveryState {
+            selection: None,
+            ..state
+        };
+        assert_eq!(round_trip(&no_selection), Some(no_selection));
+        assert!(ron::from_str::<RecoveryState>("()").is_err());
+    }
+
+    #[test]
+    fn recovery_state_clamps_to_shorter_document() {
+        let doc = Document::from_text("INT. ROOM\nShort");
+        let state = RecoveryState {
+            cursor: Cursor {
+                position: Position {
+                    line: 9,
+                    column: 40,
+                },
+                preferred_column: 40,
+            },
+            selection: Some(Selection {
+                anchor: Position { line: 0, column: 3 },
+                head: Position {
+                    line: 9,
+                    column: 40,
+                },
+            }),
+        };
+
+        let clamped = state.clamped_to(&doc);
+        assert_eq!(clamped.cursor.position, Position { line: 1, column: 5 });
+        assert_eq!(
+            clamped.selection.map(|selection| selection.head),
+            Some(Position { line: 1, column: 5 })
+        );
+    }
+}
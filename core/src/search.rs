@@ -0,0 +1,256 @@
+use std::ops::Range;
+
+use crate::buffer::Document;
+use crate::model::Position;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplaceOutcome {
+    pub replaced: usize,
+    pub scope: Option<Range<Position>>,
+}
+
+// Case-sensitive, single-line matches in document order. With a scope only
+// matches lying entirely inside it are returned.
+pub fn find_all(
+    document: &Document,
+    query: &str,
+    scope: Option<Range<Position>>,
+) -> Vec<Range<Position>> {
+    if query.is_empty() || query.contains('\n') {
+        return Vec::new();
+    }
+
+    let query_len = query.chars().count();
+    let mut matches = Vec::new();
+    for (line, text) in document.lines().iter().enumerate() {
+        let mut search_from = 0;
+        while let Some(offset) = text[search_from..].find(query) {
+            let byte_index = search_from + offset;
+            let column = text[..byte_index].chars().count();
+            let range = Position { line, column }..Position {
+                line,
+                column: column + query_len,
+            };
+            if scope.as_ref().is_none_or(|scope| contains(scope, &range)) {
+                matches.push(range);
+            }
+            search_from = byte_index + query.len();
+        }
+    }
+    matches
+}
+
+// Like find_all, but compares chars without case. Matching runs on chars, not
+// bytes, so columns stay right around accented and other non-ASCII text.
+pub fn find_all_ignoring_case(
+    document: &Document,
+    query: &str,
+    scope: Option<Range<Position>>,
+) -> Vec<Range<Position>> {
+    if query.is_empty() || query.contains('\n') {
+        return Vec::new();
+    }
+
+    let query = query.chars().map(fold_case).collect::<Vec<_>>();
+    let mut matches = Vec::new();
+    for (line, text) in document.lines().iter().enumerate() {
+        let chars = text.chars().map(fold_case).collect::<Vec<_>>();
+        let mut column = 0;
+        while column + query.len() <= chars.len() {
+            if chars[column..column + query.len()] != query[..] {
+                column += 1;
+                continue;
+            }
+            let range = Position { line, column }..Position {
+                line,
+                column: column + query.len(),
+            };
+            if scope.as_ref().is_none_or(|scope| contains(scope, &range)) {
+                matches.push(range);
+            }
+            column += query.len();
+        }
+    }
+    matches
+}
+
+// Replaces every match of `query`, restricted to `scope` when given. The returned
+// scope still covers the same text after replacements grow or shrink it.
+pub fn replace_all(
+    document: &mut Document,
+    query: &str,
+    replacement: &str,
+    scope: Option<Range<Position>>,
+) -> ReplaceOutcome {
+    let matches = find_all(document, query, scope.clone());
+    replace_matches(document, &matches, replacement, scope)
+}
+
+// Like replace_all, but replaces the matches find_all_ignoring_case finds.
+pub fn replace_all_ignoring_case(
+    document: &mut Document,
+    query: &str,
+    replacement: &str,
+    scope: Option<Range<Position>>,
+) -> ReplaceOutcome {
+    let matches = find_all_ignoring_case(document, query, scope.clone());
+    replace_matches(document, &matches, replacement, scope)
+}
+
+fn replace_matches(
+    document: &mut Document,
+    matches: &[Range<Position>],
+    replacement: &str,
+    mut scope: Option<Range<Position>>,
+) -> ReplaceOutcome {
+    // Back to front so earlier match positions stay valid.
+    for range in matches.iter().rev() {
+        document.delete_range(range.start, range.end);
+        let inserted_end = document.insert_text_bulk(range.start, replacement);
+        if let Some(scope) = scope.as_mut() {
+            scope.end = shifted_end(scope.end, range, inserted_end);
+        }
+    }
+
+    ReplaceOutcome {
+        replaced: matches.len(),
+        scope,
+    }
+}
+
+// One char per column: the few chars that lowercase to several chars, like `İ`,
+// only match themselves.
+fn fold_case(ch: char) -> char {
+    let mut lower = ch.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(single), None) => single,
+        _ => ch,
+    }
+}
+
+fn contains(scope: &Range<Position>, range: &Range<Position>) -> bool {
+    !is_before(range.start, scope.start) && !is_before(scope.end, range.end)
+}
+
+fn is_before(left: Position, right: Position) -> bool {
+    (left.line, left.column) < (right.line, right.column)
+}
+
+fn shifted_end(end: Position, replaced: &Range<Position>, inserted_end: Position) -> Position {
+    if end.line == replaced.end.line {
+        Position {
+            line: inserted_end.line,
+            column: inserted_end.column + (end.column - replaced.end.column),
+        }
+    } else {
+        Position {
+            line: end.line + (inserted_end.line - replaced.start.line),
+            column: end.column,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(line: usize, column: usize) -> Position {
+        Position { line, column }
+    }
+
+    #[test]
+    fn scoped_find_skips_matches_outside_selection() {
+        let document = Document::from_text("cat cat\ncat dog cat\ncat");
+        let scope = position(0, 4)..position(1, 9);
+
+        assert_eq!(
+            find_all(&document, "cat", Some(scope)),
+            vec![
+                position(0, 4)..position(0, 7),
+                position(1, 0)..position(1, 3)
+            ]
+        );
+        assert_eq!(find_all(&document, "cat", None).len(), 5);
+    }
+
+    #[test]
+    fn case_insensitive_find_counts_columns_in_chars() {
+        let document = Document::from_text("Élodie meets ÉLAN\nnaïve élan, Élan");
+
+        assert_eq!(
+            find_all_ignoring_case(&document, "élan", None),
+            vec![
+                position(0, 13)..position(0, 17),
+                position(1, 6)..position(1, 10),
+                position(1, 12)..position(1, 16),
+            ]
+        );
+        assert_eq!(
+            find_all(&document, "élan", None),
+            vec![position(1, 6)..position(1, 10)]
+        );
+        assert_eq!(
+            find_all_ignoring_case(&document, "NAÏVE", Some(position(1, 0)..position(1, 5))),
+            vec![position(1, 0)..position(1, 5)]
+        );
+        assert!(find_all_ignoring_case(&document, "", None).is_empty());
+    }
+
+    #[test]
+    fn scoped_replace_grows_selection_and_leaves_outside_text() {
+        let mut document = Document::from_text("cat cat\ncat dog cat\ncat");
+        let outcome = replace_all(
+            &mut document,
+            "cat",
+            "tiger",
+            Some(position(0, 4)..position(1, 7)),
+        );
+
+        assert_eq!(outcome.replaced, 2);
+        assert_eq!(outcome.scope, Some(position(0, 4)..position(1, 9)));
+        assert_eq!(document.lines(), ["cat tiger", "tiger dog cat", "cat"]);
+    }
+
+    #[test]
+    fn scoped_replace_shrinks_selection_across_new_lines() {
+        let mut document = Document::from_text("a-long-word here\nlong long");
+        let outcome = replace_all(
+            &mut document,
+            "long",
+            "x",
+            Some(position(0, 2)..position(1, 9)),
+        );
+
+        assert_eq!(outcome.replaced, 3);
+        assert_eq!(outcome.scope, Some(position(0, 2)..position(1, 3)));
+        assert_eq!(document.lines(), ["a-x-word here", "x x"]);
+
+        let mut document = Document::from_text("one, two");
+        let outcome = replace_all(
+            &mut document,
+            ", ",
+            "\n",
+            Some(position(0, 0)..position(0, 8)),
+        );
+        assert_eq!(outcome.scope, Some(position(0, 0)..position(1, 3)));
+        assert_eq!(document.lines(), ["one", "two"]);
+    }
+
+    #[test]
+    fn case_insensitive_replace_keeps_the_scope_around_the_new_text() {
+        let mut document = Document::from_text(
+            "Élan élan
+ÉLAN",
+        );
+        let outcome = replace_all_ignoring_case(
+            &mut document,
+            "élan",
+            "vigour",
+            Some(position(0, 5)..position(1, 4)),
+        );
+
+        assert_eq!(outcome.replaced, 2);
+        assert_eq!(outcome.scope, Some(position(0, 5)..position(1, 6)));
+        assert_eq!(document.lines(), ["Élan vigour", "vigour"]);
+    }
+}
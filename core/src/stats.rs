@@ -0,0 +1,86 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use crate::model::{LineKind, ParsedLine};
+use crate::parser::split_character_extension;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScriptStats {
+    pub scene_count: usize,
+    // Dialogue and parenthetical lines per character, keyed by the cue name
+    // without its extension, so `SARAH (V.O.)` counts toward `SARAH`.
+    pub dialogue_lines: BTreeMap<String, usize>,
+}
+
+impl ScriptStats {
+    pub fn character_count(&self) -> usize {
+        self.dialogue_lines.len()
+    }
+
+    // Busiest characters first; ties keep name order.
+    pub fn characters_by_lines(&self) -> Vec<(&str, usize)> {
+        let mut characters = self
+            .dialogue_lines
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+            .collect::<Vec<_>>();
+        characters.sort_by_key(|(_, count)| Reverse(*count));
+        characters
+    }
+}
+
+// Speech lines count toward the most recent cue. A cue with no lines under it
+// still counts as a character.
+pub fn document_stats(parsed: &[ParsedLine]) -> ScriptStats {
+    let mut stats = ScriptStats::default();
+    let mut speaker = None;
+
+    for line in parsed {
+        match line.kind {
+            LineKind::SceneHeading => stats.scene_count += 1,
+            LineKind::Character => {
+                let cue = line.processed_text();
+                let cue = cue.trim();
+                let name = split_character_extension(cue).map_or(cue, |(name, _)| name);
+                stats.dialogue_lines.entry(name.to_owned()).or_insert(0);
+                speaker = Some(name.to_owned());
+            }
+            LineKind::Dialogue | LineKind::Parenthetical => {
+                if let Some(count) = speaker
+                    .as_ref()
+                    .and_then(|name| stats.dialogue_lines.get_mut(name))
+                {
+                    *count += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::Document;
+    use crate::model::DocumentFormat;
+    use crate::parser::parse_document_with_format;
+
+    #[test]
+    fn counts_scenes_and_speech_lines_per_cue_name() {
+        let document = Document::from_text(
+            "INT. HOUSE - DAY\n\nSARAH\n(quietly)\nHello.\n\nBOB\nHi.\n\n\
+             EXT. YARD - NIGHT\n\nSARAH (V.O.)\nLater.\n\n@McCLANE\n",
+        );
+        let parsed = parse_document_with_format(&document, DocumentFormat::Fountain);
+        let stats = document_stats(&parsed);
+
+        assert_eq!(stats.scene_count, 2);
+        assert_eq!(stats.character_count(), 3);
+        assert_eq!(
+            stats.characters_by_lines(),
+            [("SARAH", 3), ("BOB", 1), ("MCCLANE", 0)]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+const HYPHEN: char = '-';
+
+// Greedy word wrap. Words longer than `width` overflow onto their own line
+// unless `hyphenate` is set, in which case they are broken with a trailing
+// hyphen. Words that fit within the width are never split.
+pub fn wrap_paragraph(text: &str, width: usize, hyphenate: bool) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+
+    for word in text.split_whitespace() {
+        let word_len = word.chars().count();
+        let gap = usize::from(current_len > 0);
+
+        if current_len + gap + word_len <= width {
+            if gap > 0 {
+                current.push(' ');
+            }
+            current.push_str(word);
+            current_len += gap + word_len;
+            continue;
+        }
+
+        if word_len <= width || !hyphenate || width < 2 {
+            if current_len > 0 {
+                lines.push(std::mem::take(&mut current));
+            }
+            current.push_str(word);
+            current_len = word_len;
+            continue;
+        }
+
+        let mut rest = word.chars().collect::<Vec<_>>();
+        let room = width.saturating_sub(current_len + gap);
+        if current_len > 0 && room >= 2 {
+            current.push(' ');
+            current.extend(rest.drain(..room - 1));
+            current.push(HYPHEN);
+            lines.push(std::mem::take(&mut current));
+        } else if current_len > 0 {
+            lines.push(std::mem::take(&mut current));
+        }
+        while rest.len() > width {
+            let mut piece = rest.drain(..width - 1).collect::<String>();
+            piece.push(HYPHEN);
+            lines.push(piece);
+        }
+        current = rest.into_iter().collect();
+        current_len = current.chars().count();
+    }
+
+    if current_len > 0 || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hyphenates_word_longer_than_width() {
+        let lines = wrap_paragraph("Go to supercalifragilisticexpialidocious now", 12, true);
+
+        assert_eq!(
+            lines,
+            vec![
+                "Go to super-",
+                "califragili-",
+                "sticexpiali-",
+                "docious now"
+            ]
+        );
+        assert!(lines.iter().all(|line| line.chars().count() <= 12));
+    }
+
+    #[test]
+    fn long_word_overflows_without_hyphenation() {
+        let lines = wrap_paragraph(
+            "Visit https://example.com/a/very/long/path today",
+            16,
+            false,
+        );
+
+        assert_eq!(
+            lines,
+            vec!["Visit", "https://example.com/a/very/long/path", "today"]
+        );
+    }
+
+    #[test]
+    fn never_hyphenates_words_that_fit() {
+        let text = "The quick brown fox jumps over the lazy dog";
+
+        assert_eq!(
+            wrap_paragraph(text, 10, true),
+            wrap_paragraph(text, 10, false)
+        );
+        assert!(
+            wrap_paragraph(text, 10, true)
+                .iter()
+                .all(|line| !line.ends_with(HYPHEN))
+        );
+    }
+}
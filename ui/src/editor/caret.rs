@@ -1,19 +1,61 @@
 const CARET_WIDTH: f32 = 2.0;
+const MAX_CARET_WIDTH: f32 = 8.0;
+const DEFAULT_CARET_BLINK_SECS: f32 = 0.5;
+const MIN_CARET_BLINK_SECS: f32 = 0.05;
+const MAX_CARET_BLINK_SECS: f32 = 2.0;
 const CARET_X_OFFSET: f32 = -1.0;
 // Negative moves the caret up, positive moves it down.
 const CARET_VERTICAL_OFFSET_LINES: f32 = -0.48;
 
+// Extra caret nodes spawned per panel for `EditorState::secondary_cursors`.
+const SECONDARY_CARET_CAPACITY: usize = 64;
+
 #[derive(Component)]
 struct PanelCaret {
     kind: PanelKind,
+    // 0 is the main caret; slot N shows secondary caret N - 1.
+    slot: usize,
 }
 
 fn blink_caret(time: Res<Time>, mut state: ResMut<EditorState>) {
+    if state.caret_blink_secs <= 0.0 {
+        if !state.caret_visible {
+            state.caret_visible = true;
+        }
+        return;
+    }
+
+    let interval = Duration::from_secs_f32(state.caret_blink_secs);
+    if state.caret_blink.duration() != interval {
+        state.caret_blink.set_duration(interval);
+    }
     if state.caret_blink.tick(time.delta()).just_finished() {
         state.caret_visible = !state.caret_visible;
     }
 }
 
+fn setup_secondary_carets(
+    mut commands: Commands,
+    caret_query: Query<(&PanelCaret, &Node, &ChildOf)>,
+) {
+    for (caret, node, child_of) in caret_query.iter() {
+        let kind = caret.kind;
+        let node = node.clone();
+        commands.entity(child_of.parent()).with_children(|parent| {
+            for slot in 1..=SECONDARY_CARET_CAPACITY {
+                parent.spawn((
+                    node.clone(),
+                    UiTransform::default(),
+                    BackgroundColor(LIGHT_PALETTE.plain_caret),
+                    Visibility::Hidden,
+                    ZIndex(2),
+                    PanelCaret { kind, slot },
+                ));
+            }
+        });
+    }
+}
+
 fn caret_vertical_offset(line_height: f32) -> f32 {
     CARET_VERTICAL_OFFSET_LINES * line_height
 }
@@ -29,8 +71,7 @@ fn render_panel_carets(
         ),
     >,
     state: &EditorState,
-    visible_lines: usize,
-    plain_lines: &[String],
+    plain_rows: &[PlainRow],
     plain_layout: Option<&TextLayoutInfo>,
     plain_inverse_scale: f32,
     plain_origin_x: f32,
@@ -58,10 +99,14 @@ fn render_panel_carets(
     processed_line_height: f32,
 ) {
     for (panel_caret, mut node, mut visibility, mut transform) in caret_query.iter_mut() {
-        if !state.caret_visible {
+        let position = match panel_caret.slot {
+            0 => Some(state.cursor.position),
+            slot => state.secondary_cursors.get(slot - 1).copied(),
+        };
+        let Some(position) = position.filter(|_| state.caret_visible) else {
             *visibility = Visibility::Hidden;
             continue;
-        }
+        };
 
         let (
             line_offset,
@@ -79,21 +124,16 @@ fn render_panel_carets(
             clamp_local_position_to_origin,
         ) = match panel_caret.kind {
             PanelKind::Plain => {
-                let in_view = state.cursor.position.line >= state.top_line
-                    && state.cursor.position.line < state.top_line + visible_lines;
-                if !in_view {
+                let Some(row_index) = plain_row_index(plain_rows, position) else {
                     *visibility = Visibility::Hidden;
                     continue;
-                }
+                };
 
-                let line_offset = state.cursor.position.line - state.top_line;
-                let line_text = plain_lines
-                    .get(line_offset)
-                    .map_or("", |line| line.as_str());
+                let row = &plain_rows[row_index];
                 (
-                    line_offset,
-                    state.cursor.position.column,
-                    line_text,
+                    row_index,
+                    position.column - row.start_column,
+                    row.text.as_str(),
                     plain_layout,
                     plain_inverse_scale,
                     plain_origin_x,
@@ -101,14 +141,14 @@ fn render_panel_carets(
                     plain_char_width,
                     plain_line_height,
                     CARET_X_OFFSET,
-                    CARET_WIDTH.max(1.0),
+                    state.caret_width,
                     true,
                     true,
                 )
             }
             PanelKind::Processed => {
                 let Some((visual_index, display_column, line_text)) =
-                    processed_caret_visual(state, processed_view)
+                    processed_cursor_visual_from_lines(position, &processed_view.lines)
                 else {
                     *visibility = Visibility::Hidden;
                     continue;
@@ -150,7 +190,7 @@ fn render_panel_carets(
                     processed_char_width,
                     processed_line_height,
                     CARET_X_OFFSET,
-                    CARET_WIDTH.max(1.0),
+                    state.caret_width,
                     true,
                     true,
                 )
@@ -1,13 +1,24 @@
 use std::{
+    borrow::Cow,
     collections::{BTreeMap, BTreeSet},
     fs, io,
+    ops::Range,
     path::{Path, PathBuf},
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
 use basscript_core::{
-    Cursor, Document, DocumentFormat, DocumentPath, LineKind, LinkDisplayText, ParsedLine,
-    Position, ScriptLink, parse_document_with_format,
+    ChunkedReparse, Cursor, Document, DocumentFormat, DocumentPath, DualDialogue, EditSnapshot,
+    Emphasis, HtmlPalette, IndentWidths, LineKind, LinkDisplayText, MarkerEdit, PageLayout,
+    PageMargins, PageSize, ParseOptions, ParsedLine, PlainTextOptions, Position, RecoveryState,
+    ScriptLink, ScriptStats, Selection, UndoGroupKind, apply_marker_edits, begin_chunked_reparse,
+    centered_text, document_stats, dual_dialogue_cue_text, emphasis_columns, export_html,
+    export_plain_text, export_processed_fountain, export_revision_notes, find_all_ignoring_case,
+    forced_marker_len, is_transition, next_different_kind_line, next_scene_heading_line,
+    note_ranges, outline_entries, parse_document_with_options, previous_different_kind_line,
+    previous_scene_heading_line, propose_forced_markers, reparse_from, replace_all_ignoring_case,
+    scene_number, scene_range, split_character_extension, starts_new_page, structural_range,
+    title_page_value, toggle_character_dialogue_marker,
 };
 use bevy::{
     input::{
@@ -19,9 +30,10 @@ use bevy::{
     tasks::{AsyncComputeTaskPool, Task, futures_lite::future},
     text::{LineHeight, TextLayoutInfo},
     ui::{RelativeCursorPosition, UiTransform, Val2},
-    window::{PrimaryWindow, RawHandleWrapper},
+    window::{PrimaryWindow, RawHandleWrapper, WindowFocused},
 };
 use rfd::AsyncFileDialog;
+use serde::{Deserialize, Serialize};
 
 const FONT_PATH: &str = "fonts/Courier Prime/Courier Prime.ttf";
 const FONT_BOLD_PATH: &str = "fonts/Courier Prime/Courier Prime Bold.ttf";
@@ -36,12 +48,14 @@ const DEFAULT_SAVE_PATH: &str = "scripts/session.fountain";
 const EDITOR_SETTINGS_PATH: &str = "settings/editor_settings.ron";
 const KEYBINDS_SETTINGS_PATH: &str = "settings/keybinds.ron";
 const UI_STATE_PATH: &str = "settings/state.ron";
+const RECOVERY_STATE_PATH: &str = "settings/recovery.ron";
 const THEME_SETTINGS_PATH: &str = "settings/theme.ron";
 const LEGACY_EDITOR_SETTINGS_PATH: &str = "scripts/editor_settings.ron";
 const LEGACY_KEYBINDS_SETTINGS_PATH: &str = "scripts/keybinds.ron";
 const LEGACY_SETTINGS_PATH: &str = "scripts/settings.toml";
 const PROCESSED_PAPER_CAPACITY: usize = 16;
 const SELECTION_RECT_CAPACITY: usize = 512;
+const EMPTY_DOCUMENT_PLACEHOLDER: &str = "Empty document - start typing.";
 
 const FONT_SIZE: f32 = 12.0;
 const LINE_HEIGHT: f32 = 12.0;
@@ -54,13 +68,10 @@ const ZOOM_MAX: f32 = 1.8;
 const ZOOM_STEP: f32 = 0.1;
 const NAVIGATION_REPEAT_INITIAL_DELAY_SECS: f32 = 0.30;
 const NAVIGATION_REPEAT_INTERVAL_SECS: f32 = 0.045;
-const HISTORY_LIMIT: usize = 512;
-const MM_PER_INCH: f32 = 25.4;
-const POINTS_PER_INCH: f32 = 72.0;
-const A4_WIDTH_MM: f32 = 210.0;
-const A4_HEIGHT_MM: f32 = 297.0;
-const A4_WIDTH_POINTS: f32 = A4_WIDTH_MM / MM_PER_INCH * POINTS_PER_INCH;
-const A4_HEIGHT_POINTS: f32 = A4_HEIGHT_MM / MM_PER_INCH * POINTS_PER_INCH;
+// Inserts adding at least this many lines are reparsed over several frames,
+// `CHUNKED_REPARSE_LINES_PER_FRAME` lines at a time, instead of all at once.
+const CHUNKED_REPARSE_MIN_LINES: usize = 2_000;
+const CHUNKED_REPARSE_LINES_PER_FRAME: usize = 4_000;
 const PAGE_OUTER_MARGIN: f32 = 14.0;
 const PAGE_TEXT_MARGIN_LEFT: f32 = 42.0;
 const PAGE_TEXT_MARGIN_RIGHT: f32 = 34.0;
@@ -68,6 +79,46 @@ const PAGE_TEXT_MARGIN_TOP: f32 = 30.0;
 const PAGE_TEXT_MARGIN_BOTTOM: f32 = 30.0;
 const PAGE_GAP: f32 = 24.0;
 const PAGE_MARGIN_STEP: f32 = 8.0;
+const DEFAULT_DIALOGUE_WIDTH_COLUMNS: usize = 35;
+const DEFAULT_ACTION_WIDTH_COLUMNS: usize = 61;
+const DEFAULT_WRAP_GUIDE_COLUMN: usize = 61;
+const DEFAULT_TAB_WIDTH: usize = 4;
+const MAX_TAB_WIDTH: usize = 16;
+// Zero turns the interval auto-save off.
+const DEFAULT_AUTOSAVE_INTERVAL_SECS: u64 = 0;
+const MAX_AUTOSAVE_INTERVAL_SECS: u64 = 3600;
+const AUTOSAVE_STATUS_SECS: f32 = 3.0;
+const EXTERNAL_CHANGE_CHECK_SECS: f32 = 2.0;
+// Settings keys and labels for the `IndentWidths` fields, in `indent_width_slot` order.
+const INDENT_WIDTH_SETTINGS: [(&str, &str); 6] = [
+    ("scene_heading_indent", "Scene heading indent (columns)"),
+    ("character_indent", "Character indent (columns)"),
+    ("dialogue_indent", "Dialogue indent (columns)"),
+    ("parenthetical_indent", "Parenthetical indent (columns)"),
+    ("transition_indent", "Transition indent (columns)"),
+    ("synopsis_indent", "Synopsis indent (columns)"),
+];
+const MAX_INDENT_WIDTH: usize = 60;
+const PROCESSED_KIND_TOGGLES: [(LineKind, &str, &str); 6] = [
+    (LineKind::SceneHeading, "scene_heading", "Scene headings"),
+    (LineKind::Action, "action", "Action"),
+    (LineKind::Character, "character", "Characters"),
+    (LineKind::Dialogue, "dialogue", "Dialogue"),
+    (LineKind::Parenthetical, "parenthetical", "Parentheticals"),
+    (LineKind::Transition, "transition", "Transitions"),
+];
+// Settings keys, status labels and defaults for the per-kind text colors, in
+// `PROCESSED_KIND_TOGGLES` order.
+const ELEMENT_COLOR_SETTINGS: [(&str, &str, Color); 6] = [
+    ("scene_heading_color", "scene heading color", COLOR_SCENE),
+    ("action_color", "action color", COLOR_ACTION),
+    ("character_color", "character color", COLOR_CHARACTER),
+    ("dialogue_color", "dialogue color", COLOR_DIALOGUE),
+    ("parenthetical_color", "parenthetical color", COLOR_PARENTHETICAL),
+    ("transition_color", "transition color", COLOR_TRANSITION),
+];
+const MIN_ELEMENT_WIDTH_COLUMNS: usize = 10;
+const MAX_ELEMENT_WIDTH_COLUMNS: usize = 120;
 const THEME_COLOR_WHEEL_SIZE_PX: u32 = 192;
 const THEME_COLOR_WHEEL_SIZE: f32 = THEME_COLOR_WHEEL_SIZE_PX as f32;
 const THEME_COLOR_SLIDER_WIDTH: f32 = 180.0;
@@ -83,6 +134,8 @@ const PANEL_SPLITTER_PICK_RADIUS: f32 = 18.0;
 const WORKSPACE_WIDTH_DEFAULT: f32 = 280.0;
 const WORKSPACE_WIDTH_MIN: f32 = 180.0;
 const EDITOR_PANEL_MIN_WIDTH: f32 = 220.0;
+const PANEL_SCROLLBAR_WIDTH: f32 = 8.0;
+const PANEL_SCROLLBAR_MIN_THUMB: f32 = 0.04;
 const UNDECORATED_WINDOW_CORNER_RADIUS: f32 = 8.0;
 
 const BUTTON_NORMAL: Color = Color::srgb(0.80, 0.82, 0.84);
@@ -94,6 +147,8 @@ const COLOR_CHARACTER: Color = Color::srgb(0.20, 0.16, 0.12);
 const COLOR_DIALOGUE: Color = Color::srgb(0.11, 0.12, 0.13);
 const COLOR_PARENTHETICAL: Color = Color::srgb(0.24, 0.28, 0.32);
 const COLOR_TRANSITION: Color = Color::srgb(0.15, 0.23, 0.31);
+const COLOR_SECTION: Color = Color::srgb(0.30, 0.20, 0.38);
+const COLOR_SYNOPSIS: Color = Color::srgb(0.32, 0.38, 0.30);
 const COLOR_MARKDOWN_HEADING: Color = Color::srgb(0.18, 0.24, 0.40);
 const COLOR_MARKDOWN_LIST: Color = Color::srgb(0.16, 0.22, 0.31);
 const COLOR_MARKDOWN_QUOTE: Color = Color::srgb(0.22, 0.29, 0.26);
@@ -105,12 +160,59 @@ const COLOR_PANEL_BODY_PROCESSED: Color = Color::srgb(0.82, 0.83, 0.84);
 const COLOR_PAPER: Color = Color::srgb(1.0, 1.0, 1.0);
 const COLOR_TEXT_MAIN: Color = Color::srgb(0.18, 0.19, 0.20);
 const COLOR_TEXT_MUTED: Color = Color::srgb(0.34, 0.36, 0.39);
-const COLOR_WORKSPACE_FILE: Color = Color::srgb(0.18, 0.19, 0.20);
+const COLOR_KIND_LABEL: Color = Color::srgb(0.68, 0.70, 0.73);
+const FOCUS_DIMMED_ALPHA: f32 = 0.28;
+const COLOR_DIALOGUE_OVER_WIDTH: Color = Color::srgb(0.72, 0.16, 0.20);
+const COLOR_ACTION_OVER_WIDTH: Color = Color::srgb(0.78, 0.45, 0.08);
+const COLOR_NOTED_LINE: Color = Color::srgb(0.40, 0.33, 0.20);
+const COLOR_FIND_MATCH: Color = Color::srgba(0.98, 0.80, 0.20, 0.40);
 const COLOR_WORKSPACE_FILE_HOVER: Color = Color::srgb(0.10, 0.35, 0.62);
 const COLOR_WORKSPACE_FILE_SELECTED: Color = Color::srgb(0.69, 0.28, 0.22);
 const COLOR_SPLITTER_IDLE: Color = Color::srgba(0.0, 0.0, 0.0, 0.0);
 const COLOR_SPLITTER_HOVER: Color = Color::srgba(0.0, 0.0, 0.0, 0.0);
 const COLOR_SPLITTER_ACTIVE: Color = Color::srgba(0.0, 0.0, 0.0, 0.0);
+const COLOR_SCROLLBAR_THUMB: Color = Color::srgba(0.50, 0.52, 0.55, 0.35);
+const COLOR_SCROLLBAR_THUMB_HOVER: Color = Color::srgba(0.50, 0.52, 0.55, 0.55);
+const COLOR_SCROLLBAR_THUMB_ACTIVE: Color = Color::srgba(0.50, 0.52, 0.55, 0.75);
+
+// Chrome colors that follow the light/dark theme switch. Processed pages stay
+// paper colored in both, so the line-kind colors are not part of it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ThemePalette {
+    button_normal: Color,
+    button_hover: Color,
+    button_pressed: Color,
+    panel_bg: Color,
+    panel_body_plain: Color,
+    plain_text: Color,
+    plain_caret: Color,
+    text_main: Color,
+    text_muted: Color,
+}
+
+const LIGHT_PALETTE: ThemePalette = ThemePalette {
+    button_normal: BUTTON_NORMAL,
+    button_hover: BUTTON_HOVER,
+    button_pressed: BUTTON_PRESSED,
+    panel_bg: COLOR_PANEL_BG,
+    panel_body_plain: COLOR_PANEL_BODY_PLAIN,
+    plain_text: COLOR_ACTION,
+    plain_caret: Color::srgba(0.12, 0.12, 0.13, 0.35),
+    text_main: COLOR_TEXT_MAIN,
+    text_muted: COLOR_TEXT_MUTED,
+};
+
+const DARK_PALETTE: ThemePalette = ThemePalette {
+    button_normal: Color::srgb(0.24, 0.25, 0.28),
+    button_hover: Color::srgb(0.30, 0.32, 0.35),
+    button_pressed: Color::srgb(0.36, 0.38, 0.42),
+    panel_bg: Color::srgb(0.15, 0.16, 0.18),
+    panel_body_plain: Color::srgb(0.12, 0.13, 0.15),
+    plain_text: Color::srgb(0.86, 0.87, 0.89),
+    plain_caret: Color::srgba(0.90, 0.91, 0.93, 0.55),
+    text_main: Color::srgb(0.88, 0.89, 0.91),
+    text_muted: Color::srgb(0.62, 0.65, 0.69),
+};
 
 pub struct UiPlugin;
 
@@ -133,6 +235,7 @@ impl Plugin for UiPlugin {
             .init_resource::<MouseSelectionState>()
             .init_resource::<PanelLayoutState>()
             .init_resource::<PanelSplitterDragState>()
+            .init_resource::<ScrollbarDragState>()
             .init_state::<UiScreenState>()
             .insert_non_send_resource(DialogMainThreadMarker)
             .add_systems(
@@ -140,16 +243,20 @@ impl Plugin for UiPlugin {
                 (
                     setup,
                     setup_selection_rects.after(setup),
+                    setup_secondary_carets.after(setup),
                     setup_processed_papers.after(setup),
+                    setup_plain_wrap_guide.after(setup),
                 ),
             )
             .add_systems(
                 Update,
                 (
                     style_toolbar_buttons,
+                    sync_theme_palette_colors,
                     style_workspace_file_entry_text,
                     handle_window_shortcuts,
                     sync_window_chrome,
+                    sync_window_title,
                     sync_glass_surfaces,
                     sync_top_menu_visibility,
                     sync_rounded_window_surfaces,
@@ -166,6 +273,7 @@ impl Plugin for UiPlugin {
                     handle_toolbar_buttons,
                     handle_workspace_file_buttons,
                     handle_workspace_folder_buttons,
+                    handle_outline_row_buttons,
                 )
                     .run_if(in_state(UiScreenState::Editor)),
             )
@@ -195,16 +303,29 @@ impl Plugin for UiPlugin {
                 Update,
                 (
                     handle_file_shortcuts,
+                    (
+                        autosave_on_focus_loss,
+                        autosave_on_interval,
+                        detect_external_file_change,
+                    ),
                     resolve_dialog_results,
-                    handle_text_input,
-                    handle_navigation_input,
+                    (
+                        handle_text_input,
+                        handle_navigation_input,
+                        handle_debug_recompute,
+                        persist_zoom_setting,
+                        handle_clipboard_input,
+                        advance_chunked_reparse,
+                    ),
                     handle_mouse_scroll,
                     handle_ctrl_left_drag_scroll,
                     handle_middle_mouse_autoscroll,
                     handle_panel_splitter_drag.after(handle_middle_mouse_autoscroll),
+                    handle_scrollbar_drag.after(handle_panel_splitter_drag),
                     handle_mouse_selection
                         .after(handle_middle_mouse_autoscroll)
-                        .after(handle_panel_splitter_drag),
+                        .after(handle_panel_splitter_drag)
+                        .after(handle_scrollbar_drag),
                     sync_hovered_processed_link
                         .after(handle_mouse_selection)
                         .before(render_editor),
@@ -212,6 +333,19 @@ impl Plugin for UiPlugin {
                     style_panel_splitters,
                     blink_caret,
                     render_editor,
+                    apply_plain_focus_dimming.after(render_editor),
+                    (
+                        sync_reclassify_preview_overlay,
+                        sync_find_bar_overlay,
+                        sync_stats_overlay,
+                        sync_outline_overlay,
+                    ),
+                    sync_processed_cue_ruler.after(render_editor),
+                    (
+                        sync_plain_wrap_guide,
+                        sync_panel_scrollbars.after(render_editor),
+                        sync_processed_page_numbers.after(render_editor),
+                    ),
                 )
                     .run_if(in_state(UiScreenState::Editor)),
             );
@@ -288,6 +422,16 @@ struct PanelText {
     kind: PanelKind,
 }
 
+#[derive(Component)]
+struct PanelScrollbar {
+    kind: PanelKind,
+}
+
+#[derive(Component)]
+struct PanelScrollbarThumb {
+    kind: PanelKind,
+}
+
 #[derive(Component)]
 struct PanelPaper {
     kind: PanelKind,
@@ -313,6 +457,35 @@ struct PanelSelectionRect {
 #[derive(Component)]
 struct MiddleAutoscrollIndicator;
 
+#[derive(Component)]
+struct ReclassifyPreviewOverlay;
+
+#[derive(Component)]
+struct ProcessedCueRuler;
+
+#[derive(Component)]
+struct PlainWrapGuide;
+
+#[derive(Component)]
+struct ProcessedCueRulerTick {
+    kind: LineKind,
+}
+
+#[derive(Component)]
+struct ReclassifyPreviewText;
+
+#[derive(Component)]
+struct FindBarOverlay;
+
+#[derive(Component)]
+struct FindBarText;
+
+#[derive(Component)]
+struct StatsOverlay;
+
+#[derive(Component)]
+struct StatsText;
+
 #[derive(Component)]
 struct ProcessedPaperText {
     slot: usize,
@@ -331,10 +504,30 @@ struct ProcessedChecklistIcon {
     line_offset: usize,
 }
 
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+struct PlainFocusSpan {
+    part: usize,
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+struct ProcessedKindLabel {
+    slot: usize,
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+struct ProcessedPageNumber {
+    slot: usize,
+}
+
 #[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
 enum ToolbarAction {
     OpenWorkspace,
     SaveAs,
+    SaveBundle,
+    ExportNotes,
+    ExportFountain,
+    ExportHtml,
+    ExportText,
     ZoomOut,
     ZoomIn,
     Settings,
@@ -342,12 +535,35 @@ enum ToolbarAction {
 
 #[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
 enum SettingsAction {
+    ToggleKindVisibility(usize),
     DialogueDoubleSpaceNewline,
     NonDialogueDoubleSpaceNewline,
     ShowSystemTitlebar,
     ToggleProcessedGlass,
     ToggleExplorerGlass,
     ToggleSettingsGlass,
+    ToggleDarkMode,
+    ToggleProcessedRightToLeft,
+    ToggleSmoothScrolling,
+    ToggleWrapActionLines,
+    ToggleTrimTrailingWhitespaceOnSave,
+    ToggleDialoguePageContinuations,
+    ToggleAutoNumberScenes,
+    ToggleWrapGuide,
+    ToggleAutosaveOnFocusLoss,
+    ToggleUnifiedProcessedEdit,
+    ToggleProcessedCueRuler,
+    ToggleElementWidthWarnings,
+    ToggleFocusCurrentScene,
+    ToggleSceneHeadingRequiresContent,
+    ToggleProcessedKindLabels,
+    ToggleAutoUppercaseCharacterCues,
+    ToggleAutoCapitalizeSentences,
+    ToggleAutoPairParentheses,
+    ToggleDialogueSpansSingleBlank,
+    ToggleSyncScrollPanes,
+    ToggleAutoReloadExternalChanges,
+    ToggleHyphenateExportedDialogue,
     MarginLeftDecrease,
     MarginLeftIncrease,
     MarginRightDecrease,
@@ -356,6 +572,16 @@ enum SettingsAction {
     MarginTopIncrease,
     MarginBottomDecrease,
     MarginBottomIncrease,
+    TogglePageSize,
+    ScreenplayMargins,
+    DialogueWidthDecrease,
+    DialogueWidthIncrease,
+    ActionWidthDecrease,
+    ActionWidthIncrease,
+    WrapGuideColumnDecrease,
+    WrapGuideColumnIncrease,
+    IndentWidthDecrease(usize),
+    IndentWidthIncrease(usize),
     LinkHoverHsvValueDecrease,
     LinkHoverHsvValueIncrease,
     OpenTheme,
@@ -378,9 +604,28 @@ enum ShortcutAction {
     ProcessedRawCurrentLineView,
     ToggleExplorer,
     ToggleTopMenu,
-}
-
-const SHORTCUT_ACTIONS: [ShortcutAction; 11] = [
+    JoinLines,
+    SelectSection,
+    ToggleCharacterDialogue,
+    DuplicateScene,
+    NextDifferentElement,
+    PreviousDifferentElement,
+    PreviewForcedMarkers,
+    ToggleDistractionFree,
+    NextSentence,
+    PreviousSentence,
+    InsertPageBreak,
+    TogglePlainTextMode,
+    DeleteLine,
+    DuplicateLine,
+    OpenFind,
+    ToggleStats,
+    NextScene,
+    PreviousScene,
+    ToggleOutline,
+}
+
+const SHORTCUT_ACTIONS: [ShortcutAction; 30] = [
     ShortcutAction::OpenWorkspace,
     ShortcutAction::SaveAs,
     ShortcutAction::Undo,
@@ -392,6 +637,25 @@ const SHORTCUT_ACTIONS: [ShortcutAction; 11] = [
     ShortcutAction::ProcessedRawCurrentLineView,
     ShortcutAction::ToggleExplorer,
     ShortcutAction::ToggleTopMenu,
+    ShortcutAction::JoinLines,
+    ShortcutAction::SelectSection,
+    ShortcutAction::ToggleCharacterDialogue,
+    ShortcutAction::DuplicateScene,
+    ShortcutAction::NextDifferentElement,
+    ShortcutAction::PreviousDifferentElement,
+    ShortcutAction::PreviewForcedMarkers,
+    ShortcutAction::ToggleDistractionFree,
+    ShortcutAction::NextSentence,
+    ShortcutAction::PreviousSentence,
+    ShortcutAction::InsertPageBreak,
+    ShortcutAction::TogglePlainTextMode,
+    ShortcutAction::DeleteLine,
+    ShortcutAction::DuplicateLine,
+    ShortcutAction::OpenFind,
+    ShortcutAction::ToggleStats,
+    ShortcutAction::NextScene,
+    ShortcutAction::PreviousScene,
+    ShortcutAction::ToggleOutline,
 ];
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -413,6 +677,25 @@ struct KeybindSettings {
     processed_raw_current_line_view: ShortcutBinding,
     toggle_explorer: ShortcutBinding,
     toggle_top_menu: ShortcutBinding,
+    join_lines: ShortcutBinding,
+    select_section: ShortcutBinding,
+    toggle_character_dialogue: ShortcutBinding,
+    duplicate_scene: ShortcutBinding,
+    next_different_element: ShortcutBinding,
+    previous_different_element: ShortcutBinding,
+    preview_forced_markers: ShortcutBinding,
+    toggle_distraction_free: ShortcutBinding,
+    next_sentence: ShortcutBinding,
+    previous_sentence: ShortcutBinding,
+    insert_page_break: ShortcutBinding,
+    toggle_plain_text_mode: ShortcutBinding,
+    delete_line: ShortcutBinding,
+    duplicate_line: ShortcutBinding,
+    open_find: ShortcutBinding,
+    toggle_stats: ShortcutBinding,
+    next_scene: ShortcutBinding,
+    previous_scene: ShortcutBinding,
+    toggle_outline: ShortcutBinding,
 }
 
 impl Default for KeybindSettings {
@@ -462,6 +745,82 @@ impl Default for KeybindSettings {
                 key: KeyCode::KeyB,
                 shift: false,
             },
+            join_lines: ShortcutBinding {
+                key: KeyCode::KeyJ,
+                shift: false,
+            },
+            select_section: ShortcutBinding {
+                key: KeyCode::KeyA,
+                shift: true,
+            },
+            toggle_character_dialogue: ShortcutBinding {
+                key: KeyCode::KeyC,
+                shift: true,
+            },
+            duplicate_scene: ShortcutBinding {
+                key: KeyCode::KeyD,
+                shift: true,
+            },
+            next_different_element: ShortcutBinding {
+                key: KeyCode::BracketRight,
+                shift: false,
+            },
+            previous_different_element: ShortcutBinding {
+                key: KeyCode::BracketLeft,
+                shift: false,
+            },
+            preview_forced_markers: ShortcutBinding {
+                key: KeyCode::KeyR,
+                shift: true,
+            },
+            toggle_distraction_free: ShortcutBinding {
+                key: KeyCode::KeyF,
+                shift: true,
+            },
+            next_sentence: ShortcutBinding {
+                key: KeyCode::Period,
+                shift: false,
+            },
+            previous_sentence: ShortcutBinding {
+                key: KeyCode::Comma,
+                shift: false,
+            },
+            insert_page_break: ShortcutBinding {
+                key: KeyCode::KeyP,
+                shift: true,
+            },
+            toggle_plain_text_mode: ShortcutBinding {
+                key: KeyCode::KeyT,
+                shift: true,
+            },
+            delete_line: ShortcutBinding {
+                key: KeyCode::KeyK,
+                shift: true,
+            },
+            duplicate_line: ShortcutBinding {
+                key: KeyCode::KeyD,
+                shift: false,
+            },
+            open_find: ShortcutBinding {
+                key: KeyCode::KeyF,
+                shift: false,
+            },
+            toggle_stats: ShortcutBinding {
+                key: KeyCode::KeyI,
+                shift: true,
+            },
+            next_scene: ShortcutBinding {
+                key: KeyCode::ArrowDown,
+                shift: true,
+            },
+            previous_scene: ShortcutBinding {
+                key: KeyCode::ArrowUp,
+                shift: true,
+            },
+            toggle_outline: ShortcutBinding {
+                key: KeyCode::KeyO,
+                shift: true,
+            },
         }
     }
 }
@@ -480,6 +839,25 @@ impl KeybindSettings {
             ShortcutAction::ProcessedRawCurrentLineView => self.processed_raw_current_line_view,
             ShortcutAction::ToggleExplorer => self.toggle_explorer,
             ShortcutAction::ToggleTopMenu => self.toggle_top_menu,
+            ShortcutAction::JoinLines => self.join_lines,
+            ShortcutAction::SelectSection => self.select_section,
+            ShortcutAction::ToggleCharacterDialogue => self.toggle_character_dialogue,
+            ShortcutAction::DuplicateScene => self.duplicate_scene,
+            ShortcutAction::NextDifferentElement => self.next_different_element,
+            ShortcutAction::PreviousDifferentElement => self.previous_different_element,
+            ShortcutAction::PreviewForcedMarkers => self.preview_forced_markers,
+            ShortcutAction::ToggleDistractionFree => self.toggle_distraction_free,
+            ShortcutAction::NextSentence => self.next_sentence,
+            ShortcutAction::PreviousSentence => self.previous_sentence,
+            ShortcutAction::InsertPageBreak => self.insert_page_break,
+            ShortcutAction::TogglePlainTextMode => self.toggle_plain_text_mode,
+            ShortcutAction::DeleteLine => self.delete_line,
+            ShortcutAction::DuplicateLine => self.duplicate_line,
+            ShortcutAction::OpenFind => self.open_find,
+            ShortcutAction::ToggleStats => self.toggle_stats,
+            ShortcutAction::NextScene => self.next_scene,
+            ShortcutAction::PreviousScene => self.previous_scene,
+            ShortcutAction::ToggleOutline => self.toggle_outline,
         }
     }
 
@@ -498,6 +876,25 @@ impl KeybindSettings {
             }
             ShortcutAction::ToggleExplorer => self.toggle_explorer = binding,
             ShortcutAction::ToggleTopMenu => self.toggle_top_menu = binding,
+            ShortcutAction::JoinLines => self.join_lines = binding,
+            ShortcutAction::SelectSection => self.select_section = binding,
+            ShortcutAction::ToggleCharacterDialogue => self.toggle_character_dialogue = binding,
+            ShortcutAction::DuplicateScene => self.duplicate_scene = binding,
+            ShortcutAction::NextDifferentElement => self.next_different_element = binding,
+            ShortcutAction::PreviousDifferentElement => self.previous_different_element = binding,
+            ShortcutAction::PreviewForcedMarkers => self.preview_forced_markers = binding,
+            ShortcutAction::ToggleDistractionFree => self.toggle_distraction_free = binding,
+            ShortcutAction::NextSentence => self.next_sentence = binding,
+            ShortcutAction::PreviousSentence => self.previous_sentence = binding,
+            ShortcutAction::InsertPageBreak => self.insert_page_break = binding,
+            ShortcutAction::TogglePlainTextMode => self.toggle_plain_text_mode = binding,
+            ShortcutAction::DeleteLine => self.delete_line = binding,
+            ShortcutAction::DuplicateLine => self.duplicate_line = binding,
+            ShortcutAction::OpenFind => self.open_find = binding,
+            ShortcutAction::ToggleStats => self.toggle_stats = binding,
+            ShortcutAction::NextScene => self.next_scene = binding,
+            ShortcutAction::PreviousScene => self.previous_scene = binding,
+            ShortcutAction::ToggleOutline => self.toggle_outline = binding,
         }
     }
 }
@@ -515,6 +912,25 @@ fn shortcut_action_label(action: ShortcutAction) -> &'static str {
         ShortcutAction::ProcessedRawCurrentLineView => "Processed + Raw Current Line Mode",
         ShortcutAction::ToggleExplorer => "Toggle Explorer",
         ShortcutAction::ToggleTopMenu => "Toggle Top Menu",
+        ShortcutAction::JoinLines => "Join Lines",
+        ShortcutAction::SelectSection => "Select Section",
+        ShortcutAction::ToggleCharacterDialogue => "Toggle Character/Dialogue",
+        ShortcutAction::DuplicateScene => "Duplicate Scene",
+        ShortcutAction::NextDifferentElement => "Next different element",
+        ShortcutAction::PreviousDifferentElement => "Previous different element",
+        ShortcutAction::PreviewForcedMarkers => "Preview forced markers",
+        ShortcutAction::ToggleDistractionFree => "Toggle Distraction-Free",
+        ShortcutAction::NextSentence => "Next Sentence",
+        ShortcutAction::PreviousSentence => "Previous Sentence",
+        ShortcutAction::InsertPageBreak => "Insert Page Break",
+        ShortcutAction::TogglePlainTextMode => "Toggle Plain Text Mode",
+        ShortcutAction::DeleteLine => "Delete Line",
+        ShortcutAction::DuplicateLine => "Duplicate Line",
+        ShortcutAction::OpenFind => "Find",
+        ShortcutAction::ToggleStats => "Toggle Statistics",
+        ShortcutAction::NextScene => "Next Scene",
+        ShortcutAction::PreviousScene => "Previous Scene",
+        ShortcutAction::ToggleOutline => "Toggle Outline",
     }
 }
 
@@ -531,6 +947,29 @@ fn shortcut_action_description(action: ShortcutAction) -> &'static str {
         ShortcutAction::ProcessedRawCurrentLineView => "Processed + raw current line mode",
         ShortcutAction::ToggleExplorer => "Toggle explorer",
         ShortcutAction::ToggleTopMenu => "Toggle top menu",
+        ShortcutAction::JoinLines => "Join line with next",
+        ShortcutAction::SelectSection => "Select current scene/section",
+        ShortcutAction::ToggleCharacterDialogue => "Force line as character or dialogue",
+        ShortcutAction::DuplicateScene => "Duplicate current scene",
+        ShortcutAction::NextDifferentElement => "Jump to the next line of another element type",
+        ShortcutAction::PreviousDifferentElement => {
+            "Jump to the previous line of another element type"
+        }
+        ShortcutAction::PreviewForcedMarkers => {
+            "List ambiguous lines and the forced markers that would fix them."
+        }
+        ShortcutAction::ToggleDistractionFree => "Toggle distraction-free mode",
+        ShortcutAction::NextSentence => "Move to next sentence (Shift extends)",
+        ShortcutAction::PreviousSentence => "Move to previous sentence (Shift extends)",
+        ShortcutAction::InsertPageBreak => "Insert a forced page break at the cursor",
+        ShortcutAction::TogglePlainTextMode => "Treat every line of this document as action",
+        ShortcutAction::DeleteLine => "Delete the current line",
+        ShortcutAction::DuplicateLine => "Duplicate the current line",
+        ShortcutAction::OpenFind => "Find text in the script",
+        ShortcutAction::ToggleStats => "Show scene and per-character line counts",
+        ShortcutAction::NextScene => "Jump to the next scene heading",
+        ShortcutAction::PreviousScene => "Jump to the previous scene heading",
+        ShortcutAction::ToggleOutline => "Show scenes and sections; click one to jump there",
     }
 }
 
@@ -547,6 +986,25 @@ fn shortcut_action_settings_key(action: ShortcutAction) -> &'static str {
         ShortcutAction::ProcessedRawCurrentLineView => "processed_raw_current_line_view",
         ShortcutAction::ToggleExplorer => "toggle_explorer",
         ShortcutAction::ToggleTopMenu => "toggle_top_menu",
+        ShortcutAction::JoinLines => "join_lines",
+        ShortcutAction::SelectSection => "select_section",
+        ShortcutAction::ToggleCharacterDialogue => "toggle_character_dialogue",
+        ShortcutAction::DuplicateScene => "duplicate_scene",
+        ShortcutAction::NextDifferentElement => "next_different_element",
+        ShortcutAction::PreviousDifferentElement => "previous_different_element",
+        ShortcutAction::PreviewForcedMarkers => "preview_forced_markers",
+        ShortcutAction::ToggleDistractionFree => "toggle_distraction_free",
+        ShortcutAction::NextSentence => "next_sentence",
+        ShortcutAction::PreviousSentence => "previous_sentence",
+        ShortcutAction::InsertPageBreak => "insert_page_break",
+        ShortcutAction::TogglePlainTextMode => "toggle_plain_text_mode",
+        ShortcutAction::DeleteLine => "delete_line",
+        ShortcutAction::DuplicateLine => "duplicate_line",
+        ShortcutAction::OpenFind => "open_find",
+        ShortcutAction::ToggleStats => "toggle_stats",
+        ShortcutAction::NextScene => "next_scene",
+        ShortcutAction::PreviousScene => "previous_scene",
+        ShortcutAction::ToggleOutline => "toggle_outline",
     }
 }
 
@@ -590,6 +1048,12 @@ fn binding_key_name(key: KeyCode) -> Option<&'static str> {
         KeyCode::Digit9 | KeyCode::Numpad9 => Some("9"),
         KeyCode::Equal => Some("="),
         KeyCode::Minus => Some("-"),
+        KeyCode::BracketLeft => Some("["),
+        KeyCode::BracketRight => Some("]"),
+        KeyCode::Comma => Some(","),
+        KeyCode::Period => Some("."),
+        KeyCode::ArrowUp => Some("Up"),
+        KeyCode::ArrowDown => Some("Down"),
         _ => None,
     }
 }
@@ -634,6 +1098,12 @@ fn binding_key_from_name(name: &str) -> Option<KeyCode> {
         "9" => Some(KeyCode::Digit9),
         "=" => Some(KeyCode::Equal),
         "-" => Some(KeyCode::Minus),
+        "[" => Some(KeyCode::BracketLeft),
+        "]" => Some(KeyCode::BracketRight),
+        "," => Some(KeyCode::Comma),
+        "." => Some(KeyCode::Period),
+        "UP" => Some(KeyCode::ArrowUp),
+        "DOWN" => Some(KeyCode::ArrowDown),
         _ => None,
     }
 }
@@ -754,6 +1224,20 @@ struct SettingMarginLabel {
     edge: MarginEdge,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WidthElement {
+    Dialogue,
+    Action,
+    WrapGuide,
+    // Index into `INDENT_WIDTH_SETTINGS`.
+    Indent(usize),
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+struct SettingElementWidthLabel {
+    element: WidthElement,
+}
+
 #[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
 enum ThemeColorChannel {
     Hue,
@@ -834,12 +1318,15 @@ enum ThemeColorTarget {
     ExplorerBackground,
     ProcessedBackground,
     SelectionBackground,
+    WrapGuide,
     LinkFallback,
     LinkProp,
     LinkPlace,
     LinkCharacter,
     LinkFaction,
     LinkConcept,
+    // Index into `ELEMENT_COLOR_SETTINGS`.
+    Element(usize),
 }
 
 impl ThemeColorTarget {
@@ -855,7 +1342,7 @@ impl ThemeColorTarget {
         if self.is_link_color() {
             "Adjust processed-view link colors by YAML `type`. Unmapped types use Fallback, and hover uses the HSV value offset."
         } else {
-            "Adjust editor shell colors, selection colors, and glass surfaces."
+            "Adjust editor shell colors, screenplay element colors, selection colors, and glass surfaces."
         }
     }
 
@@ -866,12 +1353,14 @@ impl ThemeColorTarget {
             Self::ExplorerBackground => "Explorer",
             Self::ProcessedBackground => "Processed pane",
             Self::SelectionBackground => "Selection background",
+            Self::WrapGuide => "Wrap guide",
             Self::LinkFallback => "Fallback",
             Self::LinkProp => "Prop",
             Self::LinkPlace => "Place",
             Self::LinkCharacter => "Character",
             Self::LinkFaction => "Faction",
             Self::LinkConcept => "Concept",
+            Self::Element(index) => PROCESSED_KIND_TOGGLES[index].2,
         }
     }
 
@@ -882,12 +1371,14 @@ impl ThemeColorTarget {
             Self::ExplorerBackground => "explorer background",
             Self::ProcessedBackground => "processed pane background",
             Self::SelectionBackground => "selection background",
+            Self::WrapGuide => "wrap guide color",
             Self::LinkFallback => "fallback link color",
             Self::LinkProp => "prop link color",
             Self::LinkPlace => "place link color",
             Self::LinkCharacter => "character link color",
             Self::LinkFaction => "faction link color",
             Self::LinkConcept => "concept link color",
+            Self::Element(index) => ELEMENT_COLOR_SETTINGS[index].1,
         }
     }
 
@@ -923,6 +1414,26 @@ struct ThemeSelectionRgbLabel;
 #[derive(Component)]
 struct ThemeSelectionHexLabel;
 
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct FindBar {
+    query: String,
+    replacement: String,
+    // Typing goes to the replacement instead of the query; Tab switches.
+    editing_replacement: bool,
+    // The selection when the bar opened. While `in_selection` is on, matches and
+    // replacements stay inside it, and replace-all keeps it around the new text.
+    selection: Option<Range<Position>>,
+    in_selection: bool,
+    matches: Vec<Range<Position>>,
+    current: Option<usize>,
+}
+
+impl FindBar {
+    fn scope(&self) -> Option<Range<Position>> {
+        self.selection.clone().filter(|_| self.in_selection)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct HoveredProcessedLink {
     source_line: usize,
@@ -933,16 +1444,38 @@ struct HoveredProcessedLink {
 #[derive(Resource)]
 struct EditorState {
     document: Document,
+    saved_document: Document,
+    is_modified: bool,
+    // Insert toggles typing over the char under the caret.
+    overwrite: bool,
+    // No file on disk backs the document until it is first saved.
+    untitled: bool,
+    pending_load_path: Option<PathBuf>,
+    // Modified time of `load_path` when it was last loaded, saved or reported as
+    // changed, so edits made by other programs can be noticed.
+    load_path_modified: Option<SystemTime>,
+    external_change_timer: Timer,
     parsed: Vec<ParsedLine>,
     document_format: DocumentFormat,
     cursor: Cursor,
+    // Extra carets that typing and deleting also apply to; never the main caret.
+    secondary_cursors: Vec<Position>,
     selection_anchor: Option<Position>,
     top_line: usize,
+    // Part of a line the Plain pane is scrolled past `top_line` by smooth scrolling.
+    plain_scroll_fraction: f32,
     processed_top_line: usize,
     processed_top_visual: usize,
+    // Page of the first paper slot and the page total, refreshed every render.
+    processed_first_visible_page: usize,
+    processed_page_count: usize,
     display_mode: DisplayMode,
     focused_panel: PanelKind,
     plain_horizontal_scroll: f32,
+    plain_scroll_followed_caret: Option<Position>,
+    // Columns a wrapped Action line fits in; None while wrapping is off. Refreshed
+    // from the pane width every frame.
+    plain_wrap_columns: Option<usize>,
     processed_horizontal_scroll: f32,
     processed_zoom_anchor_bias_px: f32,
     paths: DocumentPath,
@@ -951,9 +1484,11 @@ struct EditorState {
     pending_keybind_capture: Option<ShortcutAction>,
     workspace_sidebar_visible: bool,
     top_menu_collapsed: bool,
+    distraction_free: bool,
     processed_glass: bool,
     explorer_glass: bool,
     settings_glass: bool,
+    dark_mode: bool,
     app_bg_rgba: Vec4,
     app_bg_color: Color,
     top_menu_bg_rgba: Vec4,
@@ -964,6 +1499,8 @@ struct EditorState {
     processed_bg_color: Color,
     selection_bg_rgba: Vec4,
     selection_bg_color: Color,
+    wrap_guide_rgba: Vec4,
+    wrap_guide_color: Color,
     link_fallback_rgba: Vec4,
     link_fallback_color: Color,
     link_prop_rgba: Vec4,
@@ -976,22 +1513,66 @@ struct EditorState {
     link_faction_color: Color,
     link_concept_rgba: Vec4,
     link_concept_color: Color,
+    element_rgba: [Vec4; 6],
+    element_colors: [Color; 6],
     link_hover_hsv_value_adjustment: f32,
     theme_color_target: ThemeColorTarget,
     theme_color_picker_open: bool,
     show_system_titlebar: bool,
     caret_blink: Timer,
     caret_visible: bool,
+    autosave_interval_secs: u64,
+    caret_width: f32,
+    // None follows the theme palette.
+    caret_color: Option<Color>,
+    // Zero keeps the caret solid.
+    caret_blink_secs: f32,
+    autosave_timer: Timer,
+    // Counts down the "Auto-saved" status and holds the message it replaced.
+    autosave_status: Option<(Timer, String)>,
     dialogue_double_space_newline: bool,
     non_dialogue_double_space_newline: bool,
+    processed_right_to_left: bool,
+    smooth_scrolling: bool,
+    wrap_action_lines: bool,
+    trim_trailing_whitespace_on_save: bool,
+    dialogue_page_continuations: bool,
+    auto_number_scenes: bool,
+    show_wrap_guide: bool,
+    autosave_on_focus_loss: bool,
+    unified_processed_edit: bool,
+    processed_cue_ruler: bool,
+    element_width_warnings: bool,
+    focus_current_scene: bool,
+    scene_heading_requires_content: bool,
+    plain_text_mode: bool,
+    processed_kind_labels: bool,
+    auto_uppercase_character_cues: bool,
+    auto_capitalize_sentences: bool,
+    auto_pair_parentheses: bool,
+    dialogue_spans_single_blank: bool,
+    sync_scroll_panes: bool,
+    auto_reload_external_changes: bool,
+    hyphenate_exported_dialogue: bool,
+    page_size: PageSize,
     page_margin_left: f32,
     page_margin_right: f32,
     page_margin_top: f32,
     page_margin_bottom: f32,
+    dialogue_width_columns: usize,
+    action_width_columns: usize,
+    wrap_guide_column: usize,
+    tab_width: usize,
+    indent_widths: IndentWidths,
+    hidden_processed_kinds: Vec<LineKind>,
     zoom: f32,
     measured_line_step: f32,
+    // Set by the recompute chord; the next layout pass measures and logs the step.
+    line_step_remeasure_pending: bool,
     processed_cache: Option<ProcessedCache>,
     processed_cache_dirty_from_line: Option<usize>,
+    // Left by a large insert until its lines have all been classified.
+    chunked_reparse: Option<ChunkedReparse>,
     workspace_root: Option<PathBuf>,
     workspace_files: Vec<WorkspaceFileEntry>,
     workspace_selected: Option<usize>,
@@ -999,15 +1580,24 @@ struct EditorState {
     script_link_target_types: BTreeMap<String, String>,
     missing_script_link_targets: BTreeSet<String>,
     hovered_processed_link: Option<HoveredProcessedLink>,
+    reclassify_preview: Option<Vec<MarkerEdit>>,
+    find_bar: Option<FindBar>,
+    stats_panel_open: bool,
+    outline_open: bool,
     workspace_ui_dirty: bool,
-    undo_history: Vec<EditorHistorySnapshot>,
-    redo_history: Vec<EditorHistorySnapshot>,
+    // Scroll positions for each undo and redo step the document holds, in the
+    // same order, so stepping through history also puts the view back.
+    undo_views: Vec<EditorHistoryView>,
+    redo_views: Vec<EditorHistoryView>,
 }
 
-#[derive(Clone)]
 struct EditorHistorySnapshot {
-    document: Document,
-    cursor: Cursor,
+    edit: EditSnapshot,
+    view: EditorHistoryView,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct EditorHistoryView {
     top_line: usize,
     processed_top_line: usize,
     processed_top_visual: usize,
@@ -1066,22 +1656,88 @@ struct PanelSplitterDragState {
     suppress_next_left_click: bool,
 }
 
+#[derive(Resource, Default, Clone, Copy, Debug)]
+struct ScrollbarDragState {
+    // The panel whose scrollbar took the current left press.
+    active: Option<PanelKind>,
+    // Where the thumb was grabbed, as a fraction of the track; None for track clicks.
+    grab_offset: Option<f32>,
+}
+
 enum PendingDialog {
     Workspace(Task<Option<PathBuf>>),
     Save(Task<Option<PathBuf>>),
+    SaveBundle(Task<Option<PathBuf>>),
+    ExportNotes(Task<Option<PathBuf>>),
+    ExportFountain(Task<Option<PathBuf>>),
+    ExportHtml(Task<Option<PathBuf>>),
+    ExportText(Task<Option<PathBuf>>),
 }
 
 struct DialogMainThreadMarker;
 
-#[derive(Clone, Debug)]
+// Read and written whole through serde; fields missing from the file keep
+// their defaults and `sanitized` clamps what was read.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 struct PersistentSettings {
+    #[serde(alias = "parenthetical_double_space_newline")]
     dialogue_double_space_newline: bool,
     non_dialogue_double_space_newline: bool,
     show_system_titlebar: bool,
+    processed_right_to_left: bool,
+    smooth_scrolling: bool,
+    wrap_action_lines: bool,
+    trim_trailing_whitespace_on_save: bool,
+    dialogue_page_continuations: bool,
+    auto_number_scenes: bool,
+    show_wrap_guide: bool,
+    autosave_on_focus_loss: bool,
+    unified_processed_edit: bool,
+    processed_cue_ruler: bool,
+    element_width_warnings: bool,
+    focus_current_scene: bool,
+    scene_heading_requires_content: bool,
+    processed_kind_labels: bool,
+    auto_uppercase_character_cues: bool,
+    auto_capitalize_sentences: bool,
+    auto_pair_parentheses: bool,
+    dialogue_spans_single_blank: bool,
+    sync_scroll_panes: bool,
+    auto_reload_external_changes: bool,
+    hyphenate_exported_dialogue: bool,
+    #[serde(
+        serialize_with = "serialize_page_size",
+        deserialize_with = "deserialize_page_size"
+    )]
+    page_size: PageSize,
     page_margin_left: f32,
     page_margin_right: f32,
     page_margin_top: f32,
     page_margin_bottom: f32,
+    dialogue_width_columns: usize,
+    action_width_columns: usize,
+    wrap_guide_column: usize,
+    tab_width: usize,
+    autosave_interval_secs: u64,
+    caret_width: f32,
+    // None follows the theme palette.
+    #[serde(
+        serialize_with = "serialize_caret_color",
+        deserialize_with = "deserialize_caret_color"
+    )]
+    caret_color: Option<Color>,
+    // Zero keeps the caret solid.
+    caret_blink_secs: f32,
+    indent_widths: IndentWidths,
+    zoom: f32,
+    #[serde(
+        serialize_with = "serialize_processed_kinds",
+        deserialize_with = "deserialize_processed_kinds"
+    )]
+    hidden_processed_kinds: Vec<LineKind>,
+    // Hex strings as written in the file; `element_rgba_from_hex` validates them.
+    element_colors: [String; 6],
     workspace_root_path: Option<String>,
 }
 
@@ -1091,19 +1747,62 @@ impl Default for PersistentSettings {
             dialogue_double_space_newline: false,
             non_dialogue_double_space_newline: false,
             show_system_titlebar: false,
+            processed_right_to_left: false,
+            smooth_scrolling: false,
+            wrap_action_lines: false,
+            trim_trailing_whitespace_on_save: false,
+            dialogue_page_continuations: false,
+            auto_number_scenes: false,
+            show_wrap_guide: false,
+            autosave_on_focus_loss: false,
+            unified_processed_edit: false,
+            processed_cue_ruler: false,
+            element_width_warnings: false,
+            focus_current_scene: false,
+            scene_heading_requires_content: false,
+            processed_kind_labels: false,
+            auto_uppercase_character_cues: false,
+            auto_capitalize_sentences: false,
+            auto_pair_parentheses: false,
+            dialogue_spans_single_blank: false,
+            sync_scroll_panes: false,
+            auto_reload_external_changes: false,
+            hyphenate_exported_dialogue: false,
+            page_size: PageSize::A4,
             page_margin_left: PAGE_TEXT_MARGIN_LEFT,
             page_margin_right: PAGE_TEXT_MARGIN_RIGHT,
             page_margin_top: PAGE_TEXT_MARGIN_TOP,
             page_margin_bottom: PAGE_TEXT_MARGIN_BOTTOM,
+            dialogue_width_columns: DEFAULT_DIALOGUE_WIDTH_COLUMNS,
+            action_width_columns: DEFAULT_ACTION_WIDTH_COLUMNS,
+            wrap_guide_column: DEFAULT_WRAP_GUIDE_COLUMN,
+            tab_width: DEFAULT_TAB_WIDTH,
+            autosave_interval_secs: DEFAULT_AUTOSAVE_INTERVAL_SECS,
+            caret_width: CARET_WIDTH,
+            caret_color: None,
+            caret_blink_secs: DEFAULT_CARET_BLINK_SECS,
+            indent_widths: IndentWidths::default(),
+            zoom: 1.0,
+            hidden_processed_kinds: Vec::new(),
+            element_colors: ELEMENT_COLOR_SETTINGS.map(|(_, _, color)| color_hex(color)),
             workspace_root_path: None,
         }
     }
 }
 
+// `settings/recovery.ron`: the cursor state is only restored for the document
+// it was saved against.
+#[derive(Debug, Serialize, Deserialize)]
+struct RecoveryFile {
+    document_path: PathBuf,
+    state: RecoveryState,
+}
+
 #[derive(Clone, Debug)]
 struct PersistentUiState {
     workspace_sidebar_visible: bool,
     top_menu_collapsed: bool,
+    distraction_free: bool,
 }
 
 impl Default for PersistentUiState {
@@ -1111,6 +1810,7 @@ impl Default for PersistentUiState {
         Self {
             workspace_sidebar_visible: true,
             top_menu_collapsed: false,
+            distraction_free: false,
         }
     }
 }
@@ -1122,6 +1822,7 @@ struct ThemeSettings {
     explorer_background: Vec4,
     processed_background: Vec4,
     selection_background: Vec4,
+    wrap_guide: Vec4,
     link_fallback: Vec4,
     link_prop: Vec4,
     link_place: Vec4,
@@ -1132,6 +1833,7 @@ struct ThemeSettings {
     processed_glass: bool,
     explorer_glass: bool,
     settings_glass: bool,
+    dark_mode: bool,
 }
 
 impl Default for ThemeSettings {
@@ -1142,6 +1844,7 @@ impl Default for ThemeSettings {
             explorer_background: Vec4::new(0.86, 0.87, 0.89, 1.0),
             processed_background: Vec4::new(0.82, 0.83, 0.84, 1.0),
             selection_background: Vec4::new(0.16, 0.43, 0.88, 0.36),
+            wrap_guide: Vec4::new(0.72, 0.30, 0.26, 0.45),
             link_fallback: Vec4::new(0.10, 0.38, 0.72, 1.0),
             link_prop: Vec4::new(0.68, 0.40, 0.10, 1.0),
             link_place: Vec4::new(0.12, 0.50, 0.34, 1.0),
@@ -1152,11 +1855,26 @@ impl Default for ThemeSettings {
             processed_glass: false,
             explorer_glass: false,
             settings_glass: false,
+            dark_mode: false,
         }
     }
 }
 
 impl ThemeSettings {
+    // The backgrounds a switch to dark mode starts from; the link colors and glass
+    // toggles are left to the user's theme.
+    fn dark() -> Self {
+        Self {
+            app_background: Vec4::new(0.13, 0.14, 0.16, 1.0),
+            top_menu_background: Vec4::new(0.13, 0.14, 0.16, 1.0),
+            explorer_background: Vec4::new(0.16, 0.17, 0.19, 1.0),
+            processed_background: Vec4::new(0.20, 0.21, 0.23, 1.0),
+            selection_background: Vec4::new(0.30, 0.52, 0.95, 0.40),
+            dark_mode: true,
+            ..Self::default()
+        }
+    }
+
     fn app_background_clamped(&self) -> Vec4 {
         Vec4::new(
             self.app_background.x.clamp(0.0, 1.0),
@@ -1302,6 +2020,20 @@ impl ThemeSettings {
         Color::srgba(rgba.x, rgba.y, rgba.z, rgba.w)
     }
 
+    fn wrap_guide_clamped(&self) -> Vec4 {
+        Vec4::new(
+            self.wrap_guide.x.clamp(0.0, 1.0),
+            self.wrap_guide.y.clamp(0.0, 1.0),
+            self.wrap_guide.z.clamp(0.0, 1.0),
+            self.wrap_guide.w.clamp(0.0, 1.0),
+        )
+    }
+
+    fn wrap_guide_color(&self) -> Color {
+        let rgba = self.wrap_guide_clamped();
+        Color::srgba(rgba.x, rgba.y, rgba.z, rgba.w)
+    }
+
     fn link_concept_clamped(&self) -> Vec4 {
         Vec4::new(
             self.link_concept.x.clamp(0.0, 1.0),
@@ -1393,6 +2125,11 @@ impl PendingDialog {
         match self {
             PendingDialog::Workspace(_) => "workspace",
             PendingDialog::Save(_) => "save",
+            PendingDialog::SaveBundle(_) => "save bundle",
+            PendingDialog::ExportNotes(_) => "export notes",
+            PendingDialog::ExportFountain(_) => "export fountain",
+            PendingDialog::ExportHtml(_) => "export html",
+            PendingDialog::ExportText(_) => "export text",
         }
     }
 }
@@ -1405,6 +2142,7 @@ impl FromWorld for EditorState {
         let theme_settings = load_theme_settings();
         let saved_workspace_root = settings.workspace_root_path.clone();
         let keybinds = load_keybind_settings();
+        let untitled = !paths.load_path.is_file();
         let (document, document_format, status_message) = match Document::load(&paths.load_path) {
             Ok(doc) => {
                 let format = detect_document_format(&paths.load_path, &doc);
@@ -1432,31 +2170,89 @@ impl FromWorld for EditorState {
             }
         };
 
-        let parsed = parse_document_with_format(&document, document_format);
+        let mut next = Self::new(
+            document,
+            document_format,
+            paths,
+            &settings,
+            &ui_state,
+            &theme_settings,
+            keybinds,
+        );
+        next.untitled = untitled;
+        next.load_path_modified = file_modified_time(&next.paths.load_path);
+        next.status_message = format!("{status_message} {}", next.status_message)
+            .trim_end()
+            .to_owned();
+        let initial_status = next.status_message.clone();
+        apply_initial_workspace_root(&mut next, &initial_status, saved_workspace_root.as_deref());
+        next.restore_recovery_state();
+        next
+    }
+}
+
+impl EditorState {
+    // Builds the state from already-loaded settings without touching the disk.
+    fn new(
+        document: Document,
+        document_format: DocumentFormat,
+        paths: DocumentPath,
+        settings: &PersistentSettings,
+        ui_state: &PersistentUiState,
+        theme_settings: &ThemeSettings,
+        keybinds: KeybindSettings,
+    ) -> Self {
+        let (element_rgba, invalid_element_colors) =
+            element_rgba_from_hex(&settings.element_colors);
+        let parse_options = ParseOptions {
+            scene_heading_requires_content: settings.scene_heading_requires_content,
+            dialogue_spans_single_blank: settings.dialogue_spans_single_blank,
+            all_action: ParseOptions::for_path(&paths.load_path).all_action,
+            indent_widths: settings.indent_widths,
+        };
+        let parsed = parse_document_with_options(&document, document_format, &parse_options);
 
         let mut next = Self {
+            saved_document: document.without_history(),
+            is_modified: false,
+            overwrite: false,
+            untitled: false,
+            pending_load_path: None,
+            load_path_modified: None,
+            external_change_timer: Timer::from_seconds(
+                EXTERNAL_CHANGE_CHECK_SECS,
+                TimerMode::Repeating,
+            ),
             document,
             parsed,
             document_format,
             cursor: Cursor::default(),
+            secondary_cursors: Vec::new(),
             selection_anchor: None,
             top_line: 0,
+            plain_scroll_fraction: 0.0,
             processed_top_line: 0,
             processed_top_visual: 0,
+            processed_first_visible_page: 0,
+            processed_page_count: 0,
             display_mode: DisplayMode::Split,
             focused_panel: PanelKind::Plain,
             plain_horizontal_scroll: 0.0,
+            plain_scroll_followed_caret: None,
+            plain_wrap_columns: None,
             processed_horizontal_scroll: 0.0,
             processed_zoom_anchor_bias_px: 0.0,
             paths,
-            status_message,
+            status_message: String::new(),
             keybinds,
             pending_keybind_capture: None,
             workspace_sidebar_visible: ui_state.workspace_sidebar_visible,
             top_menu_collapsed: ui_state.top_menu_collapsed,
+            distraction_free: ui_state.distraction_free,
             processed_glass: theme_settings.processed_glass,
             explorer_glass: theme_settings.explorer_glass,
             settings_glass: theme_settings.settings_glass,
+            dark_mode: theme_settings.dark_mode,
             app_bg_rgba: theme_settings.app_background_clamped(),
             app_bg_color: theme_settings.app_background_color(),
             top_menu_bg_rgba: theme_settings.top_menu_background_clamped(),
@@ -1467,6 +2263,8 @@ impl FromWorld for EditorState {
             processed_bg_color: theme_settings.processed_background_color(),
             selection_bg_rgba: theme_settings.selection_background_clamped(),
             selection_bg_color: theme_settings.selection_background_color(),
+            wrap_guide_rgba: theme_settings.wrap_guide_clamped(),
+            wrap_guide_color: theme_settings.wrap_guide_color(),
             link_fallback_rgba: theme_settings.link_fallback_clamped(),
             link_fallback_color: theme_settings.link_fallback_color(),
             link_prop_rgba: theme_settings.link_prop_clamped(),
@@ -1479,23 +2277,68 @@ impl FromWorld for EditorState {
             link_faction_color: theme_settings.link_faction_color(),
             link_concept_rgba: theme_settings.link_concept_clamped(),
             link_concept_color: theme_settings.link_concept_color(),
+            element_rgba,
+            element_colors: element_rgba.map(color_from_rgba),
             link_hover_hsv_value_adjustment: theme_settings
                 .link_hover_hsv_value_adjustment_clamped(),
             theme_color_target: ThemeColorTarget::AppBackground,
             theme_color_picker_open: false,
             show_system_titlebar: settings.show_system_titlebar,
-            caret_blink: Timer::from_seconds(0.5, TimerMode::Repeating),
+            caret_blink: Timer::from_seconds(
+                settings.caret_blink_secs.max(MIN_CARET_BLINK_SECS),
+                TimerMode::Repeating,
+            ),
             caret_visible: true,
+            autosave_interval_secs: settings.autosave_interval_secs,
+            caret_width: settings.caret_width,
+            caret_color: settings.caret_color,
+            caret_blink_secs: settings.caret_blink_secs,
+            autosave_timer: Timer::from_seconds(
+                settings.autosave_interval_secs.max(1) as f32,
+                TimerMode::Repeating,
+            ),
+            autosave_status: None,
             dialogue_double_space_newline: settings.dialogue_double_space_newline,
             non_dialogue_double_space_newline: settings.non_dialogue_double_space_newline,
+            processed_right_to_left: settings.processed_right_to_left,
+            smooth_scrolling: settings.smooth_scrolling,
+            wrap_action_lines: settings.wrap_action_lines,
+            trim_trailing_whitespace_on_save: settings.trim_trailing_whitespace_on_save,
+            dialogue_page_continuations: settings.dialogue_page_continuations,
+            auto_number_scenes: settings.auto_number_scenes,
+            show_wrap_guide: settings.show_wrap_guide,
+            autosave_on_focus_loss: settings.autosave_on_focus_loss,
+            unified_processed_edit: settings.unified_processed_edit,
+            processed_cue_ruler: settings.processed_cue_ruler,
+            element_width_warnings: settings.element_width_warnings,
+            focus_current_scene: settings.focus_current_scene,
+            scene_heading_requires_content: settings.scene_heading_requires_content,
+            plain_text_mode: parse_options.all_action,
+            processed_kind_labels: settings.processed_kind_labels,
+            auto_uppercase_character_cues: settings.auto_uppercase_character_cues,
+            auto_capitalize_sentences: settings.auto_capitalize_sentences,
+            auto_pair_parentheses: settings.auto_pair_parentheses,
+            dialogue_spans_single_blank: settings.dialogue_spans_single_blank,
+            sync_scroll_panes: settings.sync_scroll_panes,
+            auto_reload_external_changes: settings.auto_reload_external_changes,
+            hyphenate_exported_dialogue: settings.hyphenate_exported_dialogue,
+            page_size: settings.page_size,
             page_margin_left: settings.page_margin_left,
             page_margin_right: settings.page_margin_right,
             page_margin_top: settings.page_margin_top,
             page_margin_bottom: settings.page_margin_bottom,
-            zoom: 1.0,
-            measured_line_step: LINE_HEIGHT,
+            dialogue_width_columns: settings.dialogue_width_columns,
+            action_width_columns: settings.action_width_columns,
+            wrap_guide_column: settings.wrap_guide_column,
+            tab_width: settings.tab_width,
+            indent_widths: settings.indent_widths,
+            hidden_processed_kinds: settings.hidden_processed_kinds.clone(),
+            zoom: settings.zoom,
+            measured_line_step: LINE_HEIGHT * settings.zoom,
+            line_step_remeasure_pending: false,
             processed_cache: None,
             processed_cache_dirty_from_line: Some(0),
+            chunked_reparse: None,
             workspace_root: None,
             workspace_files: Vec::new(),
             workspace_selected: None,
@@ -1503,18 +2346,27 @@ impl FromWorld for EditorState {
             script_link_target_types: BTreeMap::new(),
             missing_script_link_targets: BTreeSet::new(),
             hovered_processed_link: None,
+            reclassify_preview: None,
+            find_bar: None,
+            stats_panel_open: false,
+            outline_open: false,
             workspace_ui_dirty: true,
-            undo_history: Vec::new(),
-            redo_history: Vec::new(),
+            undo_views: Vec::new(),
+            redo_views: Vec::new(),
         };
         normalize_page_margins(&mut next);
-        let initial_status = next.status_message.clone();
-        apply_initial_workspace_root(&mut next, &initial_status, saved_workspace_root.as_deref());
+        if !invalid_element_colors.is_empty() {
+            next.status_message = format!(
+                "Invalid {}; using the default.",
+                invalid_element_colors.join(", ")
+            );
+        }
+        if next.unified_processed_edit {
+            next.set_display_mode(DisplayMode::Processed);
+        }
         next
     }
-}
 
-impl EditorState {
     fn any_glass_enabled(&self) -> bool {
         self.processed_glass || self.explorer_glass || self.settings_glass
     }
@@ -1532,6 +2384,21 @@ impl EditorState {
         true
     }
 
+    // A single-pane view shortcut pressed again returns to the split view.
+    fn toggle_display_mode(&mut self, mode: DisplayMode) -> bool {
+        let next = if self.display_mode == mode {
+            DisplayMode::Split
+        } else {
+            mode
+        };
+        self.set_display_mode(next)
+    }
+
+    // Unified editing keeps one processed pane and moves the caret by wrapped rows.
+    fn unified_processed_edit_active(&self) -> bool {
+        self.unified_processed_edit && self.display_mode == DisplayMode::Processed
+    }
+
     fn active_panel_for_display_mode(&self) -> PanelKind {
         match self.display_mode {
             DisplayMode::Split => self.focused_panel,
@@ -1544,6 +2411,18 @@ impl EditorState {
         self.display_mode.panel_visible(panel)
     }
 
+    fn page_layout(&self) -> PageLayout {
+        PageLayout {
+            size: self.page_size,
+            margins: PageMargins {
+                left: self.page_margin_left,
+                right: self.page_margin_right,
+                top: self.page_margin_top,
+                bottom: self.page_margin_bottom,
+            },
+        }
+    }
+
     fn set_zoom(&mut self, zoom: f32) {
         self.zoom = zoom.clamp(ZOOM_MIN, ZOOM_MAX);
         self.measured_line_step = scaled_line_height(self);
@@ -1554,18 +2433,161 @@ impl EditorState {
         (self.zoom * 100.0).round() as u32
     }
 
+    fn focus_scene_range(&self) -> Option<Range<usize>> {
+        if !self.focus_current_scene {
+            return None;
+        }
+
+        let line = self.cursor.position.line;
+        scene_range(&self.parsed, line).or_else(|| structural_range(&self.parsed, line))
+    }
+
+    fn element_width_columns(&self, kind: &LineKind) -> Option<usize> {
+        match kind {
+            LineKind::Dialogue | LineKind::Lyric => Some(self.dialogue_width_columns),
+            LineKind::Action => Some(self.action_width_columns),
+            _ => None,
+        }
+    }
+
+    fn plain_text_options(&self) -> PlainTextOptions {
+        PlainTextOptions {
+            dialogue_width: self.dialogue_width_columns,
+            hyphenate_dialogue: self.hyphenate_exported_dialogue,
+        }
+    }
+
+    fn processed_kind_visible(&self, kind: &LineKind) -> bool {
+        !self.hidden_processed_kinds.contains(kind)
+    }
+
+    fn toggle_processed_kind_visibility(&mut self, kind: &LineKind) -> bool {
+        if let Some(index) = self.hidden_processed_kinds.iter().position(|hidden| hidden == kind) {
+            self.hidden_processed_kinds.remove(index);
+            true
+        } else {
+            self.hidden_processed_kinds.push(kind.clone());
+            false
+        }
+    }
+
+    fn palette(&self) -> ThemePalette {
+        if self.dark_mode {
+            DARK_PALETTE
+        } else {
+            LIGHT_PALETTE
+        }
+    }
+
+    fn parse_options(&self) -> ParseOptions {
+        ParseOptions {
+            scene_heading_requires_content: self.scene_heading_requires_content,
+            dialogue_spans_single_blank: self.dialogue_spans_single_blank,
+            all_action: self.plain_text_mode,
+            indent_widths: self.indent_widths,
+        }
+    }
+
+    // Distraction-free mode overrides the chrome toggles without touching them, so
+    // leaving it restores whatever layout the user had before.
+    fn workspace_sidebar_shown(&self) -> bool {
+        self.workspace_sidebar_visible && !self.distraction_free
+    }
+
+    fn top_menu_hidden(&self) -> bool {
+        self.top_menu_collapsed || self.distraction_free
+    }
+
     fn reparse(&mut self) {
-        self.parsed = parse_document_with_format(&self.document, self.document_format);
+        self.parsed = parse_document_with_options(
+            &self.document,
+            self.document_format,
+            &self.parse_options(),
+        );
+        self.is_modified = self.document != self.saved_document;
         self.missing_script_link_targets.clear();
+        self.chunked_reparse = None;
         self.mark_processed_cache_dirty_from(0);
     }
 
     fn reparse_with_dirty_hint(&mut self, dirty_line: usize) {
-        self.parsed = parse_document_with_format(&self.document, self.document_format);
+        let options = self.parse_options();
+        // An edit during a chunked reparse may land on lines it has not reached,
+        // so the reparse takes it in and carries on from there.
+        if let Some(chunked) = self.chunked_reparse.as_mut() {
+            chunked.extend(
+                &mut self.parsed,
+                &self.document,
+                self.document_format,
+                &options,
+                dirty_line,
+            );
+            self.is_modified = self.document != self.saved_document;
+            self.missing_script_link_targets.clear();
+            self.mark_processed_cache_dirty_from(dirty_line);
+            return;
+        }
+        let scene_count = self.auto_number_scenes.then(|| scene_heading_count(&self.parsed));
+        let outcome = reparse_from(
+            &mut self.parsed,
+            &self.document,
+            self.document_format,
+            &options,
+            dirty_line,
+        );
+        // Every edit reparses, so this is where the dirty flag is kept current. It
+        // compares against the saved text, so undoing back to it clears the flag.
+        self.is_modified = self.document != self.saved_document;
+        self.missing_script_link_targets.clear();
+        // Opening or closing `/*` can hide lines in later cache segments too, and a
+        // new or removed heading renumbers every scene after it.
+        if outcome.boneyard_changed
+            || scene_count.is_some_and(|count| count != scene_heading_count(&self.parsed))
+        {
+            self.processed_cache = None;
+        }
+        self.mark_processed_cache_dirty_from(outcome.reparsed.start.min(dirty_line));
+    }
+
+    // Large inserts leave their lines as action and are classified a slice per
+    // frame by `advance_chunked_reparse`, so a big paste doesn't stall the frame.
+    fn reparse_after_insert(&mut self, dirty_line: usize, inserted_lines: usize) {
+        if inserted_lines < CHUNKED_REPARSE_MIN_LINES || self.chunked_reparse.is_some() {
+            self.reparse_with_dirty_hint(dirty_line);
+            return;
+        }
+
+        let options = self.parse_options();
+        self.chunked_reparse = Some(begin_chunked_reparse(
+            &mut self.parsed,
+            &self.document,
+            self.document_format,
+            &options,
+            dirty_line,
+        ));
+        self.is_modified = self.document != self.saved_document;
         self.missing_script_link_targets.clear();
         self.mark_processed_cache_dirty_from(dirty_line);
     }
 
+    fn advance_chunked_reparse(&mut self, max_lines: usize) {
+        let options = self.parse_options();
+        let Some(chunked) = self.chunked_reparse.as_mut() else {
+            return;
+        };
+        let Some(outcome) = chunked.step(&mut self.parsed, &self.document, &options, max_lines)
+        else {
+            return;
+        };
+
+        self.chunked_reparse = None;
+        self.missing_script_link_targets.clear();
+        // The inserted lines change kind all at once, which can move boneyards and
+        // renumber scenes, so the cache is rebuilt like after a boneyard edit.
+        self.processed_cache = None;
+        self.mark_processed_cache_dirty_from(outcome.reparsed.start);
+    }
+
     fn mark_processed_cache_dirty_from(&mut self, source_line: usize) {
         let dirty_line = source_line.min(self.document.line_count().saturating_sub(1));
         self.processed_cache_dirty_from_line = Some(
@@ -1579,18 +2601,70 @@ impl EditorState {
         self.caret_visible = true;
     }
 
-    fn selection_bounds(&self) -> Option<(Position, Position)> {
-        let anchor = self.selection_anchor?;
-        let head = self.cursor.position;
-        if anchor == head {
-            return None;
+    // Adds a caret unless one is already there. Returns whether it was added.
+    fn add_secondary_cursor(&mut self, position: Position) -> bool {
+        let position = self.document.clamp_position(position);
+        if position == self.cursor.position
+            || self.secondary_cursors.contains(&position)
+            || self.secondary_cursors.len() >= SECONDARY_CARET_CAPACITY
+        {
+            return false;
         }
 
-        if position_is_before_or_equal(anchor, head) {
-            Some((anchor, head))
+        self.selection_anchor = None;
+        self.secondary_cursors.push(position);
+        self.reset_blink();
+        true
+    }
+
+    // Adds a caret one line past the topmost or bottommost caret, at the main
+    // caret's preferred column.
+    fn add_cursor_vertically(&mut self, up: bool) -> bool {
+        let carets = std::iter::once(self.cursor.position)
+            .chain(self.secondary_cursors.iter().copied());
+        let edge = if up {
+            carets.min_by_key(|position| (position.line, position.column))
         } else {
-            Some((head, anchor))
+            carets.max_by_key(|position| (position.line, position.column))
+        };
+        let Some(edge) = edge else {
+            return false;
+        };
+        let preferred_column = self.cursor.preferred_column;
+        let next = if up {
+            self.document.move_up(edge, preferred_column)
+        } else {
+            self.document.move_down(edge, preferred_column)
+        };
+        next.line != edge.line && self.add_secondary_cursor(next)
+    }
+
+    // Runs `edit` at the main caret and every secondary caret, moves each caret to
+    // where its edit left it and returns the main caret's new position with the
+    // first line any caret sat on.
+    fn edit_at_carets(
+        &mut self,
+        edit: impl FnMut(&mut Document, Position) -> Position,
+    ) -> (Position, usize) {
+        let mut carets = vec![self.cursor.position];
+        carets.extend(self.secondary_cursors.iter().copied());
+        let first_line = carets.iter().map(|position| position.line).min().unwrap_or(0);
+
+        let mut moved = self.document.edit_at_positions(&carets, edit);
+        let next = moved.remove(0);
+        moved.retain(|position| *position != next);
+        moved.sort_by_key(|position| (position.line, position.column));
+        moved.dedup();
+        self.secondary_cursors = moved;
+        (next, first_line)
+    }
+
+    fn selection_bounds(&self) -> Option<(Position, Position)> {
+        Selection {
+            anchor: self.selection_anchor?,
+            head: self.cursor.position,
         }
+        .range()
     }
 
     fn delete_selection(&mut self) -> Option<Position> {
@@ -1600,6 +2674,468 @@ impl EditorState {
         Some(next)
     }
 
+    // Tab indents every line a multi-line selection covers and otherwise inserts
+    // spaces at the caret; Shift+Tab dedents the covered lines. Returns the first
+    // line that changed.
+    fn indent_with_tab(&mut self, dedent: bool) -> Option<usize> {
+        let width = self.tab_width;
+        let caret = self.cursor.position;
+        let (start, end) = self.selection_bounds().unwrap_or((caret, caret));
+        if !dedent && start.line == end.line {
+            self.delete_selection();
+            let at = self.cursor.position;
+            let next = self.document.insert_text(at, &" ".repeat(width));
+            self.set_cursor(next, true);
+            return Some(at.line);
+        }
+
+        // A selection ending at column 0 does not reach into its last line.
+        let last_line = if end.line > start.line && end.column == 0 {
+            end.line - 1
+        } else {
+            end.line
+        };
+        let mut first_changed = None;
+        for line in start.line..=last_line {
+            let shift = if dedent {
+                self.document.dedent_line(line, width)
+            } else {
+                self.document.indent_line(line, width)
+            };
+            if shift == 0 {
+                continue;
+            }
+            first_changed.get_or_insert(line);
+
+            let shift_position = |position: &mut Position| {
+                if position.line == line {
+                    position.column = if dedent {
+                        position.column.saturating_sub(shift)
+                    } else {
+                        position.column + shift
+                    };
+                }
+            };
+            shift_position(&mut self.cursor.position);
+            if let Some(anchor) = self.selection_anchor.as_mut() {
+                shift_position(anchor);
+            }
+        }
+        self.cursor.preferred_column = self.cursor.position.column;
+        first_changed
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        let (start, end) = self.selection_bounds()?;
+        Some(self.document.text_in_range(start, end))
+    }
+
+    // Replaces any selection. Line breaks are normalized so each pasted line becomes
+    // its own document line and reparses with the right kind.
+    fn paste_text(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let deleted_at = self.delete_selection();
+        let start = self.cursor.position;
+        let next = self.document.insert_text_bulk(start, &text);
+        self.set_cursor(next, true);
+        let dirty_line = deleted_at.map_or(start.line, |position| position.line.min(start.line));
+        self.reparse_after_insert(dirty_line, next.line - start.line);
+    }
+
+    fn jump_to_different_element(&mut self, forward: bool) -> Option<LineKind> {
+        let line = self.cursor.position.line;
+        let target = if forward {
+            next_different_kind_line(&self.parsed, line)
+        } else {
+            previous_different_kind_line(&self.parsed, line)
+        }?;
+
+        self.set_cursor(Position { line: target, column: 0 }, true);
+        Some(self.parsed[target].kind.clone())
+    }
+
+    // Returns the heading's line, or None when there is no scene that way.
+    fn jump_to_scene_heading(&mut self, forward: bool) -> Option<usize> {
+        let line = self.cursor.position.line;
+        let target = if forward {
+            next_scene_heading_line(&self.parsed, line)
+        } else {
+            previous_scene_heading_line(&self.parsed, line)
+        }?;
+
+        self.set_cursor(Position { line: target, column: 0 }, true);
+        Some(target)
+    }
+
+    fn select_structural_range(&mut self) -> bool {
+        let Some(range) = structural_range(&self.parsed, self.cursor.position.line) else {
+            return false;
+        };
+        let start = Position {
+            line: range.start,
+            column: 0,
+        };
+        let end = if range.end < self.document.line_count() {
+            Position {
+                line: range.end,
+                column: 0,
+            }
+        } else {
+            let line = self.document.line_count().saturating_sub(1);
+            Position {
+                line,
+                column: self.document.line_len_chars(line),
+            }
+        };
+
+        self.set_cursor(start, true);
+        self.set_cursor_with_selection(end, true, true);
+        true
+    }
+
+    // An empty document has nothing to select, so this returns false and leaves
+    // the caret at the start.
+    fn select_all(&mut self) -> bool {
+        let line = self.document.line_count().saturating_sub(1);
+        let end = Position {
+            line,
+            column: self.document.line_len_chars(line),
+        };
+        self.set_cursor(Position::default(), true);
+        self.set_cursor_with_selection(end, true, true);
+        self.selection_anchor.is_some()
+    }
+
+    fn toggle_character_dialogue(&mut self) -> Option<LineKind> {
+        if self.document_format != DocumentFormat::Fountain {
+            return None;
+        }
+
+        let line = self.cursor.position.line;
+        let parsed_line = self.parsed.get(line)?;
+        if parsed_line.kind == LineKind::Empty {
+            return None;
+        }
+
+        let toggled = toggle_character_dialogue_marker(&parsed_line.raw, &parsed_line.kind);
+        let line_len = self.document.line_len_chars(line);
+        let marker_delta = forced_marker_len(&toggled) as isize
+            - forced_marker_len(&parsed_line.raw) as isize;
+        let column = self.cursor.position.column.saturating_add_signed(marker_delta);
+        self.document
+            .delete_range(Position { line, column: 0 }, Position { line, column: line_len });
+        self.document.insert_text(Position { line, column: 0 }, &toggled);
+        self.set_cursor(Position { line, column }, true);
+        self.reparse_with_dirty_hint(line);
+        self.parsed.get(line).map(|parsed_line| parsed_line.kind.clone())
+    }
+
+    // Uppercases a line that parsed as a character cue, so the buffer matches what
+    // the processed pane shows. Returns whether the line changed.
+    fn auto_uppercase_character_cue(&mut self, line: usize) -> bool {
+        if !self.auto_uppercase_character_cues
+            || self
+                .parsed
+                .get(line)
+                .is_none_or(|parsed_line| parsed_line.kind != LineKind::Character)
+            || !self.document.uppercase_line(line)
+        {
+            return false;
+        }
+
+        self.reparse_with_dirty_hint(line);
+        true
+    }
+
+    // Caret lines where a typed lowercase letter may start a sentence. Only Action
+    // lines qualify, and the list is empty while the setting is off.
+    fn sentence_capitalization_lines(&self, ch: char) -> Vec<usize> {
+        if !self.auto_capitalize_sentences || !ch.is_lowercase() {
+            return Vec::new();
+        }
+
+        std::iter::once(self.cursor.position)
+            .chain(self.secondary_cursors.iter().copied())
+            .map(|position| position.line)
+            .filter(|line| {
+                self.parsed
+                    .get(*line)
+                    .is_some_and(|parsed_line| parsed_line.kind == LineKind::Action)
+            })
+            .collect()
+    }
+
+    // Caret lines where `(` auto-closes and `)` steps over a closing paren: lines in
+    // a dialogue block, and blank lines right under one where a parenthetical is
+    // about to be typed. Empty while the setting is off.
+    fn paren_pairing_lines(&self, ch: char) -> Vec<usize> {
+        if !self.auto_pair_parentheses || !matches!(ch, '(' | ')') {
+            return Vec::new();
+        }
+
+        let dialogue_kind = |line: usize| {
+            self.parsed.get(line).is_some_and(|parsed_line| {
+                matches!(
+                    parsed_line.kind,
+                    LineKind::Character
+                        | LineKind::Dialogue
+                        | LineKind::Parenthetical
+                        | LineKind::Lyric
+                )
+            })
+        };
+        std::iter::once(self.cursor.position)
+            .chain(self.secondary_cursors.iter().copied())
+            .map(|position| position.line)
+            .filter(|line| {
+                dialogue_kind(*line)
+                    || (self.document.line(*line).is_some_and(|text| text.trim().is_empty())
+                        && line.checked_sub(1).is_some_and(dialogue_kind))
+            })
+            .collect()
+    }
+
+    fn insert_page_break(&mut self) {
+        let dirty_line = self.cursor.position.line;
+        let next = insert_fountain_page_break(&mut self.document, self.cursor.position);
+        self.set_cursor(next, true);
+        self.reparse_with_dirty_hint(dirty_line);
+    }
+
+    fn delete_current_line(&mut self) {
+        let line = self.cursor.position.line;
+        let next = self.document.delete_line(line);
+        self.set_cursor(next, true);
+        self.reparse_with_dirty_hint(next.line);
+    }
+
+    fn move_current_line(&mut self, up: bool) -> bool {
+        let position = self.cursor.position;
+        let line = if up {
+            self.document.move_line_up(position.line)
+        } else {
+            self.document.move_line_down(position.line)
+        };
+        if line == position.line {
+            return false;
+        }
+
+        self.set_cursor(
+            Position {
+                line,
+                column: position.column,
+            },
+            false,
+        );
+        self.reparse_with_dirty_hint(line.min(position.line));
+        true
+    }
+
+    fn duplicate_current_line(&mut self) {
+        let position = self.cursor.position;
+        let next = self.document.duplicate_line(position);
+        self.set_cursor(next, false);
+        self.reparse_with_dirty_hint(position.line);
+    }
+
+    fn duplicate_current_scene(&mut self) -> bool {
+        let Some(range) = scene_range(&self.parsed, self.cursor.position.line) else {
+            return false;
+        };
+
+        let mut copy = self.document.lines()[range.clone()].to_vec();
+        // The last scene has no trailing gap of its own, so keep the copy off its final line.
+        let needs_separator = range.end == self.document.line_count()
+            && copy.last().is_some_and(|line| !line.trim().is_empty());
+        let separator = usize::from(needs_separator);
+        if needs_separator {
+            copy.insert(0, String::new());
+        }
+
+        self.document.insert_lines(range.end, &copy);
+        self.set_cursor(
+            Position {
+                line: range.end + separator,
+                column: 0,
+            },
+            true,
+        );
+        self.reparse_with_dirty_hint(range.start);
+        true
+    }
+
+    fn open_reclassify_preview(&mut self) -> usize {
+        if self.document_format != DocumentFormat::Fountain {
+            self.reclassify_preview = None;
+            return 0;
+        }
+
+        let edits = propose_forced_markers(&self.parsed, &self.parse_options());
+        let count = edits.len();
+        self.reclassify_preview = (count > 0).then_some(edits);
+        count
+    }
+
+    // Matches are found case-insensitively and the one at or after the caret (or
+    // the current match) is selected, so typing narrows the search in place.
+    fn refresh_find_matches(&mut self) {
+        let origin = self
+            .selection_bounds()
+            .map_or(self.cursor.position, |(start, _)| start);
+        let Some(find_bar) = self.find_bar.as_mut() else {
+            return;
+        };
+        find_bar.matches =
+            find_all_ignoring_case(&self.document, &find_bar.query, find_bar.scope());
+        find_bar.current = None;
+        let first = find_bar
+            .matches
+            .iter()
+            .position(|range| {
+                (range.start.line, range.start.column) >= (origin.line, origin.column)
+            })
+            .or((!find_bar.matches.is_empty()).then_some(0));
+        if let Some(index) = first {
+            self.select_find_match(index);
+        }
+    }
+
+    fn step_find_match(&mut self, forward: bool) -> bool {
+        let Some(find_bar) = self.find_bar.as_ref() else {
+            return false;
+        };
+        let count = find_bar.matches.len();
+        if count == 0 {
+            return false;
+        }
+        let next = match find_bar.current {
+            Some(current) if forward => (current + 1) % count,
+            Some(current) => (current + count - 1) % count,
+            None => 0,
+        };
+        self.select_find_match(next);
+        true
+    }
+
+    fn toggle_find_in_selection(&mut self) {
+        let Some(find_bar) = self.find_bar.as_mut() else {
+            return;
+        };
+        if find_bar.selection.is_none() {
+            self.status_message = "Find: no selection to search in.".to_string();
+            return;
+        }
+        find_bar.in_selection = !find_bar.in_selection;
+        self.status_message = if find_bar.in_selection {
+            "Find: in selection.".to_string()
+        } else {
+            "Find: whole script.".to_string()
+        };
+        self.refresh_find_matches();
+    }
+
+    // Replaces every match in the find scope. In selection mode the selection is
+    // restored around the replaced text, so a second pass stays in the same block.
+    fn replace_all_find_matches(&mut self) -> usize {
+        let Some(find_bar) = self.find_bar.as_ref() else {
+            return 0;
+        };
+        let Some(first_line) = find_bar.matches.first().map(|range| range.start.line) else {
+            return 0;
+        };
+        let query = find_bar.query.clone();
+        let replacement = find_bar.replacement.clone();
+        let scope = find_bar.scope();
+
+        let snapshot = self.history_snapshot();
+        let outcome =
+            replace_all_ignoring_case(&mut self.document, &query, &replacement, scope);
+        if outcome.replaced == 0 {
+            return 0;
+        }
+        self.push_undo_snapshot(snapshot);
+        self.reparse_with_dirty_hint(first_line);
+
+        match outcome.scope.clone() {
+            Some(scope) => {
+                self.set_cursor(scope.start, true);
+                self.set_cursor_with_selection(scope.end, true, true);
+            }
+            None => self.set_cursor(Position { line: first_line, column: 0 }, true),
+        }
+        if let Some(find_bar) = self.find_bar.as_mut() {
+            if outcome.scope.is_some() {
+                find_bar.selection = outcome.scope;
+            }
+            find_bar.matches =
+                find_all_ignoring_case(&self.document, &find_bar.query, find_bar.scope());
+            find_bar.current = None;
+        }
+        outcome.replaced
+    }
+
+    fn select_find_match(&mut self, index: usize) {
+        let Some(find_bar) = self.find_bar.as_mut() else {
+            return;
+        };
+        let Some(range) = find_bar.matches.get(index).cloned() else {
+            return;
+        };
+        find_bar.current = Some(index);
+        self.set_cursor(range.start, true);
+        self.set_cursor_with_selection(range.end, true, true);
+    }
+
+    fn apply_reclassify_preview(&mut self) -> usize {
+        let Some(edits) = self.reclassify_preview.take() else {
+            return 0;
+        };
+
+        let mut cursor = self.cursor.position;
+        let cursor_shift = edits.iter().any(|edit| {
+            edit.line == cursor.line
+                && self.document.line(edit.line) == Some(edit.before.as_str())
+                && cursor.column >= forced_marker_len(&edit.after) - 1
+        });
+        let applied = apply_marker_edits(&mut self.document, &edits);
+        if applied == 0 {
+            return 0;
+        }
+
+        if cursor_shift {
+            cursor.column += 1;
+        }
+        self.set_cursor(cursor, true);
+        self.reparse_with_dirty_hint(edits[0].line);
+        applied
+    }
+
+    fn join_selected_lines(&mut self) -> bool {
+        let (first_line, last_line) = match self.selection_bounds() {
+            Some((start, end)) => {
+                // A selection ending at column 0 does not reach into its last line.
+                let last_line = if end.line > start.line && end.column == 0 {
+                    end.line - 1
+                } else {
+                    end.line
+                };
+                (start.line, last_line.max(start.line + 1))
+            }
+            None => (self.cursor.position.line, self.cursor.position.line + 1),
+        };
+        if last_line >= self.document.line_count() {
+            return false;
+        }
+
+        let mut next = self.cursor.position;
+        for _ in first_line..last_line {
+            next = self.document.join_lines(first_line);
+        }
+        self.set_cursor(next, true);
+        self.reparse_with_dirty_hint(first_line);
+        true
+    }
+
     fn max_top_line(&self, _visible_lines: usize) -> usize {
         self.document.line_count().saturating_sub(1)
     }
@@ -1607,6 +3143,9 @@ impl EditorState {
     fn clamp_scroll(&mut self, visible_lines: usize) {
         let max_top = self.max_top_line(visible_lines);
         self.top_line = self.top_line.min(max_top);
+        if self.top_line == max_top {
+            self.plain_scroll_fraction = 0.0;
+        }
     }
 
     fn clamp_processed_top_line(&mut self) {
@@ -1629,22 +3168,86 @@ impl EditorState {
             .clamp(processed_min, processed_max);
     }
 
+    // Scrolls the Plain pane so the caret's row is on screen and the caret stays a
+    // couple of columns inside it sideways. Runs once per caret move, so wheel
+    // scrolling away from the caret is not undone.
+    fn follow_caret_in_plain_pane(&mut self, plain_panel_size: Option<Vec2>, visible_lines: usize) {
+        if self.plain_scroll_followed_caret == Some(self.cursor.position) {
+            return;
+        }
+        self.plain_scroll_followed_caret = Some(self.cursor.position);
+        let Some(panel_size) = plain_panel_size else {
+            return;
+        };
+
+        // Wrapped rows can push the caret below the pane while its source line is
+        // still within `visible_lines` of the top.
+        let caret = self.cursor.position;
+        let mut rows = visible_plain_rows(self, visible_lines);
+        while self.plain_wrap_columns.is_some()
+            && caret.line > self.top_line
+            && plain_row_index(&rows, caret).is_none()
+        {
+            self.top_line += 1;
+            self.plain_scroll_fraction = 0.0;
+            rows = visible_plain_rows(self, visible_lines);
+        }
+        let column = plain_row_index(&rows, caret)
+            .map_or(caret.column, |index| caret.column - rows[index].start_column);
+
+        let char_width = scaled_char_width(self).max(1.0);
+        let padding = scaled_text_padding_x(self);
+        let margin = char_width * 2.0;
+        let caret_x = padding + column as f32 * char_width;
+        let visible_left = self.plain_horizontal_scroll + padding;
+        let visible_right = self.plain_horizontal_scroll + panel_size.x - padding;
+        if caret_x + margin > visible_right {
+            self.plain_horizontal_scroll = caret_x + margin + padding - panel_size.x;
+        } else if caret_x - margin < visible_left {
+            self.plain_horizontal_scroll = caret_x - margin - padding;
+        }
+        let plain_max = plain_horizontal_scroll_max(self, plain_panel_size);
+        self.plain_horizontal_scroll = self.plain_horizontal_scroll.clamp(0.0, plain_max);
+    }
+
+    fn plain_line_wraps(&self, line: usize) -> bool {
+        self.plain_wrap_columns.is_some()
+            && self
+                .parsed
+                .get(line)
+                .is_some_and(|parsed_line| parsed_line.kind == LineKind::Action)
+    }
+
     fn scroll_by(&mut self, line_delta: isize, visible_lines: usize) {
         let max_top = self.max_top_line(visible_lines) as isize;
         let next = (self.top_line as isize + line_delta).clamp(0, max_top);
         self.top_line = next as usize;
+        self.plain_scroll_fraction = 0.0;
+        self.processed_top_line = self.top_line;
+    }
+
+    fn scroll_plain_smoothly(&mut self, line_delta: f32, visible_lines: usize) -> bool {
+        let max_top = self.max_top_line(visible_lines) as f32;
+        let before = (self.top_line, self.plain_scroll_fraction);
+        let offset = (self.top_line as f32 + self.plain_scroll_fraction + line_delta)
+            .clamp(0.0, max_top);
+        self.top_line = offset.floor() as usize;
+        self.plain_scroll_fraction = offset - offset.floor();
         self.processed_top_line = self.top_line;
+        (self.top_line, self.plain_scroll_fraction) != before
     }
 
     fn ensure_cursor_visible(&mut self, visible_lines: usize) {
         if self.cursor.position.line < self.top_line {
             self.top_line = self.cursor.position.line;
+            self.plain_scroll_fraction = 0.0;
         } else if self.cursor.position.line >= self.top_line + visible_lines {
             self.top_line = self
                 .cursor
                 .position
                 .line
                 .saturating_sub(visible_lines.saturating_sub(1));
+            self.plain_scroll_fraction = 0.0;
         }
 
         self.clamp_scroll(visible_lines);
@@ -1709,29 +3312,239 @@ impl EditorState {
         self.reset_blink();
     }
 
-    fn save_to_path(&mut self, path: PathBuf) {
+    fn has_unsaved_changes(&self) -> bool {
+        self.is_modified
+    }
+
+    // What a save writes. With trim-on-save, trailing spaces come off a copy so
+    // the buffer and caret stay as typed; lines whose double spaces break the
+    // processed pane keep theirs, since those spaces are part of the layout.
+    fn document_to_save(&self) -> Cow<'_, Document> {
+        if !self.trim_trailing_whitespace_on_save {
+            return Cow::Borrowed(&self.document);
+        }
+
+        let kept = self
+            .parsed
+            .iter()
+            .map(|line| should_split_on_double_space(self, &line.kind))
+            .collect::<Vec<_>>();
+        let mut trimmed = self.document.without_history();
+        trimmed.trim_trailing_whitespace(|line| kept.get(line).copied().unwrap_or(false));
+        Cow::Owned(trimmed)
+    }
+
+    // Loading over unsaved edits needs a second request for the same file, or a
+    // save in between (see `resolve_dialog_results`). Returns whether it loaded.
+    fn request_load(&mut self, path: PathBuf) -> bool {
+        if self.is_modified && self.pending_load_path.as_ref() != Some(&path) {
+            self.status_message = format!(
+                "Unsaved changes. Save first, or open {} again to discard them.",
+                status_path_label(&path)
+            );
+            self.pending_load_path = Some(path);
+            return false;
+        }
+
+        self.pending_load_path = None;
+        self.load_from_path(path);
+        true
+    }
+
+    // Called on a timer. A file changed by another program is reloaded when
+    // auto-reload is on and nothing is unsaved; otherwise the next open request for
+    // it loads the disk copy, the same confirmation `request_load` asks for.
+    fn check_external_change(&mut self) {
+        if self.untitled {
+            return;
+        }
+        let path = self.paths.load_path.clone();
+        let modified = file_modified_time(&path);
+        if modified.is_none() || modified == self.load_path_modified {
+            return;
+        }
+
+        self.load_path_modified = modified;
+        if self.auto_reload_external_changes && !self.is_modified {
+            self.load_from_path(path.clone());
+            self.status_message =
+                format!("Reloaded {} after an outside change.", status_path_label(&path));
+            return;
+        }
+
+        if self.is_modified {
+            self.status_message = format!(
+                "{} changed on disk. Open it again to discard your edits and reload.",
+                status_path_label(&path)
+            );
+            self.pending_load_path = Some(path);
+        } else {
+            self.status_message =
+                format!("{} changed on disk. Open it again to reload.", status_path_label(&path));
+        }
+    }
+
+    // Returns whether the document reached the disk.
+    fn save_to_path(&mut self, path: PathBuf) -> bool {
         if let Some(parent) = path.parent() {
             let _ = std::fs::create_dir_all(parent);
         }
 
-        match self.document.save(&path) {
+        let written = self.document_to_save().save(&path);
+        match written {
             Ok(()) => {
+                if let Err(error) = save_recovery_state(&path, &self.recovery_state()) {
+                    warn!("[recovery] Failed writing cursor state: {}", error);
+                }
+                self.saved_document = self.document.without_history();
+                self.is_modified = false;
                 self.paths.save_path = path.clone();
+                if self.untitled {
+                    self.paths.load_path = path.clone();
+                    self.untitled = false;
+                }
+                if path == self.paths.load_path {
+                    self.load_path_modified = file_modified_time(&path);
+                }
                 self.status_message = format!("Saved {}", status_path_label(&path));
+                true
             }
             Err(error) => {
                 self.status_message =
                     format!("Save failed for {}: {error}", status_path_label(&path));
+                false
             }
         }
     }
 
+    // Saves the source as `<base>.fountain` and writes the plain-text export as
+    // `<base>.txt` beside it. The status line reports each file, so a half-written
+    // bundle says which half is missing.
+    fn save_bundle_to(&mut self, base: PathBuf) {
+        let fountain_path = base.with_extension("fountain");
+        let text_path = base.with_extension("txt");
+        let fountain_saved = self.save_to_path(fountain_path.clone());
+        let fountain_status = std::mem::take(&mut self.status_message);
+        let text_written = fs::write(
+            &text_path,
+            export_plain_text(&self.parsed, &self.plain_text_options()),
+        );
+
+        let text_label = status_path_label(&text_path);
+        self.status_message = match (fountain_saved, text_written) {
+            (true, Ok(())) => format!(
+                "Saved bundle {} and {text_label}",
+                status_path_label(&fountain_path)
+            ),
+            (true, Err(error)) => {
+                format!("{fountain_status}, but writing {text_label} failed: {error}")
+            }
+            (false, Ok(())) => format!("{fountain_status}. Wrote {text_label} anyway."),
+            (false, Err(error)) => {
+                format!("{fountain_status}. Writing {text_label} failed too: {error}")
+            }
+        };
+    }
+
+    fn title_page_title(&self) -> Option<String> {
+        match self.document_format {
+            DocumentFormat::Fountain => self.document.title(),
+            DocumentFormat::Markdown => None,
+        }
+    }
+
+    // The title page's `Title:`, falling back to the file name.
+    fn document_title(&self) -> String {
+        self.title_page_title().unwrap_or_else(|| {
+            self.paths
+                .save_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("Untitled")
+                .to_string()
+        })
+    }
+
+    // `*` marks unsaved changes, as in most editors.
+    fn window_title(&self) -> String {
+        let name = self.title_page_title().unwrap_or_else(|| {
+            match self.paths.load_path.file_name().and_then(|name| name.to_str()) {
+                Some(name) if !self.untitled => name.to_string(),
+                _ => "Untitled".to_string(),
+            }
+        });
+        let modified_marker = if self.is_modified { "*" } else { "" };
+        format!("{name}{modified_marker} - BasScript")
+    }
+
+    fn export_revision_notes_to(&mut self, path: PathBuf) {
+        let title = self.document_title();
+        let contents = export_revision_notes(&self.parsed, &title);
+        self.status_message = match std::fs::write(&path, contents) {
+            Ok(()) => format!("Exported revision notes to {}", status_path_label(&path)),
+            Err(error) => format!("Export failed for {}: {error}", status_path_label(&path)),
+        };
+    }
+
+    fn export_processed_fountain_to(&mut self, path: PathBuf) {
+        let contents = export_processed_fountain(&self.parsed);
+        self.status_message = match std::fs::write(&path, contents) {
+            Ok(()) => format!("Exported processed script to {}", status_path_label(&path)),
+            Err(error) => format!("Export failed for {}: {error}", status_path_label(&path)),
+        };
+    }
+
+    fn export_html_to(&mut self, path: PathBuf) {
+        let title = self.document_title();
+        let contents = export_html(&self.parsed, &title, &self.html_palette());
+        self.status_message = match std::fs::write(&path, contents) {
+            Ok(()) => format!("Exported HTML to {}", status_path_label(&path)),
+            Err(error) => format!("Export failed for {}: {error}", status_path_label(&path)),
+        };
+    }
+
+    // The processed pane's look: each kind in the color it is drawn in, on the
+    // paper, over the themed processed background.
+    fn html_palette(&self) -> HtmlPalette {
+        let mut line_colors: Vec<(LineKind, String)> = Vec::new();
+        for line in &self.parsed {
+            if line_colors.iter().any(|(kind, _)| *kind == line.kind) {
+                continue;
+            }
+            let style = processed_line_style_for_kind(
+                &line.kind,
+                line.markdown_heading_level,
+                &self.element_colors,
+            );
+            line_colors.push((line.kind.clone(), color_hex(style.color)));
+        }
+        HtmlPalette {
+            background: color_hex(self.processed_bg_color),
+            paper: color_hex(COLOR_PAPER),
+            text: color_hex(default_line_render_style().color),
+            line_colors,
+        }
+    }
+
+    fn export_plain_text_to(&mut self, path: PathBuf) {
+        let contents = export_plain_text(&self.parsed, &self.plain_text_options());
+        self.status_message = match std::fs::write(&path, contents) {
+            Ok(()) => format!("Exported plain text to {}", status_path_label(&path)),
+            Err(error) => format!("Export failed for {}: {error}", status_path_label(&path)),
+        };
+    }
+
     fn load_from_path(&mut self, path: PathBuf) {
         match Document::load(&path) {
             Ok(document) => {
                 let document_format = detect_document_format(&path, &document);
+                self.saved_document = document.without_history();
+                self.clear_history_views();
+                self.is_modified = false;
+                self.untitled = false;
                 self.document = document;
                 self.document_format = document_format;
+                self.plain_text_mode = ParseOptions::for_path(&path).all_action;
                 self.clear_script_link_target_cache();
                 self.reparse();
                 self.cursor = Cursor::default();
@@ -1742,14 +3555,15 @@ impl EditorState {
                 self.plain_horizontal_scroll = 0.0;
                 self.processed_horizontal_scroll = 0.0;
                 self.processed_zoom_anchor_bias_px = 0.0;
-                self.clear_history();
                 self.paths.load_path = path.clone();
                 self.paths.save_path = path.clone();
+                self.load_path_modified = file_modified_time(&path);
                 self.status_message = format!(
                     "Loaded {} ({}).",
                     status_path_label(&path),
                     document_format_label(self.document_format)
                 );
+                self.restore_recovery_state();
                 self.sync_workspace_selection();
                 self.reset_blink();
             }
@@ -1759,10 +3573,38 @@ impl EditorState {
         }
     }
 
+    fn recovery_state(&self) -> RecoveryState {
+        RecoveryState {
+            cursor: self.cursor,
+            selection: self.selection_anchor.map(|anchor| Selection {
+                anchor,
+                head: self.cursor.position,
+            }),
+        }
+    }
+
+    fn restore_recovery_state(&mut self) {
+        let Some(recovery) = load_recovery_state(&self.paths.load_path) else {
+            return;
+        };
+
+        let recovery = recovery.clamped_to(&self.document);
+        self.cursor = recovery.cursor;
+        self.selection_anchor = recovery.selection.map(|selection| {
+            self.cursor.position = selection.head;
+            selection.anchor
+        });
+    }
+
     fn history_snapshot(&self) -> EditorHistorySnapshot {
         EditorHistorySnapshot {
-            document: self.document.clone(),
-            cursor: self.cursor,
+            edit: self.document.edit_snapshot(self.cursor.position),
+            view: self.history_view(),
+        }
+    }
+
+    fn history_view(&self) -> EditorHistoryView {
+        EditorHistoryView {
             top_line: self.top_line,
             processed_top_line: self.processed_top_line,
             processed_top_visual: self.processed_top_visual,
@@ -1772,47 +3614,67 @@ impl EditorState {
         }
     }
 
-    fn push_history_snapshot(
-        history: &mut Vec<EditorHistorySnapshot>,
+    fn push_undo_snapshot(&mut self, snapshot: EditorHistorySnapshot) {
+        self.push_grouped_undo_snapshot(snapshot, None);
+    }
+
+    fn push_grouped_undo_snapshot(
+        &mut self,
         snapshot: EditorHistorySnapshot,
+        kind: Option<UndoGroupKind>,
     ) {
-        if history.len() >= HISTORY_LIMIT {
-            history.remove(0);
+        let started = self
+            .document
+            .push_undo(snapshot.edit, self.cursor.position, kind);
+        self.redo_views.clear();
+        if started {
+            self.undo_views.push(snapshot.view);
+            let evicted = self
+                .undo_views
+                .len()
+                .saturating_sub(self.document.undo_depth());
+            self.undo_views.drain(..evicted);
         }
-        history.push(snapshot);
     }
 
-    fn push_undo_snapshot(&mut self, snapshot: EditorHistorySnapshot) {
-        Self::push_history_snapshot(&mut self.undo_history, snapshot);
-        self.redo_history.clear();
+    fn clear_history_views(&mut self) {
+        self.undo_views.clear();
+        self.redo_views.clear();
     }
 
-    fn apply_history_snapshot(
+    // The document swapped its text for an undo or redo step; rebuild everything
+    // derived from it and put the caret and view where the step says.
+    fn apply_history_step(
         &mut self,
-        snapshot: EditorHistorySnapshot,
+        position: Position,
+        view: Option<EditorHistoryView>,
         visible_lines: usize,
         plain_panel_size: Option<Vec2>,
         processed_panel_size: Option<Vec2>,
     ) {
-        self.document = snapshot.document;
-        self.parsed = parse_document_with_format(&self.document, self.document_format);
+        self.is_modified = self.document != self.saved_document;
+        self.parsed = parse_document_with_options(
+            &self.document,
+            self.document_format,
+            &self.parse_options(),
+        );
+        self.chunked_reparse = None;
         self.processed_cache = None;
         self.processed_cache_dirty_from_line = Some(0);
 
-        self.cursor = snapshot.cursor;
-        self.cursor.position = self.document.clamp_position(self.cursor.position);
-        self.cursor.preferred_column = self
-            .cursor
-            .preferred_column
-            .min(self.document.line_len_chars(self.cursor.position.line));
+        self.set_cursor(position, true);
         self.selection_anchor = None;
-
-        self.top_line = snapshot.top_line;
-        self.processed_top_line = snapshot.processed_top_line;
-        self.processed_top_visual = snapshot.processed_top_visual;
-        self.plain_horizontal_scroll = snapshot.plain_horizontal_scroll;
-        self.processed_horizontal_scroll = snapshot.processed_horizontal_scroll;
-        self.processed_zoom_anchor_bias_px = snapshot.processed_zoom_anchor_bias_px;
+        self.secondary_cursors.clear();
+
+        if let Some(view) = view {
+            self.top_line = view.top_line;
+            self.plain_scroll_fraction = 0.0;
+            self.processed_top_line = view.processed_top_line;
+            self.processed_top_visual = view.processed_top_visual;
+            self.plain_horizontal_scroll = view.plain_horizontal_scroll;
+            self.processed_horizontal_scroll = view.processed_horizontal_scroll;
+            self.processed_zoom_anchor_bias_px = view.processed_zoom_anchor_bias_px;
+        }
         self.clamp_scroll(visible_lines);
         self.clamp_processed_top_line();
         self.clamp_horizontal_scrolls(plain_panel_size, processed_panel_size);
@@ -1825,14 +3687,15 @@ impl EditorState {
         plain_panel_size: Option<Vec2>,
         processed_panel_size: Option<Vec2>,
     ) -> bool {
-        let Some(snapshot) = self.undo_history.pop() else {
+        let current = self.history_view();
+        let Some(position) = self.document.undo() else {
             return false;
         };
-
-        let current = self.history_snapshot();
-        Self::push_history_snapshot(&mut self.redo_history, current);
-        self.apply_history_snapshot(
-            snapshot,
+        let view = self.undo_views.pop();
+        self.redo_views.push(current);
+        self.apply_history_step(
+            position,
+            view,
             visible_lines,
             plain_panel_size,
             processed_panel_size,
@@ -1846,25 +3709,21 @@ impl EditorState {
         plain_panel_size: Option<Vec2>,
         processed_panel_size: Option<Vec2>,
     ) -> bool {
-        let Some(snapshot) = self.redo_history.pop() else {
+        let current = self.history_view();
+        let Some(position) = self.document.redo() else {
             return false;
         };
-
-        let current = self.history_snapshot();
-        Self::push_history_snapshot(&mut self.undo_history, current);
-        self.apply_history_snapshot(
-            snapshot,
+        let view = self.redo_views.pop();
+        self.undo_views.push(current);
+        self.apply_history_step(
+            position,
+            view,
             visible_lines,
             plain_panel_size,
             processed_panel_size,
         );
         true
     }
-
-    fn clear_history(&mut self) {
-        self.undo_history.clear();
-        self.redo_history.clear();
-    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -1895,8 +3754,8 @@ fn document_format_label(format: DocumentFormat) -> &'static str {
     }
 }
 
-fn position_is_before_or_equal(left: Position, right: Position) -> bool {
-    left.line < right.line || (left.line == right.line && left.column <= right.column)
+fn file_modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
 }
 
 fn detect_document_format(path: &Path, document: &Document) -> DocumentFormat {
@@ -2007,11 +3866,7 @@ fn is_fountain_hint(trimmed: &str) -> bool {
         return true;
     }
 
-    if upper.ends_with(" TO:")
-        || upper == "CUT TO:"
-        || upper == "FADE OUT."
-        || upper == "FADE TO BLACK."
-    {
+    if is_transition(trimmed) {
         return true;
     }
 
@@ -2028,3 +3883,168 @@ fn is_fountain_hint(trimmed: &str) -> bool {
         .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit() || " .()'-".contains(ch))
 }
 
+#[cfg(test)]
+mod editor_state_tests {
+    use super::*;
+
+    fn editor_state_for(text: &str) -> EditorState {
+        EditorState::new(
+            Document::from_text(text),
+            DocumentFormat::Fountain,
+            DocumentPath::new("scripts/test.fountain", "scripts/test.fountain"),
+            &PersistentSettings::default(),
+            &PersistentUiState::default(),
+            &ThemeSettings::default(),
+            KeybindSettings::default(),
+        )
+    }
+
+    #[test]
+    fn trim_on_save_leaves_the_buffer_and_caret_alone() {
+        let mut state = editor_state_for("INT. ROOM\n\nHe runs ");
+        state.trim_trailing_whitespace_on_save = true;
+        let caret = Position { line: 2, column: 8 };
+        state.cursor.set_position(caret);
+
+        assert_eq!(state.document_to_save().to_text(), "INT. ROOM\n\nHe runs");
+        assert_eq!(state.document.line(2), Some("He runs "));
+        assert_eq!(state.cursor.position, caret);
+
+        state.trim_trailing_whitespace_on_save = false;
+        assert_eq!(state.document_to_save().to_text(), "INT. ROOM\n\nHe runs ");
+    }
+
+    #[test]
+    fn empty_document_clicks_selects_and_renders_the_placeholder() {
+        let mut state = editor_state_for("");
+        assert!(state.document.is_empty());
+
+        let rows = visible_plain_rows(&state, 40);
+        assert_eq!(rows.len(), 1);
+        assert_eq!((rows[0].line, rows[0].start_column), (0, 0));
+        for row in [1, 10, 39] {
+            assert_eq!(
+                document_end_past_last_row(&state.document, row, rows.len()),
+                Some(Position::default())
+            );
+        }
+        state.set_cursor(Position::default(), true);
+        assert_eq!(state.cursor.position, Position::default());
+        assert_eq!(state.selection_anchor, None);
+
+        assert!(!state.select_all());
+        assert_eq!(state.selection_anchor, None);
+        assert_eq!(state.cursor.position, Position::default());
+
+        let lines = processed_display_lines(&mut state, 64, 40, 2);
+        let texts = lines
+            .iter()
+            .filter(|line| !line.is_spacer)
+            .map(|line| line.text.trim())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, [EMPTY_DOCUMENT_PLACEHOLDER]);
+        assert!(lines.iter().all(|line| line.source_line == 0));
+    }
+
+    #[test]
+    fn undo_and_redo_restore_text_caret_and_modified_flag() {
+        let mut state = editor_state_for("INT. ROOM");
+        let end = Position { line: 0, column: 9 };
+        state.set_cursor(end, true);
+
+        for ch in "!?".chars() {
+            let snapshot = state.history_snapshot();
+            let next = state.document.insert_char(state.cursor.position, ch);
+            state.set_cursor(next, true);
+            state.push_grouped_undo_snapshot(snapshot, Some(UndoGroupKind::Typing));
+        }
+        assert!(state.document != state.saved_document);
+
+        assert!(state.undo(40, None, None));
+        assert_eq!(state.document.to_text(), "INT. ROOM");
+        assert_eq!(state.cursor.position, end);
+        assert!(!state.is_modified);
+        assert!(!state.undo(40, None, None));
+
+        assert!(state.redo(40, None, None));
+        assert_eq!(state.document.to_text(), "INT. ROOM!?");
+        assert_eq!(
+            state.cursor.position,
+            Position {
+                line: 0,
+                column: 11
+            }
+        );
+        assert!(state.is_modified);
+    }
+
+    #[test]
+    fn undo_and_redo_put_the_view_back_where_each_step_was() {
+        let text = vec!["He runs."; 60].join("\n");
+        let mut state = editor_state_for(&text);
+        let caret = Position {
+            line: 25,
+            column: 8,
+        };
+        state.set_cursor(caret, true);
+        state.top_line = 20;
+        state.processed_top_line = 22;
+
+        let snapshot = state.history_snapshot();
+        let next = state.document.insert_char(caret, '!');
+        state.set_cursor(next, true);
+        state.push_undo_snapshot(snapshot);
+
+        state.top_line = 3;
+        state.processed_top_line = 4;
+        assert!(state.undo(10, None, None));
+        assert_eq!((state.top_line, state.processed_top_line), (20, 22));
+
+        state.top_line = 40;
+        assert!(state.redo(10, None, None));
+        assert_eq!((state.top_line, state.processed_top_line), (3, 4));
+        assert!(state.undo(10, None, None));
+        assert_eq!((state.top_line, state.processed_top_line), (40, 22));
+    }
+
+    #[test]
+    fn join_lines_leaves_out_the_line_a_selection_ends_at_column_zero_of() {
+        let mut state = editor_state_for("BOB\nI was going\nto say\nsomething.");
+        state.set_cursor(Position { line: 3, column: 0 }, true);
+        state.selection_anchor = Some(Position { line: 1, column: 0 });
+
+        assert!(state.join_selected_lines());
+        assert_eq!(
+            state.document.lines(),
+            ["BOB", "I was going to say", "something."]
+        );
+    }
+
+    #[test]
+    fn html_palette_follows_element_colors_and_dark_mode() {
+        let mut state = editor_state_for("INT. ROOM - DAY\n\nBOB\nHi.");
+        state.element_rgba[2] = Vec4::new(1.0, 0.0, 0.0, 1.0);
+        sync_theme_colors(&mut state);
+        let light = state.html_palette();
+        assert_eq!(light.paper, color_hex(COLOR_PAPER));
+        assert!(
+            light
+                .line_colors
+                .contains(&(LineKind::Character, "#FF0000".to_string()))
+        );
+        assert!(
+            light
+                .line_colors
+                .contains(&(LineKind::SceneHeading, color_hex(state.element_colors[0])))
+        );
+
+        set_dark_mode(&mut state, true);
+        let dark = state.html_palette();
+        assert_eq!(dark.background, color_hex(state.processed_bg_color));
+        assert_ne!(dark.background, light.background);
+        assert!(
+            dark.line_colors
+                .contains(&(LineKind::Character, "#FF0000".to_string()))
+        );
+    }
+}
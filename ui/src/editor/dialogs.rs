@@ -11,6 +11,10 @@ fn shift_modifier_pressed(keys: &ButtonInput<KeyCode>) -> bool {
     keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
 }
 
+fn alt_modifier_pressed(keys: &ButtonInput<KeyCode>) -> bool {
+    keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+}
+
 fn shortcut_just_pressed(keys: &ButtonInput<KeyCode>, binding: ShortcutBinding) -> bool {
     if !shortcut_modifier_pressed(keys) {
         return false;
@@ -27,6 +31,18 @@ fn shortcut_just_pressed(keys: &ButtonInput<KeyCode>, binding: ShortcutBinding)
     keys.just_pressed(binding.key)
 }
 
+// Motion shortcuts also fire with Shift held so it can extend the selection.
+fn motion_shortcut_just_pressed(keys: &ButtonInput<KeyCode>, binding: ShortcutBinding) -> bool {
+    if !shortcut_modifier_pressed(keys) {
+        return false;
+    }
+    if binding.shift && !shift_modifier_pressed(keys) {
+        return false;
+    }
+
+    keys.just_pressed(binding.key)
+}
+
 fn handle_window_shortcuts(
     keys: Res<ButtonInput<KeyCode>>,
     mut state: ResMut<EditorState>,
@@ -79,11 +95,41 @@ fn handle_window_shortcuts(
         handled = true;
     }
 
+    let distraction_free_binding = state.keybinds.binding(ShortcutAction::ToggleDistractionFree);
+    if shortcut_just_pressed(&keys, distraction_free_binding) {
+        state.distraction_free = !state.distraction_free;
+        let mode = if state.distraction_free { "ON" } else { "OFF" };
+        state.status_message = format!("Distraction-free: {mode}");
+        if let Err(error) = save_editor_ui_state(&state) {
+            warn!("[state] Failed saving UI state: {error}");
+            state.status_message = format!("Distraction-free: {mode} (state save failed: {error})");
+        }
+        info!("[ui] Distraction-free shortcut toggled mode to {mode}");
+        handled = true;
+    }
+
     if !handled {
         return;
     }
 }
 
+fn sync_window_title(
+    state: Res<EditorState>,
+    mut primary_window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    let Ok(mut primary_window) = primary_window_query.single_mut() else {
+        return;
+    };
+
+    let title = state.window_title();
+    if primary_window.title != title {
+        primary_window.title = title;
+    }
+}
+
 fn sync_window_chrome(
     state: Res<EditorState>,
     mut native_glass_state: ResMut<NativeGlassState>,
@@ -421,6 +467,196 @@ fn open_save_dialog(
     state.status_message = "Opening save dialog...".to_string();
 }
 
+fn open_save_bundle_dialog(
+    state: &mut EditorState,
+    dialogs: &mut DialogState,
+    parent_handle: Option<&RawHandleWrapper>,
+) {
+    if dialogs.pending.is_some() {
+        warn!("[dialog] Ignoring save bundle request because a dialog is already pending");
+        state.status_message = "A file dialog is already open.".to_string();
+        return;
+    }
+
+    let stem = state
+        .paths
+        .save_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("script");
+    let mut dialog = AsyncFileDialog::new()
+        .set_title("Save Bundle")
+        .add_filter("Fountain files", &["fountain"])
+        .set_file_name(format!("{stem}.fountain"));
+    if let Some(directory) = preferred_dialog_directory(state) {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog = attach_dialog_parent(dialog, parent_handle);
+
+    let request = dialog.save_file();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        request
+            .await
+            .map(|file_handle| file_handle.path().to_path_buf())
+    });
+
+    dialogs.begin_pending(PendingDialog::SaveBundle(task));
+    info!("[dialog] Save bundle dialog task spawned");
+    state.status_message = "Opening save dialog...".to_string();
+}
+
+fn open_export_notes_dialog(
+    state: &mut EditorState,
+    dialogs: &mut DialogState,
+    parent_handle: Option<&RawHandleWrapper>,
+) {
+    if dialogs.pending.is_some() {
+        warn!("[dialog] Ignoring export request because a dialog is already pending");
+        state.status_message = "A file dialog is already open.".to_string();
+        return;
+    }
+
+    let stem = state
+        .paths
+        .save_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("script");
+    let mut dialog = AsyncFileDialog::new()
+        .set_title("Export Revision Notes")
+        .add_filter("Text files", &["txt"])
+        .set_file_name(format!("{stem}-notes.txt"));
+    if let Some(directory) = preferred_dialog_directory(state) {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog = attach_dialog_parent(dialog, parent_handle);
+
+    let request = dialog.save_file();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        request
+            .await
+            .map(|file_handle| file_handle.path().to_path_buf())
+    });
+
+    dialogs.begin_pending(PendingDialog::ExportNotes(task));
+    info!("[dialog] Export notes dialog task spawned");
+    state.status_message = "Opening export dialog...".to_string();
+}
+
+fn open_export_fountain_dialog(
+    state: &mut EditorState,
+    dialogs: &mut DialogState,
+    parent_handle: Option<&RawHandleWrapper>,
+) {
+    if dialogs.pending.is_some() {
+        warn!("[dialog] Ignoring export request because a dialog is already pending");
+        state.status_message = "A file dialog is already open.".to_string();
+        return;
+    }
+
+    let stem = state
+        .paths
+        .save_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("script");
+    let mut dialog = AsyncFileDialog::new()
+        .set_title("Export Processed Fountain")
+        .add_filter("Fountain files", &["fountain"])
+        .set_file_name(format!("{stem}-processed.fountain"));
+    if let Some(directory) = preferred_dialog_directory(state) {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog = attach_dialog_parent(dialog, parent_handle);
+
+    let request = dialog.save_file();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        request
+            .await
+            .map(|file_handle| file_handle.path().to_path_buf())
+    });
+
+    dialogs.begin_pending(PendingDialog::ExportFountain(task));
+    info!("[dialog] Export fountain dialog task spawned");
+    state.status_message = "Opening export dialog...".to_string();
+}
+
+fn open_export_html_dialog(
+    state: &mut EditorState,
+    dialogs: &mut DialogState,
+    parent_handle: Option<&RawHandleWrapper>,
+) {
+    if dialogs.pending.is_some() {
+        warn!("[dialog] Ignoring export request because a dialog is already pending");
+        state.status_message = "A file dialog is already open.".to_string();
+        return;
+    }
+
+    let stem = state
+        .paths
+        .save_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("script");
+    let mut dialog = AsyncFileDialog::new()
+        .set_title("Export HTML")
+        .add_filter("HTML files", &["html"])
+        .set_file_name(format!("{stem}.html"));
+    if let Some(directory) = preferred_dialog_directory(state) {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog = attach_dialog_parent(dialog, parent_handle);
+
+    let request = dialog.save_file();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        request
+            .await
+            .map(|file_handle| file_handle.path().to_path_buf())
+    });
+
+    dialogs.begin_pending(PendingDialog::ExportHtml(task));
+    info!("[dialog] Export html dialog task spawned");
+    state.status_message = "Opening export dialog...".to_string();
+}
+
+fn open_export_text_dialog(
+    state: &mut EditorState,
+    dialogs: &mut DialogState,
+    parent_handle: Option<&RawHandleWrapper>,
+) {
+    if dialogs.pending.is_some() {
+        warn!("[dialog] Ignoring export request because a dialog is already pending");
+        state.status_message = "A file dialog is already open.".to_string();
+        return;
+    }
+
+    let stem = state
+        .paths
+        .save_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("script");
+    let mut dialog = AsyncFileDialog::new()
+        .set_title("Export Plain Text")
+        .add_filter("Text files", &["txt"])
+        .set_file_name(format!("{stem}.txt"));
+    if let Some(directory) = preferred_dialog_directory(state) {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog = attach_dialog_parent(dialog, parent_handle);
+
+    let request = dialog.save_file();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        request
+            .await
+            .map(|file_handle| file_handle.path().to_path_buf())
+    });
+
+    dialogs.begin_pending(PendingDialog::ExportText(task));
+    info!("[dialog] Export text dialog task spawned");
+    state.status_message = "Opening export dialog...".to_string();
+}
+
 fn attach_dialog_parent(
     dialog: AsyncFileDialog,
     parent_handle: Option<&RawHandleWrapper>,
@@ -445,6 +681,11 @@ fn resolve_dialog_results(mut state: ResMut<EditorState>, mut dialogs: ResMut<Di
     enum DialogResult {
         Workspace(Option<PathBuf>),
         Save(Option<PathBuf>),
+        SaveBundle(Option<PathBuf>),
+        ExportNotes(Option<PathBuf>),
+        ExportFountain(Option<PathBuf>),
+        ExportHtml(Option<PathBuf>),
+        ExportText(Option<PathBuf>),
     }
 
     let finished = match pending {
@@ -454,6 +695,21 @@ fn resolve_dialog_results(mut state: ResMut<EditorState>, mut dialogs: ResMut<Di
         PendingDialog::Save(task) => {
             future::block_on(future::poll_once(task)).map(DialogResult::Save)
         }
+        PendingDialog::SaveBundle(task) => {
+            future::block_on(future::poll_once(task)).map(DialogResult::SaveBundle)
+        }
+        PendingDialog::ExportNotes(task) => {
+            future::block_on(future::poll_once(task)).map(DialogResult::ExportNotes)
+        }
+        PendingDialog::ExportFountain(task) => {
+            future::block_on(future::poll_once(task)).map(DialogResult::ExportFountain)
+        }
+        PendingDialog::ExportHtml(task) => {
+            future::block_on(future::poll_once(task)).map(DialogResult::ExportHtml)
+        }
+        PendingDialog::ExportText(task) => {
+            future::block_on(future::poll_once(task)).map(DialogResult::ExportText)
+        }
     };
 
     dialogs.poll_count = dialogs.poll_count.saturating_add(1);
@@ -499,12 +755,135 @@ fn resolve_dialog_results(mut state: ResMut<EditorState>, mut dialogs: ResMut<Di
         DialogResult::Save(Some(path)) => {
             info!("[dialog] Saving to selected path: {}", path.display());
             state.save_to_path(path);
+            // A load that was held back for unsaved edits goes ahead once they are
+            // on disk.
+            if !state.has_unsaved_changes()
+                && let Some(load_path) = state.pending_load_path.take()
+            {
+                state.request_load(load_path);
+            }
         }
         DialogResult::Save(None) => {
             info!("[dialog] Save dialog canceled by user");
             state.status_message = "Save canceled.".to_string();
         }
+        DialogResult::SaveBundle(Some(path)) => {
+            info!("[dialog] Saving bundle next to: {}", path.display());
+            state.save_bundle_to(path);
+        }
+        DialogResult::SaveBundle(None) => {
+            info!("[dialog] Save bundle dialog canceled by user");
+            state.status_message = "Save canceled.".to_string();
+        }
+        DialogResult::ExportNotes(Some(path)) => {
+            info!("[dialog] Exporting revision notes to: {}", path.display());
+            state.export_revision_notes_to(path);
+        }
+        DialogResult::ExportNotes(None) => {
+            info!("[dialog] Export notes dialog canceled by user");
+            state.status_message = "Export canceled.".to_string();
+        }
+        DialogResult::ExportFountain(Some(path)) => {
+            info!("[dialog] Exporting processed fountain to: {}", path.display());
+            state.export_processed_fountain_to(path);
+        }
+        DialogResult::ExportFountain(None) => {
+            info!("[dialog] Export fountain dialog canceled by user");
+            state.status_message = "Export canceled.".to_string();
+        }
+        DialogResult::ExportHtml(Some(path)) => {
+            info!("[dialog] Exporting html to: {}", path.display());
+            state.export_html_to(path);
+        }
+        DialogResult::ExportHtml(None) => {
+            info!("[dialog] Export html dialog canceled by user");
+            state.status_message = "Export canceled.".to_string();
+        }
+        DialogResult::ExportText(Some(path)) => {
+            info!("[dialog] Exporting plain text to: {}", path.display());
+            state.export_plain_text_to(path);
+        }
+        DialogResult::ExportText(None) => {
+            info!("[dialog] Export text dialog canceled by user");
+            state.status_message = "Export canceled.".to_string();
+        }
+    }
+}
+
+// Only writes back to a file that already exists on disk, so an untitled session
+// never triggers a surprise save dialog, and never while a native dialog has focus.
+fn autosave_on_focus_loss(
+    mut focus_events: MessageReader<WindowFocused>,
+    dialogs: Res<DialogState>,
+    mut state: ResMut<EditorState>,
+) {
+    let lost_focus = focus_events.read().last().is_some_and(|event| !event.focused);
+    if !lost_focus || !state.autosave_on_focus_loss || dialogs.pending.is_some() {
+        return;
+    }
+
+    let path = state.paths.save_path.clone();
+    if !state.has_unsaved_changes() || !path.is_file() {
+        return;
     }
+
+    info!("[autosave] Window lost focus; saving {}", path.display());
+    state.save_to_path(path);
+}
+
+// Same rules as the focus-loss save: existing files only, never behind a dialog.
+fn autosave_on_interval(
+    time: Res<Time>,
+    dialogs: Res<DialogState>,
+    mut state: ResMut<EditorState>,
+) {
+    let state = &mut *state;
+    if let Some((timer, previous)) = state.autosave_status.as_mut()
+        && timer.tick(time.delta()).just_finished()
+    {
+        if state.status_message.starts_with("Auto-saved ") {
+            state.status_message = std::mem::take(previous);
+        }
+        state.autosave_status = None;
+    }
+
+    if state.autosave_interval_secs == 0
+        || !state.autosave_timer.tick(time.delta()).just_finished()
+        || dialogs.pending.is_some()
+    {
+        return;
+    }
+
+    let path = state.paths.save_path.clone();
+    if !state.is_modified || !path.is_file() {
+        return;
+    }
+
+    info!("[autosave] Interval elapsed; saving {}", path.display());
+    let previous = state.status_message.clone();
+    state.save_to_path(path.clone());
+    if !state.is_modified {
+        state.status_message = format!("Auto-saved {}", status_path_label(&path));
+        state.autosave_status = Some((
+            Timer::from_seconds(AUTOSAVE_STATUS_SECS, TimerMode::Once),
+            previous,
+        ));
+    }
+}
+
+// Skipped while a native dialog is open so a save or load in flight is not
+// mistaken for an outside change.
+fn detect_external_file_change(
+    time: Res<Time>,
+    dialogs: Res<DialogState>,
+    mut state: ResMut<EditorState>,
+) {
+    if !state.external_change_timer.tick(time.delta()).just_finished() || dialogs.pending.is_some()
+    {
+        return;
+    }
+
+    state.check_external_change();
 }
 
 fn preferred_dialog_directory(state: &EditorState) -> Option<PathBuf> {
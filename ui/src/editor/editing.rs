@@ -4,7 +4,9 @@ fn handle_text_input(
     body_query: Query<(&PanelBody, &ComputedNode)>,
     mut state: ResMut<EditorState>,
 ) {
-    if shortcut_modifier_pressed(&keys) {
+    if state.reclassify_preview.is_some() {
+        keyboard_inputs.clear();
+        handle_reclassify_preview_keys(&keys, &mut state);
         return;
     }
 
@@ -18,20 +20,49 @@ fn handle_text_input(
         .iter()
         .find(|(panel, _)| panel.kind == PanelKind::Processed)
         .map(|(_, computed)| computed.size() * computed.inverse_scale_factor());
+
+    if state.find_bar.is_some() {
+        if handle_find_bar_input(&mut keyboard_inputs, &keys, &mut state) {
+            apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+        }
+        return;
+    }
+
+    // Cmd/Ctrl only reaches text input for word-wise Backspace/Delete.
+    let shortcut_held = shortcut_modifier_pressed(&keys);
+    if keys.just_pressed(KeyCode::Insert) && !shortcut_held && !shift_modifier_pressed(&keys) {
+        state.overwrite = !state.overwrite;
+        state.status_message = if state.overwrite {
+            "Overtype on.".to_string()
+        } else {
+            "Overtype off.".to_string()
+        };
+    }
+    if shortcut_held && !keys.any_pressed([KeyCode::Backspace, KeyCode::Delete]) {
+        return;
+    }
+    let word_wise = shortcut_held || alt_modifier_pressed(&keys);
     let mut edited = false;
     let mut dirty_from_line = None::<usize>;
+    let mut inserted_lines = 0;
     let mut undo_snapshot = None::<EditorHistorySnapshot>;
+    let mut undo_group = None::<UndoGroupKind>;
+    let mut groupable = true;
+    let mut cue_line = None::<usize>;
 
     for input in keyboard_inputs.read() {
         if !input.state.is_pressed() {
             continue;
         }
 
-        let edit_intent = matches!(input.logical_key, Key::Enter | Key::Backspace | Key::Delete)
-            || input
-                .text
-                .as_ref()
-                .is_some_and(|text| !text.is_empty() && text.chars().all(is_printable_char));
+        let edit_intent = matches!(input.logical_key, Key::Backspace | Key::Delete)
+            || (!shortcut_held && input.logical_key == Key::Tab)
+            || (!shortcut_held
+                && (input.logical_key == Key::Enter
+                    || input
+                        .text
+                        .as_ref()
+                        .is_some_and(|text| is_insertable_text(text))));
         if !edit_intent {
             continue;
         }
@@ -42,20 +73,32 @@ fn handle_text_input(
 
         let mut changed = false;
         let mut selection_deleted = false;
+        let mut input_group = None::<UndoGroupKind>;
+
+        if input.logical_key == Key::Tab {
+            if let Some(line) = state.indent_with_tab(shift_modifier_pressed(&keys)) {
+                dirty_from_line = Some(dirty_from_line.map_or(line, |current| current.min(line)));
+                edited = true;
+                groupable = false;
+            }
+            continue;
+        }
 
         if let Some(next) = state.delete_selection() {
             dirty_from_line = Some(dirty_from_line.map_or(next.line, |line| line.min(next.line)));
             changed = true;
             selection_deleted = true;
+            groupable = false;
         }
 
         match &input.logical_key {
             Key::Enter => {
-                let cursor_pos = state.cursor.position;
-                let next = state.document.insert_newline(cursor_pos);
+                let (next, first_line) =
+                    state.edit_at_carets(|document, position| document.insert_newline(position));
                 state.set_cursor(next, true);
+                cue_line = next.line.checked_sub(1);
                 dirty_from_line =
-                    Some(dirty_from_line.map_or(cursor_pos.line, |line| line.min(cursor_pos.line)));
+                    Some(dirty_from_line.map_or(first_line, |line| line.min(first_line)));
                 changed = true;
             }
             Key::Backspace => {
@@ -66,10 +109,21 @@ fn handle_text_input(
                     continue;
                 }
                 let cursor_pos = state.cursor.position;
-                if cursor_pos.line > 0 || cursor_pos.column > 0 {
-                    let next = state.document.backspace(cursor_pos);
+                let multi_caret = !state.secondary_cursors.is_empty();
+                if cursor_pos.line > 0 || cursor_pos.column > 0 || multi_caret {
+                    // Joining lines and word deletes are their own undo steps.
+                    if cursor_pos.column > 0 && !word_wise {
+                        input_group = Some(UndoGroupKind::Deleting);
+                    }
+                    let (next, first_line) = state.edit_at_carets(|document, position| {
+                        if word_wise {
+                            document.delete_word_left(position)
+                        } else {
+                            document.backspace(position)
+                        }
+                    });
                     state.set_cursor(next, true);
-                    let dirty_candidate = cursor_pos.line.saturating_sub(1).min(next.line);
+                    let dirty_candidate = first_line.saturating_sub(1);
                     dirty_from_line = Some(
                         dirty_from_line.map_or(dirty_candidate, |line| line.min(dirty_candidate)),
                     );
@@ -86,25 +140,53 @@ fn handle_text_input(
                 let cursor_pos = state.cursor.position;
                 let line_len = state.document.line_len_chars(cursor_pos.line);
                 let has_next_line = cursor_pos.line + 1 < state.document.line_count();
-                if cursor_pos.column < line_len || has_next_line {
-                    let next = state.document.delete(cursor_pos);
+                let multi_caret = !state.secondary_cursors.is_empty();
+                if cursor_pos.column < line_len || has_next_line || multi_caret {
+                    let (next, first_line) = state.edit_at_carets(|document, position| {
+                        if word_wise {
+                            document.delete_word_right(position)
+                        } else {
+                            document.delete(position)
+                        }
+                    });
                     state.set_cursor(next, false);
-                    dirty_from_line = Some(
-                        dirty_from_line.map_or(cursor_pos.line, |line| line.min(cursor_pos.line)),
-                    );
+                    dirty_from_line =
+                        Some(dirty_from_line.map_or(first_line, |line| line.min(first_line)));
                     changed = true;
                 }
             }
             _ => {
                 if let Some(inserted_text) = &input.text {
-                    if !inserted_text.is_empty() && inserted_text.chars().all(is_printable_char) {
-                        let cursor_pos = state.cursor.position;
-                        let next = state.document.insert_text(cursor_pos, inserted_text);
+                    if is_insertable_text(inserted_text) {
+                        if !selection_deleted
+                            && inserted_text.chars().count() == 1
+                            && inserted_text != "\n"
+                        {
+                            input_group = Some(UndoGroupKind::Typing);
+                        }
+                        let mut typed = inserted_text.chars();
+                        let (next, first_line) = match (typed.next(), typed.next()) {
+                            (Some(ch), None) if ch != '\n' => {
+                                let rules = TypedCharRules {
+                                    overwrite: state.overwrite,
+                                    capitalize_lines: state.sentence_capitalization_lines(ch),
+                                    pair_lines: state.paren_pairing_lines(ch),
+                                };
+                                state.edit_at_carets(|document, position| {
+                                    type_char_at(document, position, ch, &rules)
+                                })
+                            }
+                            // Pasted blocks can be huge; insert whole lines instead of per char.
+                            _ => {
+                                inserted_lines += inserted_text.matches('\n').count();
+                                state.edit_at_carets(|document, position| {
+                                    document.insert_text_bulk(position, inserted_text)
+                                })
+                            }
+                        };
                         state.set_cursor(next, true);
-                        dirty_from_line = Some(
-                            dirty_from_line
-                                .map_or(cursor_pos.line, |line| line.min(cursor_pos.line)),
-                        );
+                        dirty_from_line =
+                            Some(dirty_from_line.map_or(first_line, |line| line.min(first_line)));
                         changed = true;
                     }
                 }
@@ -113,18 +195,169 @@ fn handle_text_input(
 
         if changed {
             edited = true;
+            groupable &= input_group.is_some()
+                && undo_group.is_none_or(|kind| input_group == Some(kind));
+            undo_group = input_group;
         }
     }
 
     if edited {
         if let Some(snapshot) = undo_snapshot {
-            state.push_undo_snapshot(snapshot);
+            state.push_grouped_undo_snapshot(snapshot, undo_group.filter(|_| groupable));
+        }
+        state.reparse_after_insert(dirty_from_line.unwrap_or(0), inserted_lines);
+        // Runs after the undo snapshot was pushed, so one undo reverts the Enter too.
+        if let Some(line) = cue_line {
+            state.auto_uppercase_character_cue(line);
         }
-        state.reparse_with_dirty_hint(dirty_from_line.unwrap_or(0));
         apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
     }
 }
 
+// Caret lines are collected before the edit because the closure passed to
+// `edit_at_carets` cannot reach the parsed lines.
+struct TypedCharRules {
+    overwrite: bool,
+    capitalize_lines: Vec<usize>,
+    pair_lines: Vec<usize>,
+}
+
+fn type_char_at(
+    document: &mut Document,
+    position: Position,
+    ch: char,
+    rules: &TypedCharRules,
+) -> Position {
+    if rules.pair_lines.contains(&position.line) && !rules.overwrite {
+        let next_char = document
+            .line(position.line)
+            .and_then(|text| text.chars().nth(position.column));
+        match ch {
+            ')' if next_char == Some(')') => return document.move_right(position),
+            '(' => {
+                let after = document.insert_text(position, "()");
+                return Position {
+                    column: after.column - 1,
+                    ..after
+                };
+            }
+            _ => {}
+        }
+    }
+
+    // A capital the user typed is left alone.
+    let ch = if rules.capitalize_lines.contains(&position.line)
+        && document.ends_sentence_before(position)
+    {
+        single_char_uppercase(ch)
+    } else {
+        ch
+    };
+    if rules.overwrite {
+        document.overwrite_char(position, ch)
+    } else {
+        document.insert_char(position, ch)
+    }
+}
+
+// Letters whose uppercase form is several chars, like `ß`, stay as typed.
+fn single_char_uppercase(ch: char) -> char {
+    let mut upper = ch.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) => upper,
+        _ => ch,
+    }
+}
+
+fn handle_reclassify_preview_keys(keys: &ButtonInput<KeyCode>, state: &mut EditorState) {
+    if keys.just_pressed(KeyCode::Escape) {
+        state.reclassify_preview = None;
+        state.status_message = "Forced marker preview cancelled.".to_string();
+    } else if keys.just_pressed(KeyCode::Enter) || keys.just_pressed(KeyCode::NumpadEnter) {
+        let snapshot = state.history_snapshot();
+        let applied = state.apply_reclassify_preview();
+        if applied > 0 {
+            state.push_undo_snapshot(snapshot);
+        }
+        state.status_message = format!("Applied {applied} forced markers.");
+    }
+}
+
+// Typing edits the query (or the replacement, after Tab) and Enter / Shift+Enter
+// step through the matches. Alt+L toggles the in-selection scope and Cmd/Ctrl+Enter
+// replaces every match. Returns whether the selection moved.
+fn handle_find_bar_input(
+    keyboard_inputs: &mut MessageReader<KeyboardInput>,
+    keys: &ButtonInput<KeyCode>,
+    state: &mut EditorState,
+) -> bool {
+    let shortcut_held = shortcut_modifier_pressed(keys);
+    let alt_held = alt_modifier_pressed(keys);
+    let mut query_changed = false;
+    let mut moved = false;
+
+    if alt_held && keys.just_pressed(KeyCode::KeyL) {
+        state.toggle_find_in_selection();
+        moved = true;
+    }
+
+    for input in keyboard_inputs.read() {
+        if !input.state.is_pressed() {
+            continue;
+        }
+
+        match &input.logical_key {
+            Key::Escape => {
+                state.find_bar = None;
+                state.status_message = "Find closed.".to_string();
+                return moved;
+            }
+            Key::Enter if shortcut_held => {
+                let replaced = state.replace_all_find_matches();
+                let noun = if replaced == 1 { "match" } else { "matches" };
+                state.status_message = format!("Replaced {replaced} {noun}.");
+                moved |= replaced > 0;
+            }
+            Key::Enter => moved |= state.step_find_match(!shift_modifier_pressed(keys)),
+            Key::Tab => {
+                if let Some(find_bar) = state.find_bar.as_mut() {
+                    find_bar.editing_replacement = !find_bar.editing_replacement;
+                }
+            }
+            Key::Backspace => {
+                if let Some(find_bar) = state.find_bar.as_mut() {
+                    if find_bar.editing_replacement {
+                        find_bar.replacement.pop();
+                    } else {
+                        query_changed |= find_bar.query.pop().is_some();
+                    }
+                }
+            }
+            _ => {
+                let Some(text) = input.text.as_ref().filter(|text| {
+                    !shortcut_held && !alt_held && is_insertable_text(text) && !text.contains('\n')
+                }) else {
+                    continue;
+                };
+                if let Some(find_bar) = state.find_bar.as_mut() {
+                    if find_bar.editing_replacement {
+                        find_bar.replacement.push_str(text);
+                    } else {
+                        find_bar.query.push_str(text);
+                        query_changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    if query_changed {
+        state.refresh_find_matches();
+        moved = true;
+    }
+    moved
+}
+
 fn handle_navigation_input(
     keys: Res<ButtonInput<KeyCode>>,
     time: Res<Time>,
@@ -132,6 +365,10 @@ fn handle_navigation_input(
     mut navigation_repeat: ResMut<NavigationRepeatState>,
     mut state: ResMut<EditorState>,
 ) {
+    if state.reclassify_preview.is_some() || state.find_bar.is_some() {
+        return;
+    }
+
     let visible_lines = viewport_lines(
         &body_query,
         state.display_mode,
@@ -150,15 +387,33 @@ fn handle_navigation_input(
     let extend_selection = shift_modifier_pressed(&keys);
     let mut moved = false;
 
+    if keys.just_pressed(KeyCode::Escape) && !state.secondary_cursors.is_empty() {
+        state.secondary_cursors.clear();
+        state.status_message = "Removed extra carets.".to_string();
+        return;
+    }
+
     if shortcut_modifier_pressed(&keys) {
+        // Cmd/Ctrl+Alt+Up/Down adds a caret above or below the outermost one.
+        if alt_modifier_pressed(&keys) {
+            let up = keys.just_pressed(KeyCode::ArrowUp);
+            if up || keys.just_pressed(KeyCode::ArrowDown) {
+                if state.add_cursor_vertically(up) {
+                    state.status_message =
+                        format!("{} carets.", state.secondary_cursors.len() + 1);
+                }
+                return;
+            }
+        }
+
         if shortcut_just_pressed(&keys, state.keybinds.binding(ShortcutAction::PlainView)) {
-            state.set_display_mode(DisplayMode::Plain);
+            state.toggle_display_mode(DisplayMode::Plain);
             state.status_message = format!("View mode: {}", state.display_mode.label());
             return;
         }
 
         if shortcut_just_pressed(&keys, state.keybinds.binding(ShortcutAction::ProcessedView)) {
-            state.set_display_mode(DisplayMode::Processed);
+            state.toggle_display_mode(DisplayMode::Processed);
             state.status_message = format!("View mode: {}", state.display_mode.label());
             return;
         }
@@ -169,7 +424,7 @@ fn handle_navigation_input(
                 .keybinds
                 .binding(ShortcutAction::ProcessedRawCurrentLineView),
         ) {
-            state.set_display_mode(DisplayMode::ProcessedRawCurrentLine);
+            state.toggle_display_mode(DisplayMode::ProcessedRawCurrentLine);
             state.status_message = format!("View mode: {}", state.display_mode.label());
             return;
         }
@@ -198,6 +453,214 @@ fn handle_navigation_input(
             return;
         }
 
+        if shortcut_just_pressed(
+            &keys,
+            state.keybinds.binding(ShortcutAction::NextDifferentElement),
+        ) {
+            if let Some(kind) = state.jump_to_different_element(true) {
+                state.status_message = format!("Jumped to {kind:?}.");
+                apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            } else {
+                state.status_message = "No different element below.".to_string();
+            }
+            return;
+        }
+
+        if shortcut_just_pressed(
+            &keys,
+            state.keybinds.binding(ShortcutAction::PreviousDifferentElement),
+        ) {
+            if let Some(kind) = state.jump_to_different_element(false) {
+                state.status_message = format!("Jumped to {kind:?}.");
+                apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            } else {
+                state.status_message = "No different element above.".to_string();
+            }
+            return;
+        }
+
+        for (action, forward) in [
+            (ShortcutAction::NextScene, true),
+            (ShortcutAction::PreviousScene, false),
+        ] {
+            if !shortcut_just_pressed(&keys, state.keybinds.binding(action)) {
+                continue;
+            }
+            if let Some(line) = state.jump_to_scene_heading(forward) {
+                state.status_message = format!("Jumped to scene heading on line {}.", line + 1);
+                apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            } else {
+                let direction = if forward { "below" } else { "above" };
+                state.status_message = format!("No scene heading {direction}.");
+            }
+            return;
+        }
+
+        let select_all = ShortcutBinding {
+            key: SELECT_ALL_KEY,
+            shift: false,
+        };
+        if shortcut_just_pressed(&keys, select_all) {
+            state.status_message = if state.select_all() {
+                "Selected all.".to_string()
+            } else {
+                "Nothing to select.".to_string()
+            };
+            apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            return;
+        }
+
+        if shortcut_just_pressed(&keys, state.keybinds.binding(ShortcutAction::SelectSection)) {
+            if state.select_structural_range() {
+                state.status_message = "Selected section.".to_string();
+                apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            } else {
+                state.status_message = "No scene or section heading above cursor.".to_string();
+            }
+            return;
+        }
+
+        if shortcut_just_pressed(
+            &keys,
+            state.keybinds.binding(ShortcutAction::ToggleCharacterDialogue),
+        ) {
+            let snapshot = state.history_snapshot();
+            if let Some(kind) = state.toggle_character_dialogue() {
+                state.push_undo_snapshot(snapshot);
+                state.status_message = format!("Line is now {kind:?}.");
+                apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            } else {
+                state.status_message = "Nothing to toggle on this line.".to_string();
+            }
+            return;
+        }
+
+        if shortcut_just_pressed(&keys, state.keybinds.binding(ShortcutAction::DuplicateScene)) {
+            let snapshot = state.history_snapshot();
+            if state.duplicate_current_scene() {
+                state.push_undo_snapshot(snapshot);
+                state.status_message = "Duplicated scene.".to_string();
+                apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            } else {
+                state.status_message = "No scene heading above cursor.".to_string();
+            }
+            return;
+        }
+
+        if shortcut_just_pressed(
+            &keys,
+            state.keybinds.binding(ShortcutAction::TogglePlainTextMode),
+        ) {
+            state.plain_text_mode = !state.plain_text_mode;
+            state.processed_cache = None;
+            state.reparse();
+            let mode = if state.plain_text_mode { "ON" } else { "OFF" };
+            state.status_message = format!("Plain text mode: {mode}");
+            return;
+        }
+
+        if shortcut_just_pressed(&keys, state.keybinds.binding(ShortcutAction::ToggleOutline)) {
+            state.outline_open = !state.outline_open;
+            let visibility = if state.outline_open { "VISIBLE" } else { "HIDDEN" };
+            state.status_message = format!("Outline: {visibility}");
+            return;
+        }
+
+        if shortcut_just_pressed(&keys, state.keybinds.binding(ShortcutAction::ToggleStats)) {
+            state.stats_panel_open = !state.stats_panel_open;
+            let visibility = if state.stats_panel_open { "VISIBLE" } else { "HIDDEN" };
+            state.status_message = format!("Statistics: {visibility}");
+            return;
+        }
+
+        if shortcut_just_pressed(&keys, state.keybinds.binding(ShortcutAction::OpenFind)) {
+            // A single-line selection seeds the query.
+            let query = state
+                .selected_text()
+                .filter(|text| !text.contains('\n'))
+                .unwrap_or_default();
+            let selection = state.selection_bounds().map(|(start, end)| start..end);
+            state.find_bar = Some(FindBar {
+                query,
+                selection,
+                ..default()
+            });
+            state.refresh_find_matches();
+            state.status_message = "Find: type to search, Esc closes.".to_string();
+            apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            return;
+        }
+
+        if shortcut_just_pressed(&keys, state.keybinds.binding(ShortcutAction::DuplicateLine)) {
+            let snapshot = state.history_snapshot();
+            state.duplicate_current_line();
+            state.push_undo_snapshot(snapshot);
+            state.status_message = "Duplicated line.".to_string();
+            apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            return;
+        }
+
+        if shortcut_just_pressed(&keys, state.keybinds.binding(ShortcutAction::DeleteLine)) {
+            let snapshot = state.history_snapshot();
+            state.delete_current_line();
+            state.push_undo_snapshot(snapshot);
+            state.status_message = "Deleted line.".to_string();
+            apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            return;
+        }
+
+        if shortcut_just_pressed(&keys, state.keybinds.binding(ShortcutAction::InsertPageBreak)) {
+            let snapshot = state.history_snapshot();
+            state.insert_page_break();
+            state.push_undo_snapshot(snapshot);
+            state.status_message = "Inserted page break.".to_string();
+            apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            return;
+        }
+
+        if shortcut_just_pressed(
+            &keys,
+            state.keybinds.binding(ShortcutAction::PreviewForcedMarkers),
+        ) {
+            state.status_message = match state.open_reclassify_preview() {
+                0 => "No ambiguous lines to force.".to_string(),
+                count => format!("{count} forced markers proposed. Enter applies, Esc cancels."),
+            };
+            return;
+        }
+
+        let next_sentence = motion_shortcut_just_pressed(
+            &keys,
+            state.keybinds.binding(ShortcutAction::NextSentence),
+        );
+        let previous_sentence = motion_shortcut_just_pressed(
+            &keys,
+            state.keybinds.binding(ShortcutAction::PreviousSentence),
+        );
+        if next_sentence || previous_sentence {
+            let current = state.cursor.position;
+            let target = if next_sentence {
+                state.document.move_sentence_forward(current)
+            } else {
+                state.document.move_sentence_backward(current)
+            };
+            state.set_cursor_with_selection(target, true, extend_selection);
+            apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            return;
+        }
+
+        if shortcut_just_pressed(&keys, state.keybinds.binding(ShortcutAction::JoinLines)) {
+            let snapshot = state.history_snapshot();
+            if state.join_selected_lines() {
+                state.push_undo_snapshot(snapshot);
+                state.status_message = "Joined lines.".to_string();
+                apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            } else {
+                state.status_message = "Nothing to join.".to_string();
+            }
+            return;
+        }
+
         if shortcut_just_pressed(&keys, state.keybinds.binding(ShortcutAction::ZoomIn)) {
             let next_zoom = state.zoom + ZOOM_STEP;
             set_zoom_preserving_processed_anchor(&mut state, processed_panel_size, next_zoom);
@@ -229,6 +692,22 @@ fn handle_navigation_input(
         }
     }
 
+    // Alt+Up/Down reorders lines; the neighbouring Character/Dialogue context changes,
+    // so the reparse in move_current_line reclassifies both lines.
+    if alt_modifier_pressed(&keys) {
+        let up = keys.just_pressed(KeyCode::ArrowUp);
+        if up || keys.just_pressed(KeyCode::ArrowDown) {
+            let snapshot = state.history_snapshot();
+            if state.move_current_line(up) {
+                state.push_undo_snapshot(snapshot);
+                state.status_message =
+                    format!("Moved line {}.", if up { "up" } else { "down" });
+                apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+            }
+            return;
+        }
+    }
+
     let previous_active_arrow = navigation_repeat.active_arrow;
     if let Some(arrow) = just_pressed_navigation_arrow(&keys) {
         moved |= move_cursor_by_arrow_key(&mut state, arrow, extend_selection);
@@ -257,9 +736,16 @@ fn handle_navigation_input(
         }
     }
 
+    // Smart Home: first to the line's indent, then to column 0 when already there.
     if keys.just_pressed(KeyCode::Home) {
         let line = state.cursor.position.line;
-        state.set_cursor_with_selection(Position { line, column: 0 }, true, extend_selection);
+        let indent = state.document.first_non_whitespace_column(line);
+        let column = if state.cursor.position.column == indent {
+            0
+        } else {
+            indent
+        };
+        state.set_cursor_with_selection(Position { line, column }, true, extend_selection);
         moved = true;
     }
 
@@ -315,10 +801,175 @@ fn handle_navigation_input(
     }
 
     if moved {
+        state.secondary_cursors.clear();
+        // Keyboard moves always land the Plain pane on a whole line.
+        state.plain_scroll_fraction = 0.0;
         apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
     }
 }
 
+// Zoom changes from the keyboard, the toolbar and Ctrl+wheel all land here, so the
+// level is written once per change wherever it came from.
+fn persist_zoom_setting(state: Res<EditorState>, mut persisted_zoom: Local<Option<f32>>) {
+    let persisted = *persisted_zoom.get_or_insert(state.zoom);
+    if persisted == state.zoom {
+        return;
+    }
+    *persisted_zoom = Some(state.zoom);
+    if let Err(error) = save_persistent_settings(&persistent_settings_from_state(&state)) {
+        warn!("[settings] Failed saving zoom: {error}");
+    }
+}
+
+const CLIPBOARD_COPY_KEY: KeyCode = KeyCode::KeyC;
+const CLIPBOARD_CUT_KEY: KeyCode = KeyCode::KeyX;
+const SELECT_ALL_KEY: KeyCode = KeyCode::KeyA;
+const CLIPBOARD_PASTE_KEY: KeyCode = KeyCode::KeyV;
+
+fn handle_clipboard_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    body_query: Query<(&PanelBody, &ComputedNode)>,
+    mut state: ResMut<EditorState>,
+) {
+    if state.reclassify_preview.is_some()
+        || state.find_bar.is_some()
+        || !shortcut_modifier_pressed(&keys)
+        || shift_modifier_pressed(&keys)
+    {
+        return;
+    }
+
+    let copy = keys.just_pressed(CLIPBOARD_COPY_KEY);
+    let cut = keys.just_pressed(CLIPBOARD_CUT_KEY);
+    let paste = keys.just_pressed(CLIPBOARD_PASTE_KEY);
+    if !(copy || cut || paste) {
+        return;
+    }
+
+    let mut clipboard = match arboard::Clipboard::new() {
+        Ok(clipboard) => clipboard,
+        Err(error) => {
+            warn!("Clipboard unavailable: {error}");
+            state.status_message = format!("Clipboard unavailable: {error}");
+            return;
+        }
+    };
+
+    if copy || cut {
+        let Some(text) = state.selected_text() else {
+            state.status_message = "Nothing selected.".to_string();
+            return;
+        };
+        if let Err(error) = clipboard.set_text(text) {
+            state.status_message = format!("Copy failed: {error}");
+            return;
+        }
+        if copy {
+            state.status_message = "Copied selection.".to_string();
+            return;
+        }
+    }
+
+    let snapshot = state.history_snapshot();
+    if cut {
+        let Some(next) = state.delete_selection() else {
+            return;
+        };
+        state.reparse_with_dirty_hint(next.line);
+        state.status_message = "Cut selection.".to_string();
+    } else {
+        let text = match clipboard.get_text() {
+            Ok(text) => text,
+            Err(error) => {
+                state.status_message = format!("Paste failed: {error}");
+                return;
+            }
+        };
+        if text.is_empty() {
+            state.status_message = "Clipboard is empty.".to_string();
+            return;
+        }
+        state.paste_text(&text);
+        state.status_message = "Pasted.".to_string();
+    }
+    state.push_undo_snapshot(snapshot);
+
+    let visible_lines = viewport_lines(
+        &body_query,
+        state.display_mode,
+        state.measured_line_step,
+        scaled_text_padding_y(&state),
+    );
+    let processed_panel_size = body_query
+        .iter()
+        .find(|(panel, _)| panel.kind == PanelKind::Processed)
+        .map(|(_, computed)| computed.size() * computed.inverse_scale_factor());
+    apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+}
+
+// Classifies the next slice of lines left by a large paste, keeping each frame's
+// share of the reparse bounded.
+fn advance_chunked_reparse(mut state: ResMut<EditorState>) {
+    if state.chunked_reparse.is_some() {
+        state.advance_chunked_reparse(CHUNKED_REPARSE_LINES_PER_FRAME);
+    }
+}
+
+// Hidden developer chord for chasing layout/caret desync: throws away every derived
+// cache and reclamps, then asks the next layout pass to measure the line step the
+// text layout really produced and log it next to the nominal one.
+const DEBUG_RECOMPUTE_KEY: KeyCode = KeyCode::F12;
+
+fn handle_debug_recompute(
+    keys: Res<ButtonInput<KeyCode>>,
+    body_query: Query<(&PanelBody, &ComputedNode)>,
+    mut state: ResMut<EditorState>,
+) {
+    if !(shortcut_modifier_pressed(&keys)
+        && shift_modifier_pressed(&keys)
+        && keys.just_pressed(DEBUG_RECOMPUTE_KEY))
+    {
+        return;
+    }
+
+    state.processed_cache = None;
+    state.reparse();
+    state.line_step_remeasure_pending = true;
+    let visible_lines = viewport_lines(
+        &body_query,
+        state.display_mode,
+        state.measured_line_step,
+        scaled_text_padding_y(&state),
+    );
+    let plain_panel_size = body_query
+        .iter()
+        .find(|(panel, _)| panel.kind == PanelKind::Plain)
+        .map(|(_, computed)| computed.size() * computed.inverse_scale_factor());
+    let processed_panel_size = body_query
+        .iter()
+        .find(|(panel, _)| panel.kind == PanelKind::Processed)
+        .map(|(_, computed)| computed.size() * computed.inverse_scale_factor());
+
+    let position = state.cursor.position;
+    state.set_cursor(position, false);
+    state.clamp_scroll(visible_lines);
+    state.clamp_processed_top_line();
+    state.clamp_horizontal_scrolls(plain_panel_size, processed_panel_size);
+    state.ensure_cursor_visible(visible_lines);
+
+    info!(
+        "[debug] Recomputed layout: viewport_lines={} lines={} cursor={}:{} top_line={} \
+         processed_top_line={}",
+        visible_lines,
+        state.document.line_count(),
+        state.cursor.position.line + 1,
+        state.cursor.position.column + 1,
+        state.top_line,
+        state.processed_top_line,
+    );
+    state.status_message = format!("Recomputed: {visible_lines} viewport lines");
+}
+
 fn just_pressed_navigation_arrow(keys: &ButtonInput<KeyCode>) -> Option<KeyCode> {
     [
         KeyCode::ArrowLeft,
@@ -347,11 +998,23 @@ fn move_cursor_by_arrow_key(
     extend_selection: bool,
 ) -> bool {
     let current = state.cursor.position;
-    let next = match arrow {
-        KeyCode::ArrowLeft => state.document.move_left(current),
-        KeyCode::ArrowRight => state.document.move_right(current),
-        KeyCode::ArrowUp => state.document.move_up(current, state.cursor.preferred_column),
-        KeyCode::ArrowDown => state.document.move_down(current, state.cursor.preferred_column),
+    let visual_next = if matches!(arrow, KeyCode::ArrowUp | KeyCode::ArrowDown)
+        && state.unified_processed_edit_active()
+    {
+        state.processed_cache.as_ref().and_then(|cache| {
+            processed_vertical_move(&cache.lines, current, arrow == KeyCode::ArrowDown)
+        })
+    } else {
+        None
+    };
+    let next = match (arrow, visual_next) {
+        (_, Some(position)) => position,
+        (KeyCode::ArrowLeft, _) => state.document.move_left(current),
+        (KeyCode::ArrowRight, _) => state.document.move_right(current),
+        (KeyCode::ArrowUp, _) => state.document.move_up(current, state.cursor.preferred_column),
+        (KeyCode::ArrowDown, _) => {
+            state.document.move_down(current, state.cursor.preferred_column)
+        }
         _ => return false,
     };
 
@@ -362,3 +1025,27 @@ fn move_cursor_by_arrow_key(
     );
     next != current
 }
+
+#[cfg(test)]
+mod editing_tests {
+    use super::*;
+
+    #[test]
+    fn typed_parens_pair_and_step_over_only_on_pair_lines() {
+        let mut document = Document::from_text("MARY\n\nAction");
+        let rules = TypedCharRules {
+            overwrite: false,
+            capitalize_lines: Vec::new(),
+            pair_lines: vec![1],
+        };
+
+        let inside = type_char_at(&mut document, Position { line: 1, column: 0 }, '(', &rules);
+        assert_eq!(inside, Position { line: 1, column: 1 });
+        let after = type_char_at(&mut document, inside, ')', &rules);
+        assert_eq!(after, Position { line: 1, column: 2 });
+        assert_eq!(document.line(1), Some("()"));
+
+        type_char_at(&mut document, Position { line: 2, column: 0 }, '(', &rules);
+        assert_eq!(document.line(2), Some("(Action"));
+    }
+}
@@ -50,8 +50,9 @@ impl EditorState {
         match self.resolve_script_target_path(&target) {
             Ok(path) => {
                 let metadata_warning = EntityDocument::load(&path).err();
-                self.load_from_path(path.clone());
-                if let Some(error) = metadata_warning {
+                if self.request_load(path.clone())
+                    && let Some(error) = metadata_warning
+                {
                     self.status_message = format!(
                         "Loaded {} with metadata warning: {error}",
                         status_path_label(&path)
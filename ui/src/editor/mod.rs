@@ -18,6 +18,7 @@ include!("linking/mod.rs");
 include!("selection.rs");
 // Text panel-specific logic.
 include!("../pannels/text/explorer.rs");
+include!("../pannels/text/outline.rs");
 include!("../pannels/text/plain.rs");
 include!("../pannels/text/processed.rs");
 // Scroll mode input handlers and overlays.
@@ -25,6 +26,7 @@ include!("../pannels/text/scrolling/modes/shared.rs");
 include!("../pannels/text/scrolling/modes/wheel.rs");
 include!("../pannels/text/scrolling/modes/ctrl_left_drag.rs");
 include!("../pannels/text/scrolling/modes/middle_autoscroll.rs");
+include!("../pannels/text/scrolling/modes/scrollbar.rs");
 // Native file dialog and shortcut handling.
 include!("dialogs.rs");
 // Text editing/navigation/mouse interaction systems.
@@ -1,14 +1,22 @@
-fn processed_page_step_lines() -> usize {
-    ((A4_HEIGHT_POINTS + PAGE_GAP) / LINE_HEIGHT)
+fn processed_page_step_lines(page_size: PageSize) -> usize {
+    ((page_size.height_points() + PAGE_GAP) / LINE_HEIGHT)
         .round()
         .max(1.0) as usize
 }
 
+fn max_processed_page_step_lines() -> usize {
+    PageSize::ALL
+        .into_iter()
+        .map(processed_page_step_lines)
+        .max()
+        .unwrap_or(1)
+}
+
 fn processed_page_geometry(panel_size: Vec2, state: &EditorState) -> ProcessedPageGeometry {
     let zoom = state.zoom.max(f32::EPSILON);
-    let paper_width = A4_WIDTH_POINTS * zoom;
+    let paper_width = state.page_size.width_points() * zoom;
     // Keep paper height on the same line grid used by processed pagination.
-    let page_step_lines = processed_page_step_lines();
+    let page_step_lines = processed_page_step_lines(state.page_size);
     let paper_height =
         ((page_step_lines as f32 * (LINE_HEIGHT * zoom)) - (PAGE_GAP * zoom)).max(1.0);
     let paper_left = if panel_size.x > paper_width {
@@ -41,17 +49,15 @@ fn processed_page_geometry(panel_size: Vec2, state: &EditorState) -> ProcessedPa
 
 fn processed_page_layout(panel_size: Vec2, state: &EditorState) -> ProcessedPageLayout {
     let geometry = processed_page_geometry(panel_size, state);
-    let page_step_lines = processed_page_step_lines();
-    let base_paper_height = ((page_step_lines as f32 * LINE_HEIGHT) - PAGE_GAP).max(1.0);
-    let base_text_width =
-        (A4_WIDTH_POINTS - state.page_margin_left - state.page_margin_right).max(1.0);
-    let base_text_height =
-        (base_paper_height - state.page_margin_top - state.page_margin_bottom).max(1.0);
+    let page_step_lines = processed_page_step_lines(state.page_size);
+    // Rows per page come from the same layout core pagination uses.
+    let page_layout = state.page_layout();
+    let base_text_width = page_layout.text_width_points().max(1.0);
     let base_char_width = default_char_width_for_format(state.document_format).max(0.1);
     let wrap_columns = ((base_text_width / base_char_width) + 1e-4)
         .floor()
         .max(1.0) as usize;
-    let lines_per_page = ((base_text_height / LINE_HEIGHT) + 1e-4).floor().max(1.0) as usize;
+    let lines_per_page = page_layout.lines_per_page();
     let spacer_lines = page_step_lines.saturating_sub(lines_per_page);
 
     ProcessedPageLayout {
@@ -188,7 +194,7 @@ fn processed_segment_ranges(state: &EditorState) -> Vec<(usize, usize, bool)> {
     let mut segment_start = 0usize;
 
     for (line_index, parsed_line) in state.parsed.iter().enumerate() {
-        if is_fountain_page_break_marker(&parsed_line.raw) {
+        if parsed_line.kind == LineKind::PageBreak {
             ranges.push((segment_start, line_index, true));
             segment_start = line_index.saturating_add(1);
         }
@@ -202,6 +208,7 @@ struct PreparedProcessedText {
     text: String,
     display_to_raw: Vec<usize>,
     link_targets: Vec<Option<String>>,
+    emphasis: Vec<Emphasis>,
 }
 
 fn prepared_plain_processed_text(text: impl Into<String>) -> PreparedProcessedText {
@@ -211,6 +218,18 @@ fn prepared_plain_processed_text(text: impl Into<String>) -> PreparedProcessedTe
         text,
         display_to_raw: vec![0; char_count.saturating_add(1)],
         link_targets: vec![None; char_count],
+        emphasis: vec![Emphasis::default(); char_count],
+    }
+}
+
+// Like `prepared_plain_processed_text`, but each display column maps to the raw
+// column under it, so caret tests can round-trip positions.
+#[cfg(test)]
+fn identity_prepared_processed_text(text: &str) -> PreparedProcessedText {
+    let char_count = text.chars().count();
+    PreparedProcessedText {
+        display_to_raw: (0..=char_count).collect(),
+        ..prepared_plain_processed_text(text)
     }
 }
 
@@ -256,12 +275,24 @@ fn prepare_processed_line_text(
     let (raw_column_base, rendered_raw, checklist_state) = if raw_override_active {
         (0, parsed_line.raw.clone(), None)
     } else {
-        markdown_visual_text(parsed_line).unwrap_or_else(|| (0, parsed_line.raw.clone(), None))
+        markdown_visual_text(parsed_line)
+            .or_else(|| fountain_visual_text(parsed_line))
+            .unwrap_or_else(|| (0, parsed_line.raw.clone(), None))
     };
-    let rendered = if raw_override_active {
-        identity_link_display_text(&rendered_raw)
+    let (rendered, emphasis) = if raw_override_active {
+        let rendered = identity_link_display_text(&rendered_raw);
+        let emphasis = vec![Emphasis::default(); rendered.text.chars().count()];
+        (rendered, emphasis)
     } else {
-        basscript_core::render_script_link_text(&rendered_raw)
+        let rendered = strip_rendered_notes(
+            &rendered_raw,
+            basscript_core::render_script_link_text(&rendered_raw),
+        );
+        strip_emphasis_markers(
+            &rendered_raw,
+            rendered,
+            fountain_line_has_emphasis(&parsed_line.kind),
+        )
     };
     let display_to_raw = rendered
         .display_to_raw
@@ -279,11 +310,84 @@ fn prepare_processed_line_text(
             text: rendered.text,
             display_to_raw,
             link_targets,
+            emphasis,
         },
         checklist_state,
     )
 }
 
+// Drops display chars that come from `[[note]]` spans while keeping the raw mapping
+// of everything else, so the caret still lands on the right source column.
+fn strip_rendered_notes(raw: &str, rendered: LinkDisplayText) -> LinkDisplayText {
+    let ranges = note_ranges(raw);
+    if ranges.is_empty() {
+        return rendered;
+    }
+
+    let mut text = String::new();
+    let mut display_to_raw = vec![rendered.display_to_raw.first().copied().unwrap_or(0)];
+    for (index, ch) in rendered.text.chars().enumerate() {
+        let raw_end = rendered.display_to_raw.get(index + 1).copied().unwrap_or(0);
+        let raw_column = raw_end.saturating_sub(1);
+        if ranges.iter().any(|range| range.contains(&raw_column)) {
+            if text.is_empty() {
+                display_to_raw[0] = raw_end;
+            }
+            continue;
+        }
+        text.push(ch);
+        display_to_raw.push(raw_end);
+    }
+
+    LinkDisplayText {
+        text,
+        display_to_raw,
+    }
+}
+
+// Hides the `*` and `_` markers of matched emphasis and reports the emphasis of
+// every display char that is left, keeping the raw mapping like the note pass.
+fn strip_emphasis_markers(
+    raw: &str,
+    rendered: LinkDisplayText,
+    styled: bool,
+) -> (LinkDisplayText, Vec<Emphasis>) {
+    let columns = if styled {
+        emphasis_columns(raw)
+    } else {
+        Vec::new()
+    };
+    if columns.iter().all(|column| column.is_some_and(Emphasis::is_plain)) {
+        let emphasis = vec![Emphasis::default(); rendered.text.chars().count()];
+        return (rendered, emphasis);
+    }
+
+    let mut text = String::new();
+    let mut display_to_raw = vec![rendered.display_to_raw.first().copied().unwrap_or(0)];
+    let mut emphasis = Vec::new();
+    for (index, ch) in rendered.text.chars().enumerate() {
+        let raw_end = rendered.display_to_raw.get(index + 1).copied().unwrap_or(0);
+        let column = columns.get(raw_end.saturating_sub(1)).copied();
+        let Some(style) = column.unwrap_or(Some(Emphasis::default())) else {
+            if text.is_empty() {
+                display_to_raw[0] = raw_end;
+            }
+            continue;
+        };
+        text.push(ch);
+        display_to_raw.push(raw_end);
+        emphasis.push(style);
+    }
+
+    (
+        LinkDisplayText {
+            text,
+            display_to_raw,
+        },
+        emphasis,
+    )
+}
+
 fn build_processed_segment_lines(
     state: &EditorState,
     start_line: usize,
@@ -297,17 +401,35 @@ fn build_processed_segment_lines(
     let lines_per_page = lines_per_page.max(1);
     let mut paged_lines = Vec::<ProcessedVisualLine>::new();
     let mut lines_in_page = 0usize;
+    let dual_column_columns = wrap_columns / 2;
+    let mut dual_left = Vec::<ProcessedVisualLine>::new();
+    let mut dual_right = Vec::<ProcessedVisualLine>::new();
     let markdown_front_matter =
         (!matches!(state.display_mode, DisplayMode::ProcessedRawCurrentLine))
             .then(|| markdown_front_matter_display(&state.document))
             .flatten();
+    let mut open_cue = None::<&ParsedLine>;
+    let mut scene_ordinal = if state.auto_number_scenes {
+        scene_heading_count(&state.parsed[..start_line.min(state.parsed.len())])
+    } else {
+        0
+    };
 
     for source_line in start_line..end_line_exclusive {
         let Some(parsed_line) = state.parsed.get(source_line) else {
             continue;
         };
+        if parsed_line.kind == LineKind::SceneHeading {
+            scene_ordinal += 1;
+        }
 
         let raw_override_active = raw_override_line == Some(source_line);
+        if !raw_override_active
+            && (parsed_line.kind == LineKind::Boneyard
+                || !state.processed_kind_visible(&parsed_line.kind))
+        {
+            continue;
+        }
         if !raw_override_active
             && markdown_front_matter.as_ref().is_some_and(|front_matter| {
                 source_line > 0 && source_line <= front_matter.closing_line_index
@@ -316,6 +438,42 @@ fn build_processed_segment_lines(
             continue;
         }
 
+        let dual_side = parsed_line.dual_dialogue.filter(|_| !raw_override_active);
+        if dual_side.is_none() {
+            // Blank lines between the two columns collapse into the side-by-side rows.
+            if parsed_line.kind == LineKind::Empty && !dual_left.is_empty() && dual_right.is_empty()
+            {
+                continue;
+            }
+            for visual_line in merge_dual_dialogue_rows(
+                std::mem::take(&mut dual_left),
+                std::mem::take(&mut dual_right),
+                dual_column_columns,
+            ) {
+                push_paged_visual_line(
+                    &mut paged_lines,
+                    &mut lines_in_page,
+                    visual_line,
+                    lines_per_page,
+                    spacer_lines,
+                );
+            }
+        }
+
+        // The speaker whose speech this line continues, for `(CONT'D)` cues.
+        let continued_cue = match (&parsed_line.kind, dual_side) {
+            (LineKind::Character, None) => {
+                open_cue = Some(parsed_line);
+                None
+            }
+            (LineKind::Dialogue | LineKind::Parenthetical | LineKind::Lyric, None) => open_cue,
+            _ => {
+                open_cue = None;
+                None
+            }
+        }
+        .filter(|_| state.dialogue_page_continuations);
+
         let render_override = (!raw_override_active)
             .then(|| markdown_front_matter.as_ref())
             .flatten()
@@ -326,9 +484,18 @@ fn build_processed_segment_lines(
             });
         let indent_width = if raw_override_active {
             0
+        } else if dual_side.is_some() {
+            parsed_line.indent_width() / 2
         } else {
             parsed_line.indent_width()
         };
+        let line_wrap_columns = match dual_side {
+            Some(DualDialogue::Left) => {
+                dual_column_columns.saturating_sub(DUAL_DIALOGUE_GUTTER_COLUMNS)
+            }
+            Some(DualDialogue::Right) => wrap_columns - dual_column_columns,
+            None => wrap_columns,
+        };
         let uppercase = if raw_override_active {
             false
         } else {
@@ -337,7 +504,13 @@ fn build_processed_segment_lines(
                 LineKind::SceneHeading | LineKind::Transition | LineKind::Character
             )
         };
-        let (prepared_text, checklist_state) = if let Some(front_matter) = markdown_front_matter
+        let (prepared_text, checklist_state) = if state.document.is_empty() && !raw_override_active
+        {
+            (
+                prepared_plain_processed_text(EMPTY_DOCUMENT_PLACEHOLDER),
+                None,
+            )
+        } else if let Some(front_matter) = markdown_front_matter
             .as_ref()
             .filter(|_| source_line == 0)
             .filter(|_| !raw_override_active)
@@ -347,7 +520,16 @@ fn build_processed_segment_lines(
                 None,
             )
         } else {
-            prepare_processed_line_text(parsed_line, raw_override_active)
+            // Plain text mode mirrors the source verbatim, forced markers included.
+            prepare_processed_line_text(parsed_line, raw_override_active || state.plain_text_mode)
+        };
+        // Centered rows pad to the middle of their own width; the markers are already gone.
+        // Title page rows are centered the same way with their keys dropped.
+        let centered = matches!(parsed_line.kind, LineKind::Centered | LineKind::TitlePage);
+        let indent_width = if centered && !raw_override_active {
+            line_wrap_columns.saturating_sub(prepared_text.text.chars().count()) / 2
+        } else {
+            indent_width
         };
         let mut wrapped = Vec::<ProcessedVisualLine>::new();
 
@@ -361,7 +543,8 @@ fn build_processed_segment_lines(
                     &prepared_text,
                     segment_start,
                     segment_end,
-                    wrap_columns,
+                    line_wrap_columns,
+                    state.processed_right_to_left,
                 );
             }
         } else {
@@ -373,7 +556,8 @@ fn build_processed_segment_lines(
                 &prepared_text,
                 0,
                 prepared_text.text.chars().count(),
-                wrap_columns,
+                line_wrap_columns,
+                state.processed_right_to_left,
             );
         }
 
@@ -383,23 +567,83 @@ fn build_processed_segment_lines(
             }
         }
 
+        // The plain-text view and the raw current line keep `#12#` in the text instead.
+        let shows_scene_number = parsed_line.kind == LineKind::SceneHeading
+            && !raw_override_active
+            && !state.plain_text_mode;
+        let scene_number = parsed_line
+            .scene_number()
+            .or_else(|| state.auto_number_scenes.then(|| scene_ordinal.to_string()))
+            .filter(|_| shows_scene_number);
+        if let Some(number) = scene_number
+            && let Some(first_wrapped) = wrapped.first_mut()
+        {
+            append_scene_number(
+                first_wrapped,
+                &number,
+                line_wrap_columns,
+                state.processed_right_to_left,
+            );
+        }
+
         if let Some(render_override) = render_override.clone() {
             for visual_line in &mut wrapped {
                 visual_line.render_override = Some(render_override.clone());
             }
         }
 
-        for visual_line in wrapped {
-            if lines_in_page >= lines_per_page {
-                push_page_spacers(&mut paged_lines, source_line, spacer_lines);
-                lines_in_page = 0;
+        match dual_side {
+            Some(DualDialogue::Left) => dual_left.extend(wrapped),
+            Some(DualDialogue::Right) => dual_right.extend(wrapped),
+            None => {
+                if !raw_override_active {
+                    keep_with_next_on_page(
+                        &mut paged_lines,
+                        &mut lines_in_page,
+                        source_line,
+                        &parsed_line.kind,
+                        wrapped.len(),
+                        lines_per_page,
+                    );
+                }
+                for visual_line in wrapped {
+                    if let Some(cue) = continued_cue
+                        && lines_in_page >= lines_per_page
+                        && lines_per_page > 2
+                        && page_ends_inside_speech(&paged_lines, &state.parsed)
+                    {
+                        push_dialogue_continuation(
+                            &mut paged_lines,
+                            &mut lines_in_page,
+                            cue,
+                            lines_per_page,
+                            spacer_lines,
+                            wrap_columns,
+                            state.processed_right_to_left,
+                        );
+                    }
+                    push_paged_visual_line(
+                        &mut paged_lines,
+                        &mut lines_in_page,
+                        visual_line,
+                        lines_per_page,
+                        spacer_lines,
+                    );
+                }
             }
-
-            paged_lines.push(visual_line);
-            lines_in_page = lines_in_page.saturating_add(1);
         }
     }
 
+    for visual_line in merge_dual_dialogue_rows(dual_left, dual_right, dual_column_columns) {
+        push_paged_visual_line(
+            &mut paged_lines,
+            &mut lines_in_page,
+            visual_line,
+            lines_per_page,
+            spacer_lines,
+        );
+    }
+
     if ends_with_hard_break && lines_in_page > 0 {
         let remaining_content = lines_per_page.saturating_sub(lines_in_page);
         let spacer_total = remaining_content.saturating_add(spacer_lines);
@@ -510,15 +754,15 @@ fn ensure_processed_cache(
     let marker_near_dirty = state
         .parsed
         .get(dirty_line)
-        .is_some_and(|line| is_fountain_page_break_marker(&line.raw))
+        .is_some_and(|line| line.kind == LineKind::PageBreak)
         || dirty_line
             .checked_sub(1)
             .and_then(|line| state.parsed.get(line))
-            .is_some_and(|line| is_fountain_page_break_marker(&line.raw))
+            .is_some_and(|line| line.kind == LineKind::PageBreak)
         || state
             .parsed
             .get(dirty_line.saturating_add(1))
-            .is_some_and(|line| is_fountain_page_break_marker(&line.raw));
+            .is_some_and(|line| line.kind == LineKind::PageBreak);
 
     if marker_near_dirty {
         state.processed_cache = Some(build_processed_cache(
@@ -599,13 +843,17 @@ fn push_processed_fragment(
     text: String,
     is_link: bool,
     link_target: Option<String>,
+    emphasis: Emphasis,
 ) {
     if text.is_empty() {
         return;
     }
 
     if let Some(previous) = fragments.last_mut() {
-        if previous.is_link == is_link && previous.link_target == link_target {
+        if previous.is_link == is_link
+            && previous.link_target == link_target
+            && previous.emphasis == emphasis
+        {
             previous.text.push_str(&text);
             return;
         }
@@ -615,6 +863,7 @@ fn push_processed_fragment(
         text,
         is_link,
         link_target,
+        emphasis,
     });
 }
 
@@ -635,6 +884,7 @@ fn push_wrapped_visual_lines(
     segment_start: usize,
     segment_end: usize,
     wrap_columns: usize,
+    right_to_left: bool,
 ) {
     let chars = prepared_text.text.chars().collect::<Vec<_>>();
     let max_content_columns = wrap_columns.saturating_sub(indent_width).max(1);
@@ -656,6 +906,7 @@ fn push_wrapped_visual_lines(
                 text: " ".repeat(blank_columns),
                 is_link: false,
                 link_target: None,
+                emphasis: Emphasis::default(),
             }],
             display_to_raw: vec![raw_column; blank_columns.saturating_add(1)],
             raw_start_column: raw_column,
@@ -663,6 +914,7 @@ fn push_wrapped_visual_lines(
             markdown_checklist_checked: None,
             render_override: None,
             is_spacer: false,
+            is_synthetic: false,
         });
         return;
     }
@@ -682,9 +934,21 @@ fn push_wrapped_visual_lines(
             split = max_end;
         }
 
+        // Right-to-left lines mirror the element indent from the right edge of the text box.
+        let line_indent = if right_to_left {
+            wrap_columns.saturating_sub(indent_width.saturating_add(split - start))
+        } else {
+            indent_width
+        };
         let mut fragments = Vec::<ProcessedVisualFragment>::new();
-        if indent_width > 0 {
-            push_processed_fragment(&mut fragments, " ".repeat(indent_width), false, None);
+        if line_indent > 0 {
+            push_processed_fragment(
+                &mut fragments,
+                " ".repeat(line_indent),
+                false,
+                None,
+                Emphasis::default(),
+            );
         }
 
         let mut index = start;
@@ -695,6 +959,7 @@ fn push_wrapped_visual_lines(
                 .cloned()
                 .unwrap_or(None);
             let is_link = link_target.is_some();
+            let emphasis = prepared_text.emphasis.get(index).copied().unwrap_or_default();
             let fragment_start = index;
             index += 1;
             while index < split
@@ -704,6 +969,7 @@ fn push_wrapped_visual_lines(
                     .cloned()
                     .unwrap_or(None)
                     == link_target
+                && prepared_text.emphasis.get(index).copied().unwrap_or_default() == emphasis
             {
                 index += 1;
             }
@@ -714,6 +980,7 @@ fn push_wrapped_visual_lines(
                 uppercase_processed_text(&fragment_text, uppercase),
                 is_link,
                 link_target,
+                emphasis,
             );
         }
 
@@ -731,7 +998,7 @@ fn push_wrapped_visual_lines(
             .get(split)
             .copied()
             .unwrap_or(raw_start_column);
-        let mut display_to_raw = vec![raw_start_column; indent_width.saturating_add(1)];
+        let mut display_to_raw = vec![raw_start_column; line_indent.saturating_add(1)];
         display_to_raw.extend(
             prepared_text.display_to_raw[start.saturating_add(1)..=split]
                 .iter()
@@ -748,6 +1015,7 @@ fn push_wrapped_visual_lines(
             markdown_checklist_checked: None,
             render_override: None,
             is_spacer: false,
+            is_synthetic: false,
         });
 
         // Skip one wrapping space at the split boundary for word-wrapped output.
@@ -758,6 +1026,231 @@ fn push_wrapped_visual_lines(
     }
 }
 
+// Hangs the number at the right edge of the heading's first row; a heading that
+// fills the row keeps one space before it. Right-to-left rows take it into their
+// leading indent instead, when it fits.
+fn append_scene_number(
+    row: &mut ProcessedVisualLine,
+    number: &str,
+    wrap_columns: usize,
+    right_to_left: bool,
+) {
+    if right_to_left {
+        let label = format!("{number} ");
+        let label_columns = label.chars().count();
+        let indent = row.text.chars().take_while(|ch| *ch == ' ').count();
+        if indent >= label_columns
+            && let Some(first) = row.fragments.first_mut()
+        {
+            first.text.replace_range(..label_columns, &label);
+            row.text.replace_range(..label_columns, &label);
+        }
+        return;
+    }
+
+    let used_columns = row.text.chars().count() + number.chars().count();
+    let padding = wrap_columns.saturating_sub(used_columns).max(1);
+    let suffix = format!("{}{number}", " ".repeat(padding));
+    let raw_end = row.display_to_raw.last().copied().unwrap_or(row.raw_end_column);
+    row.display_to_raw
+        .extend(std::iter::repeat_n(raw_end, suffix.chars().count()));
+    row.text.push_str(&suffix);
+    push_processed_fragment(&mut row.fragments, suffix, false, None, Emphasis::default());
+}
+
+fn scene_heading_count(parsed: &[ParsedLine]) -> usize {
+    parsed
+        .iter()
+        .filter(|line| line.kind == LineKind::SceneHeading)
+        .count()
+}
+
+fn push_paged_visual_line(
+    paged_lines: &mut Vec<ProcessedVisualLine>,
+    lines_in_page: &mut usize,
+    visual_line: ProcessedVisualLine,
+    lines_per_page: usize,
+    spacer_lines: usize,
+) {
+    if *lines_in_page >= lines_per_page {
+        push_page_spacers(paged_lines, visual_line.source_line, spacer_lines);
+        *lines_in_page = 0;
+    }
+
+    paged_lines.push(visual_line);
+    *lines_in_page = lines_in_page.saturating_add(1);
+}
+
+// Pads out the page when core pagination would start a new one before this line,
+// so a scene heading or cue opens the next page with what follows instead of
+// ending this one.
+fn keep_with_next_on_page(
+    paged_lines: &mut Vec<ProcessedVisualLine>,
+    lines_in_page: &mut usize,
+    source_line: usize,
+    kind: &LineKind,
+    line_rows: usize,
+    lines_per_page: usize,
+) {
+    if !starts_new_page(kind, line_rows, *lines_in_page, lines_per_page) {
+        return;
+    }
+
+    let room = lines_per_page.saturating_sub(*lines_in_page);
+    push_page_spacers(paged_lines, source_line, room);
+    *lines_in_page = lines_per_page;
+}
+
+const MORE_CUE: &str = "(MORE)";
+const CONTINUED_CUE_SUFFIX: &str = " (CONT'D)";
+
+// `SARAH (V.O.)` continues as `SARAH (CONT'D)`.
+fn continued_cue_text(parsed_line: &ParsedLine) -> String {
+    let cue = parsed_line.processed_text();
+    let cue = cue.trim();
+    split_character_extension(cue).map_or(cue, |(name, _)| name).to_owned()
+}
+
+// The cue itself may sit at the bottom of a page; only a speech row before the
+// break needs `(MORE)`.
+fn page_ends_inside_speech(paged_lines: &[ProcessedVisualLine], parsed: &[ParsedLine]) -> bool {
+    paged_lines
+        .last()
+        .filter(|row| row.maps_to_source())
+        .and_then(|row| parsed.get(row.source_line))
+        .is_some_and(|line| {
+            matches!(
+                line.kind,
+                LineKind::Dialogue | LineKind::Parenthetical | LineKind::Lyric
+            )
+        })
+}
+
+// Ends a full page with `(MORE)` in place of its last speech row and carries that
+// row over under a `NAME (CONT'D)` cue, as printed scripts do.
+fn push_dialogue_continuation(
+    paged_lines: &mut Vec<ProcessedVisualLine>,
+    lines_in_page: &mut usize,
+    cue: &ParsedLine,
+    lines_per_page: usize,
+    spacer_lines: usize,
+    wrap_columns: usize,
+    right_to_left: bool,
+) {
+    let Some(carried) = paged_lines.pop() else {
+        return;
+    };
+    let source_line = carried.source_line;
+    // Both synthetic rows sit at the cue's own indent.
+    let cue_row = |text: &str| {
+        synthetic_cue_row(source_line, text, cue.indent_width(), wrap_columns, right_to_left)
+    };
+    paged_lines.push(cue_row(MORE_CUE));
+    let continued = format!("{}{CONTINUED_CUE_SUFFIX}", continued_cue_text(cue));
+    for row in [cue_row(&continued), carried] {
+        push_paged_visual_line(paged_lines, lines_in_page, row, lines_per_page, spacer_lines);
+    }
+}
+
+fn synthetic_cue_row(
+    source_line: usize,
+    text: &str,
+    indent_width: usize,
+    wrap_columns: usize,
+    right_to_left: bool,
+) -> ProcessedVisualLine {
+    let prepared = prepared_plain_processed_text(text);
+    let mut rows = Vec::new();
+    push_wrapped_visual_lines(
+        &mut rows,
+        source_line,
+        indent_width,
+        true,
+        &prepared,
+        0,
+        prepared.text.chars().count(),
+        wrap_columns,
+        right_to_left,
+    );
+    let mut row = rows.remove(0);
+    row.is_synthetic = true;
+    row.render_override = Some(ProcessedLineRenderOverride {
+        kind: LineKind::Character,
+        markdown_heading_level: None,
+    });
+    row
+}
+
+const DUAL_DIALOGUE_GUTTER_COLUMNS: usize = 2;
+
+// Rows share the left column's source line, so the caret and clicks follow the left
+// speaker; right-column rows past the end of the left column keep their own line.
+fn merge_dual_dialogue_rows(
+    left: Vec<ProcessedVisualLine>,
+    right: Vec<ProcessedVisualLine>,
+    column_columns: usize,
+) -> Vec<ProcessedVisualLine> {
+    let row_count = left.len().max(right.len());
+    let mut left = left.into_iter();
+    let mut right = right.into_iter();
+    let mut rows = Vec::with_capacity(row_count);
+
+    for _ in 0..row_count {
+        let row = match (left.next(), right.next()) {
+            (Some(mut row), right_row) => {
+                if let Some(right_row) = right_row {
+                    let padding = column_columns
+                        .saturating_sub(row.text.chars().count())
+                        .max(1);
+                    push_processed_fragment(
+                        &mut row.fragments,
+                        " ".repeat(padding),
+                        false,
+                        None,
+                        Emphasis::default(),
+                    );
+                    for fragment in right_row.fragments {
+                        push_processed_fragment(
+                            &mut row.fragments,
+                            fragment.text,
+                            fragment.is_link,
+                            fragment.link_target,
+                            fragment.emphasis,
+                        );
+                    }
+                    row.text = row
+                        .fragments
+                        .iter()
+                        .map(|fragment| fragment.text.as_str())
+                        .collect();
+                }
+                row
+            }
+            (None, Some(mut row)) => {
+                let padding = " ".repeat(column_columns);
+                row.fragments.insert(
+                    0,
+                    ProcessedVisualFragment {
+                        text: padding.clone(),
+                        is_link: false,
+                        link_target: None,
+                        emphasis: Emphasis::default(),
+                    },
+                );
+                row.text.insert_str(0, &padding);
+                let mut display_to_raw = vec![row.raw_start_column; column_columns];
+                display_to_raw.append(&mut row.display_to_raw);
+                row.display_to_raw = display_to_raw;
+                row
+            }
+            (None, None) => break,
+        };
+        rows.push(row);
+    }
+
+    rows
+}
+
 fn push_page_spacers(out: &mut Vec<ProcessedVisualLine>, source_line: usize, count: usize) {
     for _ in 0..count {
         out.push(ProcessedVisualLine {
@@ -767,6 +1260,7 @@ fn push_page_spacers(out: &mut Vec<ProcessedVisualLine>, source_line: usize, cou
                 text: " ".to_owned(),
                 is_link: false,
                 link_target: None,
+                emphasis: Emphasis::default(),
             }],
             display_to_raw: vec![0, 0],
             raw_start_column: 0,
@@ -774,13 +1268,46 @@ fn push_page_spacers(out: &mut Vec<ProcessedVisualLine>, source_line: usize, cou
             markdown_checklist_checked: None,
             render_override: None,
             is_spacer: true,
+            is_synthetic: false,
         });
     }
 }
 
-fn is_fountain_page_break_marker(raw: &str) -> bool {
-    let trimmed = raw.trim();
-    trimmed.chars().count() >= 3 && trimmed.chars().all(|ch| ch == '=')
+const FOUNTAIN_PAGE_BREAK_MARKER: &str = "===";
+
+// Puts `===` on its own line at `position`, padded with blank lines so it never
+// glues onto neighbouring elements, and returns where typing should continue.
+fn insert_fountain_page_break(document: &mut Document, position: Position) -> Position {
+    let position = document.clamp_position(position);
+    let is_blank = |document: &Document, line: usize| {
+        document.line(line).is_some_and(|raw| raw.trim().is_empty())
+    };
+
+    let insert_at = if is_blank(document, position.line) || position.column == 0 {
+        position.line
+    } else if position.column >= document.line_len_chars(position.line) {
+        position.line + 1
+    } else {
+        document.insert_newline(position).line
+    };
+
+    let mut lines = Vec::new();
+    if insert_at > 0 && !is_blank(document, insert_at - 1) {
+        lines.push(String::new());
+    }
+    let marker_line = insert_at + lines.len();
+    lines.push(FOUNTAIN_PAGE_BREAK_MARKER.to_string());
+    if !is_blank(document, insert_at) {
+        lines.push(String::new());
+    }
+    document.insert_lines(insert_at, &lines);
+
+    let line = if marker_line + 2 < document.line_count() {
+        marker_line + 2
+    } else {
+        marker_line + 1
+    };
+    Position { line, column: 0 }
 }
 
 fn should_split_on_double_space(state: &EditorState, kind: &LineKind) -> bool {
@@ -809,11 +1336,11 @@ fn first_visual_index_for_source_line(
 ) -> Option<usize> {
     lines
         .iter()
-        .position(|line| !line.is_spacer && line.source_line >= source_line)
+        .position(|line| line.maps_to_source() && line.source_line >= source_line)
         .or_else(|| {
             lines
                 .iter()
-                .rposition(|line| !line.is_spacer && line.source_line <= source_line)
+                .rposition(|line| line.maps_to_source() && line.source_line <= source_line)
         })
 }
 
@@ -874,33 +1401,47 @@ fn processed_display_column_from_raw(
     display_column.min(visual_line.text.chars().count())
 }
 
-fn processed_caret_visual<'a>(
-    state: &EditorState,
-    processed_view: &'a ProcessedView,
-) -> Option<(usize, usize, &'a str)> {
-    processed_cursor_visual_from_lines(state, &processed_view.lines)
-}
-
-fn processed_cursor_visual_from_lines<'a>(
-    state: &EditorState,
-    lines: &'a [ProcessedVisualLine],
-) -> Option<(usize, usize, &'a str)> {
-    let source_line = state.cursor.position.line;
-    let raw_column = state.cursor.position.column;
+fn processed_cursor_visual_from_lines(
+    cursor: Position,
+    lines: &[ProcessedVisualLine],
+) -> Option<(usize, usize, &str)> {
+    let source_line = cursor.line;
+    let raw_column = cursor.column;
 
     let relevant = lines
         .iter()
         .enumerate()
-        .filter(|(_, line)| !line.is_spacer && line.source_line == source_line)
+        .filter(|(_, line)| line.maps_to_source() && line.source_line == source_line)
         .collect::<Vec<_>>();
 
-    let (default_index, default_line) = *relevant.last()?;
+    let Some(&(default_index, default_line)) = relevant.last() else {
+        // The caret's line is not rendered (hidden kind or front matter): park it at
+        // the start of the nearest rendered line below, else the last one above.
+        let index = lines
+            .iter()
+            .position(|line| line.maps_to_source() && line.source_line > source_line)
+            .or_else(|| {
+                lines
+                    .iter()
+                    .rposition(|line| line.maps_to_source() && line.source_line < source_line)
+            })?;
+        return Some((index, 0, &lines[index].text));
+    };
 
     for (entry_index, (visual_index, visual_line)) in relevant.iter().enumerate() {
         let next_start = relevant
             .get(entry_index + 1)
             .map(|(_, next_line)| next_line.raw_start_column);
 
+        // A caret anywhere in the double-space gap between beats belongs to
+        // the start of the following beat row.
+        let in_beat_gap = next_start.is_some_and(|start| {
+            start >= visual_line.raw_end_column + 2 && raw_column >= visual_line.raw_end_column
+        });
+        if in_beat_gap {
+            continue;
+        }
+
         if raw_column <= visual_line.raw_end_column
             || next_start.is_some_and(|start| raw_column < start)
             || entry_index + 1 == relevant.len()
@@ -920,22 +1461,41 @@ fn processed_cursor_visual_from_lines<'a>(
     ))
 }
 
+fn processed_vertical_move(
+    lines: &[ProcessedVisualLine],
+    cursor: Position,
+    down: bool,
+) -> Option<Position> {
+    let (index, display_column, _) = processed_cursor_visual_from_lines(cursor, lines)?;
+    let target = if down {
+        (index + 1..lines.len()).find(|candidate| lines[*candidate].maps_to_source())?
+    } else {
+        (0..index).rev().find(|candidate| lines[*candidate].maps_to_source())?
+    };
+    let visual_line = &lines[target];
+
+    Some(Position {
+        line: visual_line.source_line,
+        column: processed_raw_column_from_display(visual_line, display_column),
+    })
+}
+
 fn nearest_non_spacer_visual_index(lines: &[ProcessedVisualLine], index: usize) -> Option<usize> {
     if lines.is_empty() {
         return None;
     }
-    if lines.get(index).is_some_and(|line| !line.is_spacer) {
+    if lines.get(index).is_some_and(|line| line.maps_to_source()) {
         return Some(index);
     }
 
     for distance in 1..lines.len() {
         let forward = index.saturating_add(distance);
-        if lines.get(forward).is_some_and(|line| !line.is_spacer) {
+        if lines.get(forward).is_some_and(|line| line.maps_to_source()) {
             return Some(forward);
         }
 
         let backward = index.saturating_sub(distance);
-        if lines.get(backward).is_some_and(|line| !line.is_spacer) {
+        if lines.get(backward).is_some_and(|line| line.maps_to_source()) {
             return Some(backward);
         }
     }
@@ -964,6 +1524,9 @@ fn processed_visual_fragment_for_part(
     let all_same_link_state = tail.iter().all(|fragment| {
         fragment.is_link == tail[0].is_link && fragment.link_target == tail[0].link_target
     });
+    let all_same_emphasis = tail
+        .iter()
+        .all(|fragment| fragment.emphasis == tail[0].emphasis);
 
     Some(ProcessedVisualFragment {
         text: tail
@@ -976,6 +1539,11 @@ fn processed_visual_fragment_for_part(
         } else {
             None
         },
+        emphasis: if all_same_emphasis {
+            tail[0].emphasis
+        } else {
+            Emphasis::default()
+        },
     })
 }
 
@@ -1045,6 +1613,7 @@ fn apply_processed_styles(
             &mut TextFont,
             &mut LineHeight,
             &mut TextColor,
+            &mut UnderlineColor,
         ),
         Without<PanelText>,
     >,
@@ -1059,10 +1628,18 @@ fn apply_processed_styles(
 ) {
     let page_step_lines = page_step_lines.max(1);
     let lines_per_page = lines_per_page.max(1).min(page_step_lines);
-
-    for (processed_span, mut text_span, mut text_font, mut text_line_height, mut text_color) in
-        processed_span_query.iter_mut()
+    let focus_range = state.focus_scene_range();
+
+    for (
+        processed_span,
+        mut text_span,
+        mut text_font,
+        mut text_line_height,
+        mut text_color,
+        mut underline_color,
+    ) in processed_span_query.iter_mut()
     {
+        underline_color.0 = Color::NONE;
         let page_index = first_visible_page.saturating_add(processed_span.slot);
         let line_offset = processed_span
             .line_offset
@@ -1101,13 +1678,14 @@ fn apply_processed_styles(
                 processed_line_style_for_kind(
                     &render_override.kind,
                     render_override.markdown_heading_level,
+                    &state.element_colors,
                 ),
                 true,
             )
         } else if raw_current_line_mode_active {
             (default_line_render_style(), false)
         } else if let Some(parsed_line) = state.parsed.get(visual_line.source_line) {
-            (processed_line_style(parsed_line), true)
+            (processed_line_style(parsed_line, &state.element_colors), true)
         } else {
             (default_line_render_style(), false)
         };
@@ -1157,7 +1735,47 @@ fn apply_processed_styles(
         } else {
             style.color
         };
+        if let Some(color) = over_width_color(state, visual_line) {
+            text_color.0 = color;
+        }
+        if focus_range
+            .as_ref()
+            .is_some_and(|range| !range.contains(&visual_line.source_line))
+        {
+            text_color.0 = dimmed_focus_color(text_color.0);
+        }
+        if fragment.emphasis.underline && allow_link_color {
+            underline_color.0 = text_color.0;
+        }
+    }
+}
+
+// Dialogue and action have different printable widths on a real page; a row
+// wider than its element's width is tinted by element so both are told apart.
+fn over_width_color(state: &EditorState, visual_line: &ProcessedVisualLine) -> Option<Color> {
+    if !state.element_width_warnings || !visual_line.maps_to_source() {
+        return None;
+    }
+
+    let parsed_line = state.parsed.get(visual_line.source_line)?;
+    // Side-by-side rows hold two half-width columns, not one over-long element.
+    if parsed_line.dual_dialogue.is_some() {
+        return None;
+    }
+    let kind = &parsed_line.kind;
+    let width = state.element_width_columns(kind)?;
+    if visual_line.text.trim().chars().count() <= width {
+        return None;
     }
+
+    Some(match kind {
+        LineKind::Dialogue | LineKind::Lyric => COLOR_DIALOGUE_OVER_WIDTH,
+        _ => COLOR_ACTION_OVER_WIDTH,
+    })
+}
+
+fn dimmed_focus_color(color: Color) -> Color {
+    color.with_alpha(color.alpha() * FOCUS_DIMMED_ALPHA)
 }
 
 fn panel_layout_info<'a>(
@@ -1187,6 +1805,16 @@ fn layout_line_bounds(layout: &TextLayoutInfo, inverse_scale: f32) -> Vec<(usize
         .collect()
 }
 
+// Distance between consecutive line tops in a laid-out text block; None until at
+// least two lines have glyphs.
+fn layout_line_step(layout: &TextLayoutInfo, inverse_scale: f32) -> Option<f32> {
+    let top_samples = layout_line_bounds(layout, inverse_scale)
+        .into_iter()
+        .map(|(index, top, _)| (index, top))
+        .collect::<Vec<_>>();
+    (top_samples.len() >= 2).then(|| default_line_step(&top_samples, 0.0))
+}
+
 fn median(values: &mut [f32]) -> Option<f32> {
     if values.is_empty() {
         return None;
@@ -1484,3 +2112,267 @@ fn is_printable_char(chr: char) -> bool {
 
     !private_use && !chr.is_ascii_control()
 }
+
+fn is_insertable_text(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|chr| chr == '\n' || is_printable_char(chr))
+}
+
+#[cfg(test)]
+mod processed_page_tests {
+    use super::*;
+
+    #[test]
+    fn inserted_page_break_is_padded_and_splits_pages() {
+        let mut document = Document::from_text("INT. HOUSE - DAY\nJohn sits. He waits.\nCUT TO:");
+        let caret = insert_fountain_page_break(
+            &mut document,
+            Position {
+                line: 1,
+                column: 10,
+            },
+        );
+
+        assert_eq!(
+            document.lines(),
+            ["INT. HOUSE - DAY", "John sits.", "", "===", "", " He waits.", "CUT TO:"]
+        );
+        assert_eq!(caret, Position { line: 5, column: 0 });
+        assert!(basscript_core::is_page_break(document.lines()[3].as_str()));
+
+        let mut document = Document::from_text("FADE IN:\n");
+        let caret = insert_fountain_page_break(&mut document, Position { line: 1, column: 0 });
+        assert_eq!(document.lines(), ["FADE IN:", "", "===", ""]);
+        assert_eq!(caret, Position { line: 3, column: 0 });
+    }
+
+    #[test]
+    fn caret_in_beat_gap_lands_on_next_beat_row() {
+        let prepared = identity_prepared_processed_text("Wait.  Listen.");
+        let mut lines = Vec::new();
+        for (start, end) in double_space_segments(&prepared.text) {
+            push_wrapped_visual_lines(&mut lines, 0, 0, false, &prepared, start, end, 40, false);
+        }
+        assert_eq!(lines.len(), 2);
+
+        let caret_at = |column| {
+            processed_cursor_visual_from_lines(Position { line: 0, column }, &lines)
+                .map(|(index, display_column, _)| (index, display_column))
+        };
+        assert_eq!(caret_at(4), Some((0, 4)));
+        assert_eq!(caret_at(5), Some((1, 0)));
+        assert_eq!(caret_at(6), Some((1, 0)));
+        assert_eq!(caret_at(7), Some((1, 0)));
+        assert_eq!(caret_at(8), Some((1, 1)));
+    }
+
+    #[test]
+    fn vertical_move_steps_through_wrapped_rows() {
+        let first = identity_prepared_processed_text("one two three four");
+        let second = identity_prepared_processed_text("five");
+        let mut lines = Vec::new();
+        push_wrapped_visual_lines(&mut lines, 0, 0, false, &first, 0, 18, 10, false);
+        push_wrapped_visual_lines(&mut lines, 1, 0, false, &second, 0, 4, 10, false);
+        assert_eq!(lines.len(), 3);
+
+        let down = processed_vertical_move(&lines, Position { line: 0, column: 2 }, true);
+        let down = down.expect("second wrapped row");
+        assert_eq!(down.line, 0);
+        assert_eq!(down.column, lines[1].raw_start_column + 2);
+
+        let next_source = processed_vertical_move(&lines, down, true);
+        assert_eq!(next_source, Some(Position { line: 1, column: 2 }));
+        assert_eq!(processed_vertical_move(&lines, Position { line: 1, column: 0 }, true), None);
+        assert_eq!(processed_vertical_move(&lines, Position { line: 0, column: 0 }, false), None);
+    }
+
+    #[test]
+    fn caret_on_hidden_line_parks_on_nearest_rendered_line() {
+        let prepared = identity_prepared_processed_text("CUT");
+        let mut lines = Vec::new();
+        push_wrapped_visual_lines(&mut lines, 0, 0, false, &prepared, 0, 3, 40, false);
+        push_wrapped_visual_lines(&mut lines, 2, 0, false, &prepared, 0, 3, 40, false);
+
+        let visual_for = |line| {
+            processed_cursor_visual_from_lines(Position { line, column: 2 }, &lines)
+                .map(|(index, display_column, _)| (index, display_column))
+        };
+        assert_eq!(visual_for(1), Some((1, 0)));
+        assert_eq!(visual_for(5), Some((1, 0)));
+    }
+
+    #[test]
+    fn dual_dialogue_rows_sit_side_by_side() {
+        let brick = identity_prepared_processed_text("BRICK");
+        let steel = identity_prepared_processed_text("STEEL");
+        let said = identity_prepared_processed_text("Screw it all.");
+        let mut left = Vec::new();
+        push_wrapped_visual_lines(&mut left, 0, 4, false, &brick, 0, 5, 18, false);
+        let mut right = Vec::new();
+        push_wrapped_visual_lines(&mut right, 2, 4, false, &steel, 0, 5, 20, false);
+        push_wrapped_visual_lines(&mut right, 3, 2, false, &said, 0, 13, 10, false);
+
+        let rows = merge_dual_dialogue_rows(left, right, 20);
+        let texts = rows.iter().map(|row| row.text.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            [
+                format!("    BRICK{}STEEL", " ".repeat(15)),
+                format!("{}Screw", " ".repeat(22)),
+                format!("{}it all.", " ".repeat(22)),
+            ]
+        );
+        assert_eq!(rows[0].source_line, 0);
+        assert_eq!(rows[1].source_line, 3);
+        assert_eq!(processed_raw_column_from_display(&rows[1], 23), 1);
+    }
+
+    #[test]
+    fn stripped_notes_keep_caret_mapping_for_remaining_text() {
+        let raw = "Run [[faster?]] now [[ok]]";
+        let rendered = strip_rendered_notes(raw, basscript_core::render_script_link_text(raw));
+
+        assert_eq!(rendered.text, "Run now");
+        assert_eq!(rendered.display_to_raw, [0, 1, 2, 3, 16, 17, 18, 19]);
+    }
+
+    #[test]
+    fn scene_numbers_hang_at_the_right_edge() {
+        let parsed = parse_document_with_options(
+            &Document::from_text("INT. HOUSE - DAY #12A#"),
+            DocumentFormat::Fountain,
+            &ParseOptions::default(),
+        );
+        let (prepared, _) = prepare_processed_line_text(&parsed[0], false);
+        assert_eq!(prepared.text, "INT. HOUSE - DAY");
+
+        let mut rows = Vec::new();
+        push_wrapped_visual_lines(&mut rows, 0, 0, true, &prepared, 0, 16, 30, false);
+        append_scene_number(&mut rows[0], "12A", 30, false);
+        assert_eq!(rows[0].text, format!("INT. HOUSE - DAY{}12A", " ".repeat(11)));
+        assert_eq!(rows[0].display_to_raw.len(), 31);
+        assert_eq!(rows[0].display_to_raw[30], 16);
+
+        let mut rows = Vec::new();
+        push_wrapped_visual_lines(&mut rows, 0, 0, true, &prepared, 0, 16, 30, true);
+        append_scene_number(&mut rows[0], "12A", 30, true);
+        assert_eq!(rows[0].text, format!("12A{}INT. HOUSE - DAY", " ".repeat(11)));
+    }
+
+    #[test]
+    fn emphasis_markers_are_hidden_and_split_fragments() {
+        let parsed = parse_document_with_options(
+            &Document::from_text("She *really* means **it**."),
+            DocumentFormat::Fountain,
+            &ParseOptions::default(),
+        );
+        let (prepared, _) = prepare_processed_line_text(&parsed[0], false);
+        assert_eq!(prepared.text, "She really means it.");
+        assert_eq!(prepared.display_to_raw[4..=5], [4, 6]);
+
+        let mut rows = Vec::new();
+        push_wrapped_visual_lines(&mut rows, 0, 0, false, &prepared, 0, 20, 40, false);
+        let fragments = rows[0]
+            .fragments
+            .iter()
+            .map(|fragment| (fragment.text.as_str(), fragment.emphasis))
+            .collect::<Vec<_>>();
+        let italic = Emphasis {
+            italic: true,
+            ..Emphasis::default()
+        };
+        let bold = Emphasis {
+            bold: true,
+            ..Emphasis::default()
+        };
+        assert_eq!(
+            fragments,
+            [
+                ("She ", Emphasis::default()),
+                ("really", italic),
+                (" means ", Emphasis::default()),
+                ("it", bold),
+                (".", Emphasis::default()),
+            ]
+        );
+
+        let (raw, _) = prepare_processed_line_text(&parsed[0], true);
+        assert_eq!(raw.text, "She *really* means **it**.");
+    }
+
+    #[test]
+    fn scene_heading_near_the_page_bottom_moves_to_the_next_page() {
+        let heading = LineKind::SceneHeading;
+        let mut rows = Vec::new();
+        let mut lines_in_page = 50;
+        keep_with_next_on_page(&mut rows, &mut lines_in_page, 7, &heading, 1, 54);
+        assert!(rows.is_empty());
+        assert_eq!(lines_in_page, 50);
+
+        lines_in_page = 52;
+        keep_with_next_on_page(&mut rows, &mut lines_in_page, 7, &heading, 1, 54);
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| row.is_spacer && row.source_line == 7));
+        assert_eq!(lines_in_page, 54);
+
+        let heading = prepared_plain_processed_text("INT. BANK - DAY");
+        push_wrapped_visual_lines(&mut rows, 7, 0, true, &heading, 0, 15, 40, false);
+        let mut pushed = Vec::new();
+        push_paged_visual_line(&mut pushed, &mut lines_in_page, rows.pop().unwrap(), 54, 3);
+        assert_eq!(pushed.len(), 4);
+        assert_eq!(lines_in_page, 1);
+    }
+
+    #[test]
+    fn speech_across_a_page_break_gets_more_and_contd() {
+        let mut rows = Vec::new();
+        for (source_line, text) in [(0, "BOB"), (1, "First."), (2, "Second.")] {
+            let prepared = prepared_plain_processed_text(text);
+            push_wrapped_visual_lines(&mut rows, source_line, 0, false, &prepared, 0, 7, 40, false);
+        }
+        let mut lines_in_page = 3;
+
+        let document = Document::from_text("BOB\nFirst.");
+        let options = ParseOptions::default();
+        let cue = &parse_document_with_options(&document, DocumentFormat::Fountain, &options)[0];
+        push_dialogue_continuation(&mut rows, &mut lines_in_page, cue, 3, 1, 40, false);
+        let texts = rows.iter().map(|row| row.text.trim()).collect::<Vec<_>>();
+        let indent = LineKind::Character.indent_width();
+        assert_eq!(texts, ["BOB", "First.", "(MORE)", "", "BOB (CONT'D)", "Second."]);
+        assert_eq!(rows[2].text.chars().take_while(|ch| *ch == ' ').count(), indent);
+        assert_eq!(lines_in_page, 2);
+
+        let caret = processed_cursor_visual_from_lines(Position { line: 2, column: 0 }, &rows);
+        assert_eq!(caret.map(|(index, _, _)| index), Some(5));
+        let up = processed_vertical_move(&rows, Position { line: 2, column: 0 }, false);
+        assert_eq!(up, Some(Position { line: 1, column: 0 }));
+    }
+
+    #[test]
+    fn synced_top_line_lands_on_the_top_edge_of_the_view() {
+        let mut rows = Vec::new();
+        for (source_line, text) in [(0, "Long words wrap over rows."), (1, "Next."), (2, "Last.")] {
+            let prepared = prepared_plain_processed_text(text);
+            let end = text.chars().count();
+            push_wrapped_visual_lines(
+                &mut rows,
+                source_line,
+                0,
+                false,
+                &prepared,
+                0,
+                end,
+                8,
+                false,
+            );
+        }
+
+        let anchor = first_visual_index_for_source_line(&rows, 1).unwrap();
+        assert!(anchor > 1);
+        let page_step_lines = 2;
+        let view = build_processed_view(&rows, anchor, page_step_lines, 4);
+        let line_in_page = processed_anchor_line_in_page(&view, page_step_lines);
+        assert_eq!(view.start_index + line_in_page, anchor);
+        assert_eq!(view.lines[line_in_page].source_line, 1);
+        assert_eq!(view.lines[line_in_page].text.trim(), "Next.");
+    }
+}
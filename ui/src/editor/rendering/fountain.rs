@@ -1,31 +1,81 @@
-fn fountain_line_style(kind: &LineKind) -> Option<LineRenderStyle> {
+fn fountain_visual_text(parsed_line: &ParsedLine) -> Option<(usize, String, Option<bool>)> {
+    if parsed_line.kind == LineKind::Centered {
+        let (column, inner) = centered_text(&parsed_line.raw)?;
+        return Some((column, inner.to_owned(), None));
+    }
+    if parsed_line.kind == LineKind::TitlePage {
+        let (column, value) = title_page_value(&parsed_line.raw);
+        return Some((column, value.to_owned(), None));
+    }
+
+    if !matches!(
+        parsed_line.kind,
+        LineKind::Character
+            | LineKind::Dialogue
+            | LineKind::Lyric
+            | LineKind::Action
+            | LineKind::SceneHeading
+            | LineKind::Transition
+    ) {
+        return None;
+    }
+
+    let marker_len = forced_marker_len(&parsed_line.raw);
+    let dual_cue = (parsed_line.kind == LineKind::Character
+        && parsed_line.dual_dialogue == Some(DualDialogue::Right))
+    .then(|| dual_dialogue_cue_text(&parsed_line.raw))
+    .flatten();
+    // The number itself is drawn at the right edge of the row.
+    let heading_end = (parsed_line.kind == LineKind::SceneHeading)
+        .then(|| scene_number(&parsed_line.raw))
+        .flatten()
+        .map(|(column, _)| column);
+    if marker_len == 0 && dual_cue.is_none() && heading_end.is_none() {
+        return None;
+    }
+
+    let visible = dual_cue.unwrap_or(&parsed_line.raw);
+    let rendered = visible
+        .chars()
+        .take(heading_end.unwrap_or(usize::MAX))
+        .skip(marker_len)
+        .collect::<String>();
+    Some((marker_len, rendered, None))
+}
+
+// Inline `*italic*`, `**bold**` and `_underline_` only style screenplay text.
+fn fountain_line_has_emphasis(kind: &LineKind) -> bool {
+    matches!(
+        kind,
+        LineKind::SceneHeading
+            | LineKind::Action
+            | LineKind::Character
+            | LineKind::Dialogue
+            | LineKind::Parenthetical
+            | LineKind::Lyric
+            | LineKind::Transition
+            | LineKind::Centered
+            | LineKind::TitlePage
+    )
+}
+
+// Screenplay elements take their color from `element_colors`, indexed like
+// `PROCESSED_KIND_TOGGLES`.
+fn fountain_line_style(kind: &LineKind, element_colors: &[Color; 6]) -> Option<LineRenderStyle> {
+    let style = |font, color| Some(LineRenderStyle::new(font, color, 1.0, 1.0));
     match kind {
-        LineKind::SceneHeading => Some(LineRenderStyle::new(FontVariant::Bold, COLOR_SCENE, 1.0, 1.0)),
-        LineKind::Action => Some(LineRenderStyle::new(FontVariant::Regular, COLOR_ACTION, 1.0, 1.0)),
-        LineKind::Character => Some(LineRenderStyle::new(
-            FontVariant::Bold,
-            COLOR_CHARACTER,
-            1.0,
-            1.0,
-        )),
-        LineKind::Dialogue => Some(LineRenderStyle::new(
-            FontVariant::Regular,
-            COLOR_DIALOGUE,
-            1.0,
-            1.0,
-        )),
-        LineKind::Parenthetical => Some(LineRenderStyle::new(
-            FontVariant::Italic,
-            COLOR_PARENTHETICAL,
-            1.0,
-            1.0,
-        )),
-        LineKind::Transition => Some(LineRenderStyle::new(
-            FontVariant::BoldItalic,
-            COLOR_TRANSITION,
-            1.0,
-            1.0,
-        )),
+        LineKind::SceneHeading => style(FontVariant::Bold, element_colors[0]),
+        LineKind::Action => style(FontVariant::Regular, element_colors[1]),
+        LineKind::Character => style(FontVariant::Bold, element_colors[2]),
+        LineKind::Dialogue => style(FontVariant::Regular, element_colors[3]),
+        LineKind::Parenthetical => style(FontVariant::Italic, element_colors[4]),
+        LineKind::Lyric => style(FontVariant::Italic, element_colors[3]),
+        LineKind::Transition => style(FontVariant::BoldItalic, element_colors[5]),
+        LineKind::Section => style(FontVariant::Bold, COLOR_SECTION),
+        LineKind::Synopsis => style(FontVariant::Italic, COLOR_SYNOPSIS),
+        LineKind::Centered | LineKind::TitlePage => {
+            style(FontVariant::Regular, element_colors[1])
+        }
         _ => None,
     }
 }
@@ -53,6 +53,7 @@ fn render_editor(
             &mut TextFont,
             &mut LineHeight,
             &mut TextColor,
+            &mut UnderlineColor,
         ),
         Without<PanelText>,
     >,
@@ -107,6 +108,27 @@ fn render_editor(
         ),
     >,
     mut status_query: Query<&mut Text, (With<StatusText>, Without<PanelText>, Without<PanelCaret>)>,
+    mut kind_label_query: Query<
+        (
+            &ProcessedKindLabel,
+            &mut Text,
+            &mut TextFont,
+            &mut LineHeight,
+            &mut Node,
+            &mut Visibility,
+        ),
+        (
+            Without<PanelText>,
+            Without<PanelPaper>,
+            Without<PanelCaret>,
+            Without<PanelCanvas>,
+            Without<PanelSelectionRect>,
+            Without<StatusText>,
+            Without<ProcessedPaperText>,
+            Without<ProcessedPaperLineSpan>,
+            Without<ProcessedChecklistIcon>,
+        ),
+    >,
     fonts: Res<EditorFonts>,
     checklist_icons: Res<ChecklistIcons>,
     mut state: ResMut<EditorState>,
@@ -114,7 +136,6 @@ fn render_editor(
     let plain_font_size = scaled_font_size(&state);
     let plain_line_height = state.measured_line_step.max(1.0);
     let plain_char_width = scaled_char_width(&state).max(1.0);
-    let plain_origin_y = scaled_text_padding_y(&state);
     let processed_font_size = scaled_font_size(&state);
     let processed_line_height = scaled_line_height(&state).max(1.0);
 
@@ -135,8 +156,8 @@ fn render_editor(
             }
         }
     }
+    state.plain_wrap_columns = plain_wrap_columns(&state, plain_panel_size);
     state.clamp_horizontal_scrolls(plain_panel_size, processed_panel_size);
-    let plain_origin_x = scaled_text_padding_x(&state) - state.plain_horizontal_scroll;
     let processed_layout_info =
         processed_page_layout(processed_panel_size.unwrap_or(Vec2::ZERO), &state);
     let processed_geometry = processed_layout_info.geometry;
@@ -149,12 +170,19 @@ fn render_editor(
         &body_query,
         state.display_mode,
         state.measured_line_step,
-        plain_origin_y,
+        scaled_text_padding_y(&state),
     );
     state.clamp_scroll(visible_lines);
     state.clamp_processed_top_line();
+    state.follow_caret_in_plain_pane(plain_panel_size, visible_lines);
+    let plain_origin_x = scaled_text_padding_x(&state) - state.plain_horizontal_scroll;
+    let plain_origin_y = plain_text_top(&state);
 
-    let plain_lines = visible_plain_lines(&state, visible_lines);
+    let plain_view = join_plain_view(
+        visible_plain_rows(&state, visible_lines)
+            .iter()
+            .map(|row| row.text.as_str()),
+    );
     let processed_view_capacity = processed_page_step_lines
         .saturating_mul(PROCESSED_PAPER_CAPACITY)
         .max(1);
@@ -164,6 +192,7 @@ fn render_editor(
         processed_lines_per_page,
         processed_spacer_lines,
     );
+    sync_processed_top_to_plain(&mut state, &processed_all_lines);
     if processed_all_lines.is_empty() {
         state.processed_top_visual = 0;
     } else {
@@ -178,6 +207,8 @@ fn render_editor(
         processed_view_capacity,
     );
     let first_visible_page = processed_view.start_index / processed_page_step_lines;
+    state.processed_first_visible_page = first_visible_page;
+    state.processed_page_count = processed_all_lines.len().div_ceil(processed_page_step_lines);
     let anchor_line_in_page =
         processed_anchor_line_in_page(&processed_view, processed_page_step_lines);
     let processed_anchor_offset_px =
@@ -280,7 +311,47 @@ fn render_editor(
         *visibility = Visibility::Visible;
     }
 
-    let plain_view = plain_lines.join("\n");
+    for (label, mut text, mut text_font, mut line_height_comp, mut node, mut visibility) in
+        kind_label_query.iter_mut()
+    {
+        if !state.processed_kind_labels || label.slot >= PROCESSED_PAPER_CAPACITY {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let page_start = first_visible_page
+            .saturating_add(label.slot)
+            .saturating_mul(processed_page_step_lines);
+        let mut labels = String::new();
+        for line_offset in 0..processed_lines_per_page {
+            let global_index = page_start.saturating_add(line_offset);
+            if line_offset > 0 {
+                labels.push('\n');
+            }
+            let Some(visual_line) = processed_all_lines.get(global_index) else {
+                continue;
+            };
+            let continues_previous = global_index
+                .checked_sub(1)
+                .and_then(|index| processed_all_lines.get(index))
+                .is_some_and(|previous| {
+                    previous.maps_to_source() && previous.source_line == visual_line.source_line
+                });
+            if !visual_line.maps_to_source() || continues_previous {
+                continue;
+            }
+            if let Some(parsed_line) = state.parsed.get(visual_line.source_line) {
+                labels.push_str(line_kind_margin_label(&parsed_line.kind));
+            }
+        }
+
+        **text = labels;
+        text_font.font_size = processed_font_size * 0.6;
+        *line_height_comp = LineHeight::Px(processed_line_height);
+        node.left = px(checklist_icon_gap);
+        node.top = px(text_top_in_paper);
+        *visibility = Visibility::Visible;
+    }
 
     for (panel_text, mut text, mut text_font, mut line_height_comp, mut node, mut transform) in
         text_query.iter_mut()
@@ -334,10 +405,14 @@ fn render_editor(
 
     let plain_layout = panel_layout_info(&text_layout_query, PanelKind::Plain);
     state.measured_line_step = scaled_line_height(&state);
+    if state.line_step_remeasure_pending {
+        report_remeasured_line_step(&mut state, plain_layout, plain_inverse_scale);
+    }
+    let plain_rows = visible_plain_rows(&state, visible_lines);
     render_selection_rects(
         &mut selection_rect_query,
         &state,
-        &plain_lines,
+        &plain_rows,
         plain_layout,
         plain_inverse_scale,
         plain_origin_x,
@@ -360,8 +435,7 @@ fn render_editor(
     render_panel_carets(
         &mut caret_query,
         &state,
-        visible_lines,
-        &plain_lines,
+        &plain_rows,
         plain_layout,
         plain_inverse_scale,
         plain_origin_x,
@@ -382,6 +456,90 @@ fn render_editor(
     );
 }
 
+// Beyond this the text layout and the row math disagree by a visible amount.
+const LINE_STEP_DESYNC_TOLERANCE_PX: f32 = 0.5;
+
+// Logs the line step the Plain text layout actually produced after the recompute
+// chord. A mismatch with the nominal step is a layout desync and is reported as one.
+fn report_remeasured_line_step(
+    state: &mut EditorState,
+    plain_layout: Option<&TextLayoutInfo>,
+    inverse_scale: f32,
+) {
+    state.line_step_remeasure_pending = false;
+    let nominal = scaled_line_height(state);
+    let Some(measured) = plain_layout.and_then(|layout| layout_line_step(layout, inverse_scale))
+    else {
+        warn!("[debug] Could not measure line step: fewer than two laid out Plain lines");
+        state.status_message = "Recomputed: too few Plain lines to measure".to_string();
+        return;
+    };
+
+    info!("[debug] Measured line step: measured={measured:.2}px nominal={nominal:.2}px");
+    if (measured - nominal).abs() > LINE_STEP_DESYNC_TOLERANCE_PX {
+        warn!("[debug] Line step desync: layout {measured:.2}px, row math {nominal:.2}px");
+    }
+    state.status_message =
+        format!("Recomputed: measured line step {measured:.2}px (nominal {nominal:.2}px)");
+}
+
+fn apply_plain_focus_dimming(
+    mut text_query: Query<(&PanelText, &mut Text, &TextFont, &LineHeight, &mut TextColor)>,
+    mut span_query: Query<
+        (
+            &PlainFocusSpan,
+            &mut TextSpan,
+            &mut TextFont,
+            &mut LineHeight,
+            &mut TextColor,
+        ),
+        Without<PanelText>,
+    >,
+    state: Res<EditorState>,
+) {
+    let Some((_, mut text, text_font, line_height, mut text_color)) = text_query
+        .iter_mut()
+        .find(|(panel_text, ..)| panel_text.kind == PanelKind::Plain)
+    else {
+        return;
+    };
+
+    let plain_text_color = state.palette().plain_text;
+    // Split the plain view at line boundaries so glyph byte offsets stay
+    // line-relative: lines before the scene stay in the root text.
+    let mut parts = [String::new(), String::new()];
+    if let Some(range) = state.focus_scene_range() {
+        let full = std::mem::take(&mut **text);
+        let rows = visible_plain_rows(&state, full.split_inclusive('\n').count());
+        for (row, line) in rows.iter().zip(full.split_inclusive('\n')) {
+            let line_index = row.line;
+            if line_index < range.start {
+                text.push_str(line);
+            } else if line_index < range.end {
+                parts[0].push_str(line);
+            } else {
+                parts[1].push_str(line);
+            }
+        }
+        text_color.0 = dimmed_focus_color(plain_text_color);
+    } else {
+        text_color.0 = plain_text_color;
+    }
+
+    for (span, mut text_span, mut span_font, mut span_line_height, mut span_color) in
+        span_query.iter_mut()
+    {
+        **text_span = std::mem::take(&mut parts[span.part.min(1)]);
+        *span_font = text_font.clone();
+        *span_line_height = *line_height;
+        span_color.0 = if span.part == 0 {
+            plain_text_color
+        } else {
+            dimmed_focus_color(plain_text_color)
+        };
+    }
+}
+
 fn viewport_lines(
     body_query: &Query<(&PanelBody, &ComputedNode)>,
     display_mode: DisplayMode,
@@ -430,20 +588,88 @@ fn viewport_lines_from_panels(
     (usable_height / step).floor().max(1.0) as usize
 }
 
-fn visible_plain_lines(state: &EditorState, visible_lines: usize) -> Vec<String> {
-    let last = state
-        .top_line
-        .saturating_add(visible_lines)
-        .min(state.document.line_count());
+// One displayed row of the Plain pane. Unwrapped lines are a single row starting
+// at column 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PlainRow {
+    line: usize,
+    start_column: usize,
+    text: String,
+}
 
-    state
-        .document
-        .lines()
-        .iter()
-        .skip(state.top_line)
-        .take(last.saturating_sub(state.top_line))
-        .cloned()
-        .collect()
+impl PlainRow {
+    fn end_column(&self) -> usize {
+        self.start_column + self.text.chars().count()
+    }
+}
+
+// The rows from `top_line` down, at most `visible_lines` of them plus one more
+// while a smooth scroll leaves the pane between lines.
+fn visible_plain_rows(state: &EditorState, visible_lines: usize) -> Vec<PlainRow> {
+    let visible_lines = visible_lines + usize::from(state.plain_scroll_fraction > 0.0);
+    let mut rows = Vec::with_capacity(visible_lines);
+    for (line, text) in state.document.lines().iter().enumerate().skip(state.top_line) {
+        if rows.len() >= visible_lines {
+            break;
+        }
+        match state.plain_wrap_columns.filter(|_| state.plain_line_wraps(line)) {
+            Some(columns) => rows.extend(plain_wrap_rows(line, text, columns)),
+            None => rows.push(PlainRow {
+                line,
+                start_column: 0,
+                text: text.clone(),
+            }),
+        }
+    }
+    rows.truncate(visible_lines);
+    rows
+}
+
+// Breaks after the last space that fits, or mid-word when no space does. Rows keep
+// their trailing space so the columns of a line run on without gaps.
+fn plain_wrap_rows(line: usize, text: &str, columns: usize) -> Vec<PlainRow> {
+    let columns = columns.max(1);
+    let chars = text.char_indices().collect::<Vec<_>>();
+    let mut rows = Vec::new();
+    let mut start = 0;
+    while chars.len() - start > columns {
+        let limit = start + columns;
+        let end = (start + 1..=limit)
+            .rev()
+            .find(|index| chars[index - 1].1 == ' ')
+            .unwrap_or(limit);
+        rows.push(PlainRow {
+            line,
+            start_column: start,
+            text: text[chars[start].0..chars[end].0].to_string(),
+        });
+        start = end;
+    }
+    rows.push(PlainRow {
+        line,
+        start_column: start,
+        text: chars
+            .get(start)
+            .map_or(String::new(), |(byte, _)| text[*byte..].to_string()),
+    });
+    rows
+}
+
+// The row showing `position`; at a wrap point that is the row the next text starts.
+fn plain_row_index(rows: &[PlainRow], position: Position) -> Option<usize> {
+    rows.iter()
+        .rposition(|row| row.line == position.line && row.start_column <= position.column)
+}
+
+fn join_plain_view<'a>(lines: impl Iterator<Item = &'a str>) -> String {
+    let mut view = String::new();
+    for (index, line) in lines.enumerate() {
+        if index > 0 {
+            view.push('\n');
+        }
+        view.push_str(line);
+    }
+    view
 }
 
 #[derive(Clone, Debug)]
@@ -451,6 +677,7 @@ struct ProcessedVisualFragment {
     text: String,
     is_link: bool,
     link_target: Option<String>,
+    emphasis: Emphasis,
 }
 
 #[derive(Clone, Debug)]
@@ -464,6 +691,275 @@ struct ProcessedVisualLine {
     markdown_checklist_checked: Option<bool>,
     render_override: Option<ProcessedLineRenderOverride>,
     is_spacer: bool,
+    // `(MORE)` and `(CONT'D)` rows: drawn, but never hold the caret.
+    is_synthetic: bool,
+}
+
+impl ProcessedVisualLine {
+    fn maps_to_source(&self) -> bool {
+        !self.is_spacer && !self.is_synthetic
+    }
+}
+
+const RECLASSIFY_PREVIEW_MAX_ROWS: usize = 24;
+const CUE_RULER_HEIGHT: f32 = 16.0;
+const CUE_RULER_MARKS: [(LineKind, &str); 5] = [
+    (LineKind::SceneHeading, "Scene"),
+    (LineKind::Dialogue, "Dialogue"),
+    (LineKind::Parenthetical, "Paren"),
+    (LineKind::Character, "Character"),
+    (LineKind::Transition, "Transition"),
+];
+
+// Ticks follow the same indent table and char width as processed layout, so zoom,
+// margins and page size changes move them with the text.
+fn sync_processed_cue_ruler(
+    state: Res<EditorState>,
+    body_query: Query<(&PanelBody, &ComputedNode)>,
+    mut ruler_query: Query<&mut Node, (With<ProcessedCueRuler>, Without<ProcessedCueRulerTick>)>,
+    mut tick_query: Query<(&ProcessedCueRulerTick, &mut Node), Without<ProcessedCueRuler>>,
+) {
+    let Ok(mut ruler) = ruler_query.single_mut() else {
+        return;
+    };
+
+    let visible =
+        state.processed_cue_ruler && state.document_format == DocumentFormat::Fountain;
+    ruler.display = if visible { Display::Flex } else { Display::None };
+    if !visible {
+        return;
+    }
+
+    let Some(panel_size) = body_query
+        .iter()
+        .find(|(panel, _)| panel.kind == PanelKind::Processed)
+        .map(|(_, computed)| computed.size() * computed.inverse_scale_factor())
+    else {
+        return;
+    };
+
+    let geometry = processed_page_geometry(panel_size, &state);
+    let text_left = geometry.text_left - state.processed_horizontal_scroll;
+    let char_width = scaled_char_width(&state);
+    for (tick, mut node) in tick_query.iter_mut() {
+        let offset = state.indent_widths.for_kind(&tick.kind) as f32 * char_width;
+        node.left = px(if state.processed_right_to_left {
+            text_left + geometry.text_width - offset
+        } else {
+            text_left + offset
+        });
+    }
+}
+
+// Printed scripts number every page but the first at the top right, in the
+// header margin above the text.
+fn sync_processed_page_numbers(
+    state: Res<EditorState>,
+    body_query: Query<(&PanelBody, &ComputedNode)>,
+    mut number_query: Query<(&ProcessedPageNumber, &mut Text, &mut TextFont, &mut Node)>,
+) {
+    let Some(panel_size) = body_query
+        .iter()
+        .find(|(panel, _)| panel.kind == PanelKind::Processed)
+        .map(|(_, computed)| computed.size() * computed.inverse_scale_factor())
+    else {
+        return;
+    };
+
+    let geometry = processed_page_geometry(panel_size, &state);
+    let line_height = scaled_line_height(&state);
+    let right =
+        geometry.paper_left + geometry.paper_width - geometry.text_left - geometry.text_width;
+    let top = ((geometry.text_top - geometry.paper_top - line_height) * 0.5).max(0.0);
+    for (number, mut text, mut text_font, mut node) in number_query.iter_mut() {
+        let page = state.processed_first_visible_page + number.slot + 1;
+        let shown = state.document_format == DocumentFormat::Fountain
+            && page > 1
+            && page <= state.processed_page_count;
+        node.display = if shown { Display::Flex } else { Display::None };
+        if !shown {
+            continue;
+        }
+
+        let label = format!("{page}.");
+        if text.0 != label {
+            text.0 = label;
+        }
+        text_font.font_size = scaled_font_size(&state);
+        node.right = px(right);
+        node.top = px(top);
+    }
+}
+
+// Drawn whether or not soft wrap is on; it only marks where a line would wrap.
+fn sync_plain_wrap_guide(
+    state: Res<EditorState>,
+    mut guide_query: Query<(&mut Node, &mut BackgroundColor), With<PlainWrapGuide>>,
+) {
+    let Ok((mut node, mut color)) = guide_query.single_mut() else {
+        return;
+    };
+
+    let visible = state.show_wrap_guide && state.panel_visible(PanelKind::Plain);
+    node.display = if visible { Display::Flex } else { Display::None };
+    if !visible {
+        return;
+    }
+
+    let plain_origin_x = scaled_text_padding_x(&state) - state.plain_horizontal_scroll;
+    node.left = px(plain_origin_x + state.wrap_guide_column as f32 * scaled_char_width(&state));
+    color.0 = state.wrap_guide_color;
+}
+
+fn sync_reclassify_preview_overlay(
+    state: Res<EditorState>,
+    mut overlay_query: Query<&mut Node, With<ReclassifyPreviewOverlay>>,
+    mut text_query: Query<&mut Text, With<ReclassifyPreviewText>>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+
+    let Ok(mut overlay) = overlay_query.single_mut() else {
+        return;
+    };
+    let Some(edits) = state.reclassify_preview.as_ref() else {
+        overlay.display = Display::None;
+        return;
+    };
+
+    overlay.display = Display::Flex;
+    if let Ok(mut text) = text_query.single_mut() {
+        text.0 = reclassify_preview_text(edits);
+    }
+}
+
+fn sync_find_bar_overlay(
+    state: Res<EditorState>,
+    mut overlay_query: Query<&mut Node, With<FindBarOverlay>>,
+    mut text_query: Query<&mut Text, With<FindBarText>>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+
+    let Ok(mut overlay) = overlay_query.single_mut() else {
+        return;
+    };
+    let Some(find_bar) = state.find_bar.as_ref() else {
+        overlay.display = Display::None;
+        return;
+    };
+
+    overlay.display = Display::Flex;
+    if let Ok(mut text) = text_query.single_mut() {
+        text.0 = find_bar_text(find_bar);
+    }
+}
+
+// Recomputed from the parse whenever the state changes while the panel is open.
+fn sync_stats_overlay(
+    state: Res<EditorState>,
+    mut overlay_query: Query<&mut Node, With<StatsOverlay>>,
+    mut text_query: Query<&mut Text, With<StatsText>>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+
+    let Ok(mut overlay) = overlay_query.single_mut() else {
+        return;
+    };
+    if !state.stats_panel_open {
+        overlay.display = Display::None;
+        return;
+    }
+
+    overlay.display = Display::Flex;
+    if let Ok(mut text) = text_query.single_mut() {
+        text.0 = stats_panel_text(&document_stats(&state.parsed));
+    }
+}
+
+fn stats_panel_text(stats: &ScriptStats) -> String {
+    let mut rows = vec![
+        "Statistics".to_owned(),
+        format!("Scenes: {}", stats.scene_count),
+        format!("Characters: {}", stats.character_count()),
+    ];
+    for (name, count) in stats.characters_by_lines() {
+        let noun = if count == 1 { "line" } else { "lines" };
+        rows.push(format!("  {name}: {count} {noun}"));
+    }
+    rows.join("\n")
+}
+
+fn find_bar_text(find_bar: &FindBar) -> String {
+    let count = match (find_bar.matches.len(), find_bar.current) {
+        _ if find_bar.query.is_empty() => "type to search".to_owned(),
+        (0, _) => "no matches".to_owned(),
+        (total, Some(current)) => format!("{} of {total}", current + 1),
+        (total, None) => format!("{total} matches"),
+    };
+    let (query_caret, replacement_caret) = if find_bar.editing_replacement {
+        ("", "|")
+    } else {
+        ("|", "")
+    };
+    let scope = if find_bar.in_selection {
+        "in selection"
+    } else {
+        "whole script"
+    };
+    format!(
+        "Find: {}{query_caret}   {count}   [{scope}]\nReplace: {}{replacement_caret}\n\
+         Enter next, Shift+Enter previous, Tab switches field, Alt+L selection only, \
+         Cmd/Ctrl+Enter replaces all, Esc closes",
+        find_bar.query, find_bar.replacement
+    )
+}
+
+fn reclassify_preview_text(edits: &[MarkerEdit]) -> String {
+    let mut rows = vec![format!("Forced markers ({}), Enter applies, Esc cancels", edits.len())];
+    rows.extend(edits.iter().take(RECLASSIFY_PREVIEW_MAX_ROWS).map(|edit| {
+        format!(
+            "{:>5}  {}  {} -> {}",
+            edit.line + 1,
+            edit.after.trim(),
+            line_kind_margin_label(&edit.kind_before),
+            line_kind_margin_label(&edit.kind_after),
+        )
+    }));
+    if edits.len() > RECLASSIFY_PREVIEW_MAX_ROWS {
+        rows.push(format!("... and {} more", edits.len() - RECLASSIFY_PREVIEW_MAX_ROWS));
+    }
+    rows.join("\n")
+}
+
+fn line_kind_margin_label(kind: &LineKind) -> &'static str {
+    match kind {
+        LineKind::Empty => "",
+        LineKind::SceneHeading => "SCENE",
+        LineKind::Action => "ACTION",
+        LineKind::Character => "CHARACTER",
+        LineKind::Dialogue => "DIALOGUE",
+        LineKind::Parenthetical => "PAREN",
+        LineKind::Transition => "TRANSITION",
+        LineKind::Centered => "CENTERED",
+        LineKind::Lyric => "LYRIC",
+        LineKind::TitlePage => "TITLE",
+        LineKind::PageBreak => "PAGE BREAK",
+        LineKind::Boneyard => "BONEYARD",
+        LineKind::Section => "SECTION",
+        LineKind::Synopsis => "SYNOPSIS",
+        LineKind::MarkdownHeading => "HEADING",
+        LineKind::MarkdownListItem => "LIST",
+        LineKind::MarkdownQuote => "QUOTE",
+        LineKind::MarkdownCodeFence => "FENCE",
+        LineKind::MarkdownCode => "CODE",
+        LineKind::MarkdownRule => "RULE",
+        LineKind::MarkdownParagraph => "TEXT",
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -534,15 +1030,25 @@ fn default_line_render_style() -> LineRenderStyle {
     LineRenderStyle::new(FontVariant::Regular, COLOR_ACTION, 1.0, 1.0)
 }
 
-fn processed_line_style(parsed_line: &ParsedLine) -> LineRenderStyle {
-    processed_line_style_for_kind(&parsed_line.kind, parsed_line.markdown_heading_level)
+// Notes are hidden in the processed pane, so the line they sit on is tinted instead.
+fn processed_line_style(parsed_line: &ParsedLine, element_colors: &[Color; 6]) -> LineRenderStyle {
+    let mut style = processed_line_style_for_kind(
+        &parsed_line.kind,
+        parsed_line.markdown_heading_level,
+        element_colors,
+    );
+    if parsed_line.has_note() {
+        style.color = COLOR_NOTED_LINE;
+    }
+    style
 }
 
 fn processed_line_style_for_kind(
     kind: &LineKind,
     markdown_heading_level: Option<u8>,
+    element_colors: &[Color; 6],
 ) -> LineRenderStyle {
-    fountain_line_style(kind)
+    fountain_line_style(kind, element_colors)
         .or_else(|| markdown_line_style(kind, markdown_heading_level))
         .unwrap_or_else(default_line_render_style)
 }
@@ -552,13 +1058,19 @@ fn font_variant_for_processed_fragment(
     fragment: &ProcessedVisualFragment,
     format: DocumentFormat,
 ) -> FontVariant {
-    if !fragment.is_link || format != DocumentFormat::Fountain {
+    if format != DocumentFormat::Fountain {
         return base;
     }
 
+    let bold = fragment.is_link || fragment.emphasis.bold;
+    let italic = fragment.emphasis.italic;
     match base {
-        FontVariant::Italic | FontVariant::BoldItalic => FontVariant::BoldItalic,
-        FontVariant::Regular | FontVariant::Bold => FontVariant::Bold,
+        FontVariant::Bold if italic => FontVariant::BoldItalic,
+        FontVariant::Italic if bold => FontVariant::BoldItalic,
+        FontVariant::Regular if bold && italic => FontVariant::BoldItalic,
+        FontVariant::Regular if bold => FontVariant::Bold,
+        FontVariant::Regular if italic => FontVariant::Italic,
+        base => base,
     }
 }
 
@@ -582,3 +1094,23 @@ fn font_for_variant_with_format(
         },
     }
 }
+
+#[cfg(test)]
+mod rendering_tests {
+    use super::*;
+
+    #[test]
+    fn plain_wrap_rows_break_after_spaces_and_split_long_words() {
+        let rows = plain_wrap_rows(4, "He waits by the door.", 10);
+        let texts = rows.iter().map(|row| row.text.as_str()).collect::<Vec<_>>();
+        assert_eq!(texts, ["He waits ", "by the ", "door."]);
+        assert_eq!(rows.iter().map(|row| row.start_column).collect::<Vec<_>>(), [0, 9, 16]);
+        assert!(rows.iter().all(|row| row.line == 4));
+
+        let rows = plain_wrap_rows(0, "abcdefgh", 3);
+        let texts = rows.iter().map(|row| row.text.as_str()).collect::<Vec<_>>();
+        assert_eq!(texts, ["abc", "def", "gh"]);
+        assert_eq!(plain_row_index(&rows, Position { line: 0, column: 3 }), Some(1));
+        assert_eq!(plain_wrap_rows(0, "", 3).len(), 1);
+    }
+}
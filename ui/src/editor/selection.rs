@@ -3,8 +3,21 @@ struct MouseSelectionState {
     active: bool,
     extend_from_existing: bool,
     dragged: bool,
+    // Seconds since startup and where the last press landed, for double and
+    // triple clicks.
+    last_click: Option<(f64, Position)>,
+    click_count: u8,
+    // Fraction of a line the drag auto-scroll has travelled but not yet moved.
+    autoscroll_carry_lines: f32,
 }
 
+const MULTI_CLICK_INTERVAL_SECS: f64 = 0.4;
+// Dragging past an edge scrolls at the minimum rate right at the edge and speeds
+// up with distance, reaching the maximum this many lines out.
+const DRAG_AUTOSCROLL_MIN_LINES_PER_SEC: f32 = 4.0;
+const DRAG_AUTOSCROLL_MAX_LINES_PER_SEC: f32 = 48.0;
+const DRAG_AUTOSCROLL_GAIN_LINES: f32 = 6.0;
+
 fn setup_selection_rects(
     mut commands: Commands,
     selection_layer_query: Query<(Entity, &PanelSelectionLayer)>,
@@ -35,8 +48,10 @@ fn setup_selection_rects(
 fn handle_mouse_selection(
     mouse_buttons: Res<ButtonInput<MouseButton>>,
     keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
     mut middle_autoscroll: ResMut<MiddleAutoscrollState>,
     mut splitter_drag: ResMut<PanelSplitterDragState>,
+    scrollbar_drag: Res<ScrollbarDragState>,
     mut mouse_selection: ResMut<MouseSelectionState>,
     panel_query: Query<(&PanelBody, &RelativeCursorPosition, &ComputedNode)>,
     text_layout_query: Query<(&PanelText, &TextLayoutInfo)>,
@@ -53,6 +68,10 @@ fn handle_mouse_selection(
         mouse_selection.active = false;
         return;
     }
+    if scrollbar_drag.active.is_some() {
+        mouse_selection.active = false;
+        return;
+    }
 
     if middle_autoscroll.suppress_next_left_click && !mouse_buttons.pressed(MouseButton::Left) {
         middle_autoscroll.suppress_next_left_click = false;
@@ -109,12 +128,12 @@ fn handle_mouse_selection(
     let processed_lines_per_page = processed_layout_info.map_or(40, |layout| layout.lines_per_page);
     let processed_spacer_lines = processed_layout_info.map_or(2, |layout| layout.spacer_lines);
     let processed_step_lines = processed_layout_info
-        .map_or(processed_page_step_lines(), |layout| layout.page_step_lines)
+        .map_or(processed_page_step_lines(state.page_size), |layout| layout.page_step_lines)
         .max(1);
     let processed_view_capacity = processed_step_lines
         .saturating_mul(PROCESSED_PAPER_CAPACITY)
         .max(1);
-    let plain_lines = visible_plain_lines(&state, visible_lines);
+    let plain_rows = visible_plain_rows(&state, visible_lines);
     let processed_all_lines = processed_display_lines(
         &mut state,
         processed_wrap_columns,
@@ -141,7 +160,7 @@ fn handle_mouse_selection(
     let plain_char_width = scaled_char_width(&state).max(1.0);
     let processed_char_width = scaled_char_width(&state).max(1.0);
     let plain_origin_x = scaled_text_padding_x(&state) - state.plain_horizontal_scroll;
-    let plain_origin_y = scaled_text_padding_y(&state);
+    let plain_origin_y = plain_text_top(&state);
     let anchor_line_in_page = processed_anchor_line_in_page(&processed_view, processed_step_lines);
     let processed_anchor_offset_px =
         processed_anchor_scroll_offset_px(anchor_line_in_page, processed_line_height);
@@ -256,10 +275,18 @@ fn handle_mouse_selection(
                     (line_in_page, display_column)
                 });
 
-            let global_index = page_index
+            let unclamped_index = page_index
                 .saturating_mul(processed_step_lines)
-                .saturating_add(line_in_page)
-                .min(processed_all_lines.len().saturating_sub(1));
+                .saturating_add(line_in_page);
+            if let Some(position) = document_end_past_last_row(
+                &state.document,
+                unclamped_index,
+                processed_all_lines.len(),
+            ) {
+                hit = Some((PanelKind::Processed, position));
+                break;
+            }
+            let global_index = unclamped_index.min(processed_all_lines.len().saturating_sub(1));
             let Some(global_index) =
                 nearest_non_spacer_visual_index(&processed_all_lines, global_index)
             else {
@@ -278,7 +305,9 @@ fn handle_mouse_selection(
 
         let local_x = (panel_x - plain_origin_x).max(0.0);
         let local_y = (panel_y - plain_origin_y).max(0.0);
-        let panel_line_count = plain_lines.len().max(1);
+        // Rows are resolved against the whole viewport, not just the rendered lines, so
+        // a click in the blank area under a short document is recognised as such.
+        let panel_line_count = visible_lines.max(plain_rows.len()).max(1);
         let line_offset = plain_layout
             .and_then(|layout| {
                 line_index_from_layout_y(layout, local_y, panel_line_count, inverse_scale)
@@ -287,14 +316,14 @@ fn handle_mouse_selection(
                 ((local_y / plain_line_height).floor().max(0.0) as usize)
                     .min(panel_line_count.saturating_sub(1))
             });
-        let line = state
-            .top_line
-            .saturating_add(line_offset)
-            .min(state.document.line_count().saturating_sub(1));
-        let visible_offset = line.saturating_sub(state.top_line);
-        let display_line = plain_lines
-            .get(visible_offset)
-            .map_or("", |line| line.as_str());
+        let Some(row) = plain_rows.get(line_offset) else {
+            hit = document_end_past_last_row(&state.document, line_offset, plain_rows.len())
+                .map(|position| (PanelKind::Plain, position));
+            break;
+        };
+        let line = row.line;
+        let visible_offset = line_offset;
+        let display_line = row.text.as_str();
         let raw_column = plain_layout
             .and_then(|layout| {
                 column_from_layout_x(
@@ -308,28 +337,117 @@ fn handle_mouse_selection(
             })
             .unwrap_or_else(|| (local_x / plain_char_width).round().max(0.0) as usize);
 
+        // Past the end of a wrapped row lands before its trailing space, so the caret
+        // stays on the clicked row.
+        let row_len = display_line.chars().count();
+        let wraps_on = plain_rows
+            .get(visible_offset + 1)
+            .is_some_and(|next| next.line == line);
+        let local_column = if wraps_on {
+            raw_column.min(row_len.saturating_sub(1))
+        } else {
+            raw_column
+        };
         let max_col = state.document.line_len_chars(line);
-        let column = raw_column.min(max_col);
+        let column = (row.start_column + local_column).min(max_col);
         hit = Some((PanelKind::Plain, Position { line, column }));
         break;
     }
 
+    // Dragging past the top or bottom edge keeps extending the selection at a rate
+    // set by how far out the pointer is, and the follow-scroll below brings each new
+    // line into view.
+    if hit.is_none()
+        && is_drag_update
+        && let Some(overshoot_px) = drag_autoscroll_overshoot(&panel_query, state.focused_panel)
+    {
+        let overshoot_lines = overshoot_px / state.measured_line_step.max(1.0);
+        let lines_per_sec = overshoot_lines.signum() * DRAG_AUTOSCROLL_MIN_LINES_PER_SEC
+            + autoscroll_axis_speed(
+                overshoot_lines,
+                0.0,
+                DRAG_AUTOSCROLL_GAIN_LINES,
+                MIDDLE_AUTOSCROLL_ACCEL_EXPONENT,
+                DRAG_AUTOSCROLL_MAX_LINES_PER_SEC - DRAG_AUTOSCROLL_MIN_LINES_PER_SEC,
+            );
+        mouse_selection.autoscroll_carry_lines += lines_per_sec * time.delta_secs();
+        let whole_lines = mouse_selection.autoscroll_carry_lines.trunc();
+        mouse_selection.autoscroll_carry_lines -= whole_lines;
+
+        let caret = state.cursor.position;
+        let preferred_column = state.cursor.preferred_column;
+        let mut position = caret;
+        for _ in 0..whole_lines.abs() as usize {
+            position = if whole_lines > 0.0 {
+                state.document.move_down(position, preferred_column)
+            } else {
+                state.document.move_up(position, preferred_column)
+            };
+        }
+        if position != caret {
+            state.set_cursor_with_selection(position, false, true);
+            mouse_selection.dragged = true;
+            apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+        }
+        return;
+    }
+    mouse_selection.autoscroll_carry_lines = 0.0;
+
     let Some((_panel, position)) = hit else {
         return;
     };
 
+    // Alt+click, or Cmd+click where Ctrl is not taken by drag scrolling, adds a caret.
+    if is_start && (alt_modifier_pressed(&keys) || shortcut_modifier_pressed(&keys)) {
+        mouse_selection.active = false;
+        if state.add_secondary_cursor(position) {
+            state.status_message = format!("{} carets.", state.secondary_cursors.len() + 1);
+        }
+        return;
+    }
+
     if consume_script_link_click(&mut state, &mut mouse_selection, &keys, is_start, position) {
         return;
     }
 
     if is_start {
+        state.secondary_cursors.clear();
         let extend_selection = shift_modifier_pressed(&keys);
         mouse_selection.active = true;
         mouse_selection.extend_from_existing = extend_selection;
         mouse_selection.dragged = false;
 
+        let now = time.elapsed_secs_f64();
+        let repeated = mouse_selection.last_click.is_some_and(|(at, last)| {
+            now - at <= MULTI_CLICK_INTERVAL_SECS
+                && last.line == position.line
+                && last.column.abs_diff(position.column) <= 1
+        });
+        mouse_selection.click_count = if repeated {
+            mouse_selection.click_count % 3 + 1
+        } else {
+            1
+        };
+        mouse_selection.last_click = Some((now, position));
+
         if extend_selection {
             state.set_cursor_with_selection(position, true, true);
+        } else if mouse_selection.click_count > 1 {
+            // Word and line selections are final; a drag after them does not extend.
+            let range = if mouse_selection.click_count == 2 {
+                state.document.word_range_at(position)
+            } else {
+                Position {
+                    line: position.line,
+                    column: 0,
+                }..Position {
+                    line: position.line,
+                    column: state.document.line_len_chars(position.line),
+                }
+            };
+            state.set_cursor(range.start, true);
+            state.set_cursor_with_selection(range.end, true, true);
+            mouse_selection.active = false;
         } else {
             state.set_cursor(position, true);
             state.selection_anchor = Some(position);
@@ -345,6 +463,38 @@ fn handle_mouse_selection(
     apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
 }
 
+// Some(true) below the panel, Some(false) above it, None while the pointer is level
+// with it.
+// How far past the bottom (positive) or top (negative) edge of the panel the
+// pointer is, in logical pixels; None while it is inside the panel vertically.
+fn drag_autoscroll_overshoot(
+    panel_query: &Query<(&PanelBody, &RelativeCursorPosition, &ComputedNode)>,
+    kind: PanelKind,
+) -> Option<f32> {
+    let (_, relative_cursor, computed) =
+        panel_query.iter().find(|(panel, _, _)| panel.kind == kind)?;
+    let normalized = relative_cursor.normalized?;
+    let height = computed.size().y * computed.inverse_scale_factor();
+    let past_edge = normalized.y.abs() - 0.5;
+    (past_edge > 0.0).then(|| normalized.y.signum() * past_edge * height)
+}
+
+fn document_end_past_last_row(
+    document: &Document,
+    row: usize,
+    row_count: usize,
+) -> Option<Position> {
+    if row < row_count {
+        return None;
+    }
+
+    let line = document.line_count().saturating_sub(1);
+    Some(Position {
+        line,
+        column: document.line_len_chars(line),
+    })
+}
+
 fn sync_hovered_processed_link(
     panel_query: Query<(&PanelBody, &RelativeCursorPosition, &ComputedNode)>,
     processed_text_layout_query: Query<
@@ -537,7 +687,7 @@ fn render_selection_rects(
         ),
     >,
     state: &EditorState,
-    plain_lines: &[String],
+    plain_rows: &[PlainRow],
     plain_layout: Option<&TextLayoutInfo>,
     plain_inverse_scale: f32,
     plain_origin_x: f32,
@@ -559,12 +709,86 @@ fn render_selection_rects(
     processed_char_width: f32,
     processed_line_height: f32,
 ) {
-    let mut plain_rects = Vec::<(f32, f32, f32, f32)>::new();
+    let mut plain_rects = Vec::<(f32, f32, f32, f32, Color)>::new();
     let mut processed_rects = Vec::<(f32, f32, f32, f32)>::new();
+    let visible_first_line = state.top_line;
+    let visible_last_line = plain_rows.last().map_or(visible_first_line, |row| row.line);
+    let plain_rect = |visible_offset: usize, line_start: usize, line_end: usize| {
+        let display_line = plain_rows.get(visible_offset)?.text.as_str();
+        let display_len = display_line.chars().count();
+        let start_byte = char_to_byte_index(display_line, line_start.min(display_len));
+        let end_byte = char_to_byte_index(display_line, line_end.min(display_len));
+        let left_x = plain_layout
+            .and_then(|layout| {
+                caret_x_from_layout(
+                    layout,
+                    visible_offset,
+                    display_line,
+                    start_byte,
+                    plain_inverse_scale,
+                    plain_char_width,
+                )
+            })
+            .unwrap_or(line_start as f32 * plain_char_width);
+        let right_x = plain_layout
+            .and_then(|layout| {
+                caret_x_from_layout(
+                    layout,
+                    visible_offset,
+                    display_line,
+                    end_byte,
+                    plain_inverse_scale,
+                    plain_char_width,
+                )
+            })
+            .unwrap_or(line_end as f32 * plain_char_width);
+        let line_top = plain_layout
+            .and_then(|layout| line_top_from_layout(layout, visible_offset, plain_inverse_scale))
+            .unwrap_or(visible_offset as f32 * plain_line_height);
+
+        Some((
+            plain_origin_x + left_x.min(right_x),
+            plain_origin_y + line_top,
+            (right_x - left_x).abs().max(1.0),
+            plain_line_height.max(1.0),
+        ))
+    };
+    // A wrapped line gets one rect per row its column span crosses.
+    let plain_line_rects = |line: usize, line_start: usize, line_end: usize| {
+        plain_rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.line == line)
+            .filter_map(|(visible_offset, row)| {
+                let start = line_start.max(row.start_column);
+                let end = line_end.min(row.end_column());
+                if start >= end {
+                    return None;
+                }
+                plain_rect(visible_offset, start - row.start_column, end - row.start_column)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Find matches sit under the selection, which marks the current one.
+    let find_matches = state
+        .find_bar
+        .as_ref()
+        .map_or(&[][..], |find_bar| find_bar.matches.as_slice());
+    for range in find_matches.iter().filter(|range| {
+        range.start.line >= visible_first_line && range.start.line <= visible_last_line
+    }) {
+        if plain_rects.len() >= SELECTION_RECT_CAPACITY {
+            break;
+        }
+        for (left, top, width, height) in
+            plain_line_rects(range.start.line, range.start.column, range.end.column)
+        {
+            plain_rects.push((left, top, width, height, COLOR_FIND_MATCH));
+        }
+    }
+
     if let Some((start, end)) = state.selection_bounds() {
-        let visible_first_line = state.top_line;
-        let visible_last_line = visible_first_line
-            .saturating_add(plain_lines.len().saturating_sub(1));
         let range_start_line = start.line.max(visible_first_line);
         let range_end_line = end.line.min(visible_last_line);
 
@@ -574,10 +798,6 @@ fn render_selection_rects(
                     break;
                 }
 
-                let visible_offset = line.saturating_sub(visible_first_line);
-                let Some(display_line) = plain_lines.get(visible_offset) else {
-                    continue;
-                };
                 let line_len = state.document.line_len_chars(line);
                 let line_start = if line == start.line {
                     start.column.min(line_len)
@@ -594,50 +814,14 @@ fn render_selection_rects(
                     continue;
                 }
 
-                let display_len = display_line.chars().count();
-                let start_byte = char_to_byte_index(display_line, line_start.min(display_len));
-                let end_byte = char_to_byte_index(display_line, line_end.min(display_len));
-                let left_x = plain_layout
-                    .and_then(|layout| {
-                        caret_x_from_layout(
-                            layout,
-                            visible_offset,
-                            display_line,
-                            start_byte,
-                            plain_inverse_scale,
-                            plain_char_width,
-                        )
-                    })
-                    .unwrap_or(line_start as f32 * plain_char_width);
-                let right_x = plain_layout
-                    .and_then(|layout| {
-                        caret_x_from_layout(
-                            layout,
-                            visible_offset,
-                            display_line,
-                            end_byte,
-                            plain_inverse_scale,
-                            plain_char_width,
-                        )
-                    })
-                    .unwrap_or(line_end as f32 * plain_char_width);
-                let line_top = plain_layout
-                    .and_then(|layout| {
-                        line_top_from_layout(layout, visible_offset, plain_inverse_scale)
-                    })
-                    .unwrap_or(visible_offset as f32 * plain_line_height);
-
-                plain_rects.push((
-                    plain_origin_x + left_x.min(right_x),
-                    plain_origin_y + line_top,
-                    (right_x - left_x).abs().max(1.0),
-                    plain_line_height.max(1.0),
-                ));
+                for (left, top, width, height) in plain_line_rects(line, line_start, line_end) {
+                    plain_rects.push((left, top, width, height, state.selection_bg_color));
+                }
             }
         }
 
         for (visual_index, visual_line) in processed_view.lines.iter().enumerate() {
-            if processed_rects.len() >= SELECTION_RECT_CAPACITY || visual_line.is_spacer {
+            if processed_rects.len() >= SELECTION_RECT_CAPACITY || !visual_line.maps_to_source() {
                 continue;
             }
 
@@ -745,9 +929,13 @@ fn render_selection_rects(
     for (selection_rect, mut node, mut color, mut visibility) in selection_rect_query.iter_mut() {
         let rect = match selection_rect.kind {
             PanelKind::Plain => plain_rects.get(selection_rect.index).copied(),
-            PanelKind::Processed => processed_rects.get(selection_rect.index).copied(),
+            PanelKind::Processed => processed_rects
+                .get(selection_rect.index)
+                .map(|&(left, top, width, height)| {
+                    (left, top, width, height, state.selection_bg_color)
+                }),
         };
-        let Some((left, top, width, height)) = rect else {
+        let Some((left, top, width, height, rect_color)) = rect else {
             *visibility = Visibility::Hidden;
             continue;
         };
@@ -756,7 +944,29 @@ fn render_selection_rects(
         node.top = px(top);
         node.width = px(width);
         node.height = px(height);
-        color.0 = state.selection_bg_color;
+        color.0 = rect_color;
         *visibility = Visibility::Visible;
     }
 }
+
+#[cfg(test)]
+mod selection_tests {
+    use super::*;
+
+    #[test]
+    fn click_below_short_document_lands_at_document_end() {
+        let document = Document::from_text("INT. HOUSE - DAY\nJohn waits.");
+        let viewport_rows = 40;
+
+        assert_eq!(document_end_past_last_row(&document, 1, document.line_count()), None);
+        for row in [2, 10, viewport_rows - 1] {
+            assert_eq!(
+                document_end_past_last_row(&document, row, document.line_count()),
+                Some(Position {
+                    line: 1,
+                    column: 11
+                })
+            );
+        }
+    }
+}
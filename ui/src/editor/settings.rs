@@ -39,8 +39,20 @@ fn load_persistent_settings() -> PersistentSettings {
         }
     };
 
-    info!("[settings] Loaded settings from {}", path.display());
-    persistent_settings_from_ron(&contents, &defaults)
+    match persistent_settings_from_ron(&contents) {
+        Ok(settings) => {
+            info!("[settings] Loaded settings from {}", path.display());
+            settings
+        }
+        Err(error) => {
+            warn!(
+                "[settings] Failed parsing {}: {}; using defaults",
+                path.display(),
+                error
+            );
+            defaults
+        }
+    }
 }
 
 fn load_keybind_settings() -> KeybindSettings {
@@ -160,42 +172,30 @@ fn load_theme_settings() -> ThemeSettings {
 
 fn save_persistent_settings(settings: &PersistentSettings) -> io::Result<()> {
     let path = PathBuf::from(EDITOR_SETTINGS_PATH);
-    let workspace_root_path = settings
-        .workspace_root_path
-        .as_deref()
-        .unwrap_or("")
-        .replace('\\', "/");
-
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
 
-    let contents = format!(
-        "(\n\
-         \tdialogue_double_space_newline: {},\n\
-         \tnon_dialogue_double_space_newline: {},\n\
-         \tshow_system_titlebar: {},\n\
-         \tpage_margin_left: {:.3},\n\
-         \tpage_margin_right: {:.3},\n\
-         \tpage_margin_top: {:.3},\n\
-         \tpage_margin_bottom: {:.3},\n\
-         \tworkspace_root_path: \"{}\",\n\
-         )\n",
-        settings.dialogue_double_space_newline,
-        settings.non_dialogue_double_space_newline,
-        settings.show_system_titlebar,
-        settings.page_margin_left,
-        settings.page_margin_right,
-        settings.page_margin_top,
-        settings.page_margin_bottom,
-        workspace_root_path,
-    );
-
-    fs::write(&path, contents)?;
+    fs::write(&path, persistent_settings_ron(settings))?;
     info!("[settings] Saved settings to {}", path.display());
     Ok(())
 }
 
+// `Some` values are written bare so hand-edited files can leave out the wrapper.
+fn persistent_settings_ron_options() -> ron::Options {
+    ron::Options::default().with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+}
+
+fn persistent_settings_ron(settings: &PersistentSettings) -> String {
+    let pretty = ron::ser::PrettyConfig::default()
+        .indentor("\t")
+        .struct_names(false);
+    persistent_settings_ron_options()
+        .to_string_pretty(settings, pretty)
+        .map(|contents| format!("{contents}\n"))
+        .expect("persistent settings serialize to RON")
+}
+
 fn save_keybind_settings(keybinds: &KeybindSettings) -> io::Result<()> {
     let path = PathBuf::from(KEYBINDS_SETTINGS_PATH);
     if let Some(parent) = path.parent() {
@@ -227,9 +227,11 @@ fn save_persistent_ui_state(ui_state: &PersistentUiState) -> io::Result<()> {
         "(\n\
          \tworkspace_sidebar_visible: {},\n\
          \ttop_menu_collapsed: {},\n\
+         \tdistraction_free: {},\n\
          )\n",
         ui_state.workspace_sidebar_visible,
-        ui_state.top_menu_collapsed
+        ui_state.top_menu_collapsed,
+        ui_state.distraction_free
     );
 
     fs::write(&path, contents)?;
@@ -237,6 +239,34 @@ fn save_persistent_ui_state(ui_state: &PersistentUiState) -> io::Result<()> {
     Ok(())
 }
 
+fn save_recovery_state(document_path: &Path, recovery: &RecoveryState) -> io::Result<()> {
+    let path = PathBuf::from(RECOVERY_STATE_PATH);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file = RecoveryFile {
+        document_path: document_path.to_path_buf(),
+        state: *recovery,
+    };
+    let contents = ron::ser::to_string_pretty(&file, ron::ser::PrettyConfig::default())
+        .map_err(io::Error::other)?;
+
+    fs::write(&path, contents)?;
+    info!("[recovery] Saved cursor state to {}", path.display());
+    Ok(())
+}
+
+fn load_recovery_state(document_path: &Path) -> Option<RecoveryState> {
+    let contents = fs::read_to_string(RECOVERY_STATE_PATH).ok()?;
+    let file = ron::from_str::<RecoveryFile>(&contents).ok()?;
+    if file.document_path != document_path {
+        return None;
+    }
+
+    Some(file.state)
+}
+
 fn save_theme_settings(theme: &ThemeSettings) -> io::Result<()> {
     let path = PathBuf::from(THEME_SETTINGS_PATH);
     if let Some(parent) = path.parent() {
@@ -248,6 +278,7 @@ fn save_theme_settings(theme: &ThemeSettings) -> io::Result<()> {
     let explorer_background = theme.explorer_background_clamped();
     let processed_background = theme.processed_background_clamped();
     let selection_background = theme.selection_background_clamped();
+    let wrap_guide = theme.wrap_guide_clamped();
     let link_fallback = theme.link_fallback_clamped();
     let link_prop = theme.link_prop_clamped();
     let link_place = theme.link_place_clamped();
@@ -262,6 +293,7 @@ fn save_theme_settings(theme: &ThemeSettings) -> io::Result<()> {
          \texplorer_background: ({:.3}, {:.3}, {:.3}, {:.3}),\n\
          \tprocessed_background: ({:.3}, {:.3}, {:.3}, {:.3}),\n\
          \tselection_background: ({:.3}, {:.3}, {:.3}, {:.3}),\n\
+         \twrap_guide: ({:.3}, {:.3}, {:.3}, {:.3}),\n\
          \tlink_fallback: ({:.3}, {:.3}, {:.3}, {:.3}),\n\
          \tlink_prop: ({:.3}, {:.3}, {:.3}, {:.3}),\n\
          \tlink_place: ({:.3}, {:.3}, {:.3}, {:.3}),\n\
@@ -272,6 +304,7 @@ fn save_theme_settings(theme: &ThemeSettings) -> io::Result<()> {
          \tprocessed_glass: {},\n\
          \texplorer_glass: {},\n\
          \tsettings_glass: {},\n\
+         \tdark_mode: {},\n\
          )\n",
         app_background.x,
         app_background.y,
@@ -293,6 +326,10 @@ fn save_theme_settings(theme: &ThemeSettings) -> io::Result<()> {
         selection_background.y,
         selection_background.z,
         selection_background.w,
+        wrap_guide.x,
+        wrap_guide.y,
+        wrap_guide.z,
+        wrap_guide.w,
         link_fallback.x,
         link_fallback.y,
         link_fallback.z,
@@ -320,7 +357,8 @@ fn save_theme_settings(theme: &ThemeSettings) -> io::Result<()> {
         link_hover_hsv_value_adjustment,
         theme.processed_glass,
         theme.explorer_glass,
-        theme.settings_glass
+        theme.settings_glass,
+        theme.dark_mode
     );
 
     fs::write(&path, contents)?;
@@ -361,6 +399,10 @@ fn parse_ron_f32(contents: &str, key: &str) -> Option<f32> {
     parse_ron_value(contents, key)?.parse::<f32>().ok()
 }
 
+fn parse_ron_usize(contents: &str, key: &str) -> Option<usize> {
+    parse_ron_value(contents, key)?.parse::<usize>().ok()
+}
+
 fn parse_ron_vec4(contents: &str, key: &str) -> Option<Vec4> {
     let raw = parse_ron_value(contents, key)?;
     parse_ron_vec4_value(&raw)
@@ -432,35 +474,140 @@ fn parse_ron_string(contents: &str, key: &str) -> Option<String> {
     None
 }
 
-fn persistent_settings_from_ron(contents: &str, defaults: &PersistentSettings) -> PersistentSettings {
-    let dialogue_value = parse_ron_bool(contents, "dialogue_double_space_newline")
-        .unwrap_or(defaults.dialogue_double_space_newline);
-    let non_dialogue_value = parse_ron_bool(contents, "non_dialogue_double_space_newline")
-        .unwrap_or(defaults.non_dialogue_double_space_newline);
-    let show_system_titlebar =
-        parse_ron_bool(contents, "show_system_titlebar").unwrap_or(defaults.show_system_titlebar);
-    let page_margin_left = parse_ron_f32(contents, "page_margin_left").unwrap_or(defaults.page_margin_left);
-    let page_margin_right =
-        parse_ron_f32(contents, "page_margin_right").unwrap_or(defaults.page_margin_right);
-    let page_margin_top = parse_ron_f32(contents, "page_margin_top").unwrap_or(defaults.page_margin_top);
-    let page_margin_bottom =
-        parse_ron_f32(contents, "page_margin_bottom").unwrap_or(defaults.page_margin_bottom);
-    let workspace_root_path = parse_ron_string(contents, "workspace_root_path")
-        .and_then(|value| if value.trim().is_empty() { None } else { Some(value) })
-        .or_else(|| defaults.workspace_root_path.clone());
+fn persistent_settings_from_ron(
+    contents: &str,
+) -> Result<PersistentSettings, ron::error::SpannedError> {
+    let mut settings =
+        persistent_settings_ron_options().from_str::<PersistentSettings>(contents)?;
+    apply_flat_settings_keys(contents, &mut settings);
+    Ok(settings.sanitized())
+}
 
-    PersistentSettings {
-        dialogue_double_space_newline: dialogue_value,
-        non_dialogue_double_space_newline: non_dialogue_value,
-        show_system_titlebar,
-        page_margin_left,
-        page_margin_right,
-        page_margin_top,
-        page_margin_bottom,
-        workspace_root_path,
+// Files written before serde kept indent widths and element colors as flat
+// keys; they are only read when the nested field is absent.
+fn apply_flat_settings_keys(contents: &str, settings: &mut PersistentSettings) {
+    if parse_ron_value(contents, "indent_widths").is_none() {
+        for (index, (key, _)) in INDENT_WIDTH_SETTINGS.iter().enumerate() {
+            if let Some(width) = parse_ron_usize(contents, key)
+                && let Some(slot) = indent_width_slot(&mut settings.indent_widths, index)
+            {
+                *slot = width;
+            }
+        }
+    }
+    if parse_ron_value(contents, "element_colors").is_none() {
+        for ((key, _, _), hex) in ELEMENT_COLOR_SETTINGS
+            .iter()
+            .zip(&mut settings.element_colors)
+        {
+            if let Some(value) = parse_ron_string(contents, key) {
+                *hex = value;
+            }
+        }
+    }
+}
+
+impl PersistentSettings {
+    fn sanitized(mut self) -> Self {
+        let defaults = Self::default();
+        self.dialogue_width_columns = clamp_element_width_columns(self.dialogue_width_columns);
+        self.action_width_columns = clamp_element_width_columns(self.action_width_columns);
+        self.wrap_guide_column = clamp_element_width_columns(self.wrap_guide_column);
+        self.tab_width = self.tab_width.clamp(1, MAX_TAB_WIDTH);
+        self.autosave_interval_secs = self.autosave_interval_secs.min(MAX_AUTOSAVE_INTERVAL_SECS);
+        self.caret_width = if self.caret_width.is_finite() {
+            self.caret_width.clamp(1.0, MAX_CARET_WIDTH)
+        } else {
+            defaults.caret_width
+        };
+        self.caret_blink_secs = if !self.caret_blink_secs.is_finite() {
+            defaults.caret_blink_secs
+        } else if self.caret_blink_secs <= 0.0 {
+            0.0
+        } else {
+            self.caret_blink_secs
+                .clamp(MIN_CARET_BLINK_SECS, MAX_CARET_BLINK_SECS)
+        };
+        for index in 0..INDENT_WIDTH_SETTINGS.len() {
+            if let Some(slot) = indent_width_slot(&mut self.indent_widths, index) {
+                *slot = (*slot).min(MAX_INDENT_WIDTH);
+            }
+        }
+        self.zoom = if self.zoom.is_finite() {
+            self.zoom.clamp(ZOOM_MIN, ZOOM_MAX)
+        } else {
+            defaults.zoom
+        };
+        self.workspace_root_path = self
+            .workspace_root_path
+            .filter(|path| !path.trim().is_empty());
+        self
     }
 }
 
+fn serialize_page_size<S: serde::Serializer>(
+    size: &PageSize,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(size.settings_key())
+}
+
+fn deserialize_page_size<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<PageSize, D::Error> {
+    let key = String::deserialize(deserializer)?;
+    Ok(PageSize::from_settings_key(&key).unwrap_or(PersistentSettings::default().page_size))
+}
+
+fn serialize_caret_color<S: serde::Serializer>(
+    color: &Option<Color>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&color.map_or_else(String::new, color_hex))
+}
+
+// An empty or unreadable color follows the theme.
+fn deserialize_caret_color<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Color>, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+    Ok(Srgba::hex(hex.trim()).ok().map(Color::from))
+}
+
+fn serialize_processed_kinds<S: serde::Serializer>(
+    kinds: &[LineKind],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&processed_kind_keys(kinds))
+}
+
+fn deserialize_processed_kinds<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<LineKind>, D::Error> {
+    let keys = String::deserialize(deserializer)?;
+    Ok(processed_kinds_from_keys(&keys))
+}
+
+fn processed_kind_keys(kinds: &[LineKind]) -> String {
+    PROCESSED_KIND_TOGGLES
+        .iter()
+        .filter(|(kind, _, _)| kinds.contains(kind))
+        .map(|(_, key, _)| *key)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn processed_kinds_from_keys(keys: &str) -> Vec<LineKind> {
+    keys.split(',')
+        .filter_map(|key| {
+            PROCESSED_KIND_TOGGLES
+                .iter()
+                .find(|(_, candidate, _)| *candidate == key.trim())
+                .map(|(kind, _, _)| kind.clone())
+        })
+        .collect()
+}
+
 fn persistent_ui_state_from_ron(
     contents: &str,
     defaults: &PersistentUiState,
@@ -469,10 +616,13 @@ fn persistent_ui_state_from_ron(
         .unwrap_or(defaults.workspace_sidebar_visible);
     let top_menu_collapsed =
         parse_ron_bool(contents, "top_menu_collapsed").unwrap_or(defaults.top_menu_collapsed);
+    let distraction_free =
+        parse_ron_bool(contents, "distraction_free").unwrap_or(defaults.distraction_free);
 
     PersistentUiState {
         workspace_sidebar_visible,
         top_menu_collapsed,
+        distraction_free,
     }
 }
 
@@ -497,6 +647,7 @@ fn theme_settings_from_ron(contents: &str, defaults: &ThemeSettings) -> ThemeSet
                 .unwrap_or(defaults.selection_background.w),
         )
     });
+    let wrap_guide = parse_ron_vec4(contents, "wrap_guide").unwrap_or(defaults.wrap_guide);
     let legacy_processed_link = parse_ron_vec4(contents, "processed_link")
         .unwrap_or(defaults.link_fallback);
     let link_fallback = parse_ron_vec4(contents, "link_fallback").unwrap_or(legacy_processed_link);
@@ -516,6 +667,7 @@ fn theme_settings_from_ron(contents: &str, defaults: &ThemeSettings) -> ThemeSet
     let settings_glass = parse_ron_bool(contents, "settings_glass")
         .or_else(|| parse_ron_bool(contents, "top_menu_glass"))
         .unwrap_or(defaults.settings_glass);
+    let dark_mode = parse_ron_bool(contents, "dark_mode").unwrap_or(defaults.dark_mode);
 
     ThemeSettings {
         app_background: clamp_vec4_rgba(app_background),
@@ -528,6 +680,7 @@ fn theme_settings_from_ron(contents: &str, defaults: &ThemeSettings) -> ThemeSet
             selection_background.z.clamp(0.0, 1.0),
             selection_background.w.clamp(0.0, 1.0),
         ),
+        wrap_guide: clamp_vec4_rgba(wrap_guide),
         link_fallback: clamp_vec4_rgba(link_fallback),
         link_prop: clamp_vec4_rgba(link_prop),
         link_place: clamp_vec4_rgba(link_place),
@@ -540,6 +693,7 @@ fn theme_settings_from_ron(contents: &str, defaults: &ThemeSettings) -> ThemeSet
         processed_glass,
         explorer_glass,
         settings_glass,
+        dark_mode,
     }
 }
 
@@ -560,8 +714,7 @@ fn apply_keybind_settings_from_ron(contents: &str, keybinds: &mut KeybindSetting
 fn load_legacy_persistent_settings_ron() -> Option<PersistentSettings> {
     let path = PathBuf::from(LEGACY_EDITOR_SETTINGS_PATH);
     let contents = fs::read_to_string(path).ok()?;
-    let defaults = PersistentSettings::default();
-    Some(persistent_settings_from_ron(&contents, &defaults))
+    persistent_settings_from_ron(&contents).ok()
 }
 
 fn load_legacy_keybind_settings_ron() -> Option<KeybindSettings> {
@@ -575,66 +728,12 @@ fn load_legacy_keybind_settings_ron() -> Option<KeybindSettings> {
 fn load_legacy_toml_settings() -> Option<PersistentSettings> {
     let path = PathBuf::from(LEGACY_SETTINGS_PATH);
     let contents = fs::read_to_string(&path).ok()?;
-    let defaults = PersistentSettings::default();
-    Some(PersistentSettings {
-        dialogue_double_space_newline: parse_toml_bool(&contents, "dialogue_double_space_newline")
-            .or_else(|| parse_toml_bool(&contents, "parenthetical_double_space_newline"))
-            .unwrap_or(defaults.dialogue_double_space_newline),
-        non_dialogue_double_space_newline: parse_toml_bool(
-            &contents,
-            "non_dialogue_double_space_newline",
-        )
-        .unwrap_or(defaults.non_dialogue_double_space_newline),
-        show_system_titlebar: parse_toml_bool(&contents, "show_system_titlebar")
-            .unwrap_or(defaults.show_system_titlebar),
-        page_margin_left: parse_toml_f32(&contents, "page_margin_left")
-            .unwrap_or(defaults.page_margin_left),
-        page_margin_right: parse_toml_f32(&contents, "page_margin_right")
-            .unwrap_or(defaults.page_margin_right),
-        page_margin_top: parse_toml_f32(&contents, "page_margin_top")
-            .unwrap_or(defaults.page_margin_top),
-        page_margin_bottom: parse_toml_f32(&contents, "page_margin_bottom")
-            .unwrap_or(defaults.page_margin_bottom),
-        workspace_root_path: None,
-    })
-}
-
-fn parse_toml_bool(contents: &str, key: &str) -> Option<bool> {
-    for line in contents.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
-
-        let Some((lhs, rhs)) = line.split_once('=') else {
-            continue;
-        };
-        if lhs.trim() == key {
-            return match rhs.trim() {
-                "true" => Some(true),
-                "false" => Some(false),
-                _ => None,
-            };
-        }
-    }
-    None
+    persistent_settings_from_toml(&contents).ok()
 }
 
-fn parse_toml_f32(contents: &str, key: &str) -> Option<f32> {
-    for line in contents.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
-
-        let Some((lhs, rhs)) = line.split_once('=') else {
-            continue;
-        };
-        if lhs.trim() == key {
-            return rhs.trim().parse::<f32>().ok();
-        }
-    }
-    None
+// The TOML file only ever held a few keys; everything else starts at its default.
+fn persistent_settings_from_toml(contents: &str) -> Result<PersistentSettings, toml::de::Error> {
+    toml::from_str::<PersistentSettings>(contents).map(PersistentSettings::sanitized)
 }
 
 fn persistent_settings_from_state(state: &EditorState) -> PersistentSettings {
@@ -642,10 +741,44 @@ fn persistent_settings_from_state(state: &EditorState) -> PersistentSettings {
         dialogue_double_space_newline: state.dialogue_double_space_newline,
         non_dialogue_double_space_newline: state.non_dialogue_double_space_newline,
         show_system_titlebar: state.show_system_titlebar,
+        processed_right_to_left: state.processed_right_to_left,
+        smooth_scrolling: state.smooth_scrolling,
+        wrap_action_lines: state.wrap_action_lines,
+        trim_trailing_whitespace_on_save: state.trim_trailing_whitespace_on_save,
+        dialogue_page_continuations: state.dialogue_page_continuations,
+        auto_number_scenes: state.auto_number_scenes,
+        show_wrap_guide: state.show_wrap_guide,
+        autosave_on_focus_loss: state.autosave_on_focus_loss,
+        unified_processed_edit: state.unified_processed_edit,
+        processed_cue_ruler: state.processed_cue_ruler,
+        element_width_warnings: state.element_width_warnings,
+        focus_current_scene: state.focus_current_scene,
+        scene_heading_requires_content: state.scene_heading_requires_content,
+        processed_kind_labels: state.processed_kind_labels,
+        auto_uppercase_character_cues: state.auto_uppercase_character_cues,
+        auto_capitalize_sentences: state.auto_capitalize_sentences,
+        auto_pair_parentheses: state.auto_pair_parentheses,
+        dialogue_spans_single_blank: state.dialogue_spans_single_blank,
+        sync_scroll_panes: state.sync_scroll_panes,
+        auto_reload_external_changes: state.auto_reload_external_changes,
+        hyphenate_exported_dialogue: state.hyphenate_exported_dialogue,
+        page_size: state.page_size,
         page_margin_left: state.page_margin_left,
         page_margin_right: state.page_margin_right,
         page_margin_top: state.page_margin_top,
         page_margin_bottom: state.page_margin_bottom,
+        dialogue_width_columns: state.dialogue_width_columns,
+        action_width_columns: state.action_width_columns,
+        wrap_guide_column: state.wrap_guide_column,
+        tab_width: state.tab_width,
+        autosave_interval_secs: state.autosave_interval_secs,
+        caret_width: state.caret_width,
+        caret_color: state.caret_color,
+        caret_blink_secs: state.caret_blink_secs,
+        indent_widths: state.indent_widths,
+        zoom: state.zoom,
+        hidden_processed_kinds: state.hidden_processed_kinds.clone(),
+        element_colors: state.element_colors.map(color_hex),
         workspace_root_path: state
             .workspace_root
             .as_ref()
@@ -657,6 +790,7 @@ fn persistent_ui_state_from_state(state: &EditorState) -> PersistentUiState {
     PersistentUiState {
         workspace_sidebar_visible: state.workspace_sidebar_visible,
         top_menu_collapsed: state.top_menu_collapsed,
+        distraction_free: state.distraction_free,
     }
 }
 
@@ -672,6 +806,7 @@ fn theme_settings_from_state(state: &EditorState) -> ThemeSettings {
             state.selection_bg_rgba.z.clamp(0.0, 1.0),
             state.selection_bg_rgba.w.clamp(0.0, 1.0),
         ),
+        wrap_guide: clamp_vec4_rgba(state.wrap_guide_rgba),
         link_fallback: clamp_vec4_rgba(state.link_fallback_rgba),
         link_prop: clamp_vec4_rgba(state.link_prop_rgba),
         link_place: clamp_vec4_rgba(state.link_place_rgba),
@@ -684,9 +819,27 @@ fn theme_settings_from_state(state: &EditorState) -> ThemeSettings {
         processed_glass: state.processed_glass,
         explorer_glass: state.explorer_glass,
         settings_glass: state.settings_glass,
+        dark_mode: state.dark_mode,
     }
 }
 
+// Switching modes swaps the chrome backgrounds for the mode's preset; other
+// theme colors stay as the user set them.
+fn set_dark_mode(state: &mut EditorState, dark_mode: bool) {
+    let preset = if dark_mode {
+        ThemeSettings::dark()
+    } else {
+        ThemeSettings::default()
+    };
+    state.dark_mode = dark_mode;
+    state.app_bg_rgba = preset.app_background;
+    state.top_menu_bg_rgba = preset.top_menu_background;
+    state.explorer_bg_rgba = preset.explorer_background;
+    state.processed_bg_rgba = preset.processed_background;
+    state.selection_bg_rgba = preset.selection_background;
+    sync_theme_colors(state);
+}
+
 fn sync_theme_colors(state: &mut EditorState) {
     state.app_bg_rgba = clamp_vec4_rgba(state.app_bg_rgba);
     state.app_bg_color = color_from_rgba(state.app_bg_rgba);
@@ -708,6 +861,8 @@ fn sync_theme_colors(state: &mut EditorState) {
         state.selection_bg_rgba.z,
         state.selection_bg_rgba.w,
     );
+    state.wrap_guide_rgba = clamp_vec4_rgba(state.wrap_guide_rgba);
+    state.wrap_guide_color = color_from_rgba(state.wrap_guide_rgba);
     state.link_fallback_rgba = clamp_vec4_rgba(state.link_fallback_rgba);
     state.link_fallback_color = color_from_rgba(state.link_fallback_rgba);
     state.link_prop_rgba = clamp_vec4_rgba(state.link_prop_rgba);
@@ -722,6 +877,8 @@ fn sync_theme_colors(state: &mut EditorState) {
     state.link_concept_color = color_from_rgba(state.link_concept_rgba);
     state.link_hover_hsv_value_adjustment =
         clamp_link_hover_hsv_value_adjustment(state.link_hover_hsv_value_adjustment);
+    state.element_rgba = state.element_rgba.map(clamp_vec4_rgba);
+    state.element_colors = state.element_rgba.map(color_from_rgba);
 }
 
 fn active_theme_rgba(state: &EditorState) -> Vec4 {
@@ -735,12 +892,14 @@ fn theme_rgba_for_target(state: &EditorState, target: ThemeColorTarget) -> Vec4
         ThemeColorTarget::ExplorerBackground => state.explorer_bg_rgba,
         ThemeColorTarget::ProcessedBackground => state.processed_bg_rgba,
         ThemeColorTarget::SelectionBackground => state.selection_bg_rgba,
+        ThemeColorTarget::WrapGuide => state.wrap_guide_rgba,
         ThemeColorTarget::LinkFallback => state.link_fallback_rgba,
         ThemeColorTarget::LinkProp => state.link_prop_rgba,
         ThemeColorTarget::LinkPlace => state.link_place_rgba,
         ThemeColorTarget::LinkCharacter => state.link_character_rgba,
         ThemeColorTarget::LinkFaction => state.link_faction_rgba,
         ThemeColorTarget::LinkConcept => state.link_concept_rgba,
+        ThemeColorTarget::Element(index) => state.element_rgba[index],
     }
 }
 
@@ -751,12 +910,14 @@ fn theme_color_for_target(state: &EditorState, target: ThemeColorTarget) -> Colo
         ThemeColorTarget::ExplorerBackground => state.explorer_bg_color,
         ThemeColorTarget::ProcessedBackground => state.processed_bg_color,
         ThemeColorTarget::SelectionBackground => state.selection_bg_color,
+        ThemeColorTarget::WrapGuide => state.wrap_guide_color,
         ThemeColorTarget::LinkFallback => state.link_fallback_color,
         ThemeColorTarget::LinkProp => state.link_prop_color,
         ThemeColorTarget::LinkPlace => state.link_place_color,
         ThemeColorTarget::LinkCharacter => state.link_character_color,
         ThemeColorTarget::LinkFaction => state.link_faction_color,
         ThemeColorTarget::LinkConcept => state.link_concept_color,
+        ThemeColorTarget::Element(index) => state.element_colors[index],
     }
 }
 
@@ -767,12 +928,14 @@ fn set_active_theme_rgba(state: &mut EditorState, rgba: Vec4) {
         ThemeColorTarget::ExplorerBackground => state.explorer_bg_rgba = rgba,
         ThemeColorTarget::ProcessedBackground => state.processed_bg_rgba = rgba,
         ThemeColorTarget::SelectionBackground => state.selection_bg_rgba = rgba,
+        ThemeColorTarget::WrapGuide => state.wrap_guide_rgba = rgba,
         ThemeColorTarget::LinkFallback => state.link_fallback_rgba = rgba,
         ThemeColorTarget::LinkProp => state.link_prop_rgba = rgba,
         ThemeColorTarget::LinkPlace => state.link_place_rgba = rgba,
         ThemeColorTarget::LinkCharacter => state.link_character_rgba = rgba,
         ThemeColorTarget::LinkFaction => state.link_faction_rgba = rgba,
         ThemeColorTarget::LinkConcept => state.link_concept_rgba = rgba,
+        ThemeColorTarget::Element(index) => state.element_rgba[index] = rgba,
     }
     sync_theme_colors(state);
 }
@@ -813,7 +976,9 @@ impl EditorState {
             | ThemeColorTarget::TopMenuBackground
             | ThemeColorTarget::ExplorerBackground
             | ThemeColorTarget::ProcessedBackground
-            | ThemeColorTarget::SelectionBackground => self.link_fallback_rgba,
+            | ThemeColorTarget::SelectionBackground
+            | ThemeColorTarget::WrapGuide
+            | ThemeColorTarget::Element(_) => self.link_fallback_rgba,
         }
     }
 }
@@ -864,6 +1029,25 @@ fn clamp_link_hover_hsv_value_adjustment(value: f32) -> f32 {
     value.clamp(0.0, LINK_HOVER_HSV_VALUE_MAX)
 }
 
+fn color_hex(color: Color) -> String {
+    color.to_srgba().to_hex()
+}
+
+// Falls back to the default for each value that is not a hex color and names
+// the offending settings so the caller can warn about them.
+fn element_rgba_from_hex(values: &[String; 6]) -> ([Vec4; 6], Vec<&'static str>) {
+    let mut invalid = Vec::new();
+    let rgba = std::array::from_fn(|index| {
+        let (key, _, default) = ELEMENT_COLOR_SETTINGS[index];
+        let color = Srgba::hex(values[index].trim()).unwrap_or_else(|_| {
+            invalid.push(key);
+            default.to_srgba()
+        });
+        clamp_vec4_rgba(color.to_vec4())
+    });
+    (rgba, invalid)
+}
+
 fn color_from_rgba(value: Vec4) -> Color {
     Color::srgba(value.x, value.y, value.z, value.w)
 }
@@ -940,13 +1124,15 @@ fn normalize_page_margins(state: &mut EditorState) {
     state.page_margin_top = state.page_margin_top.max(0.0);
     state.page_margin_bottom = state.page_margin_bottom.max(0.0);
 
-    let max_horizontal = (A4_WIDTH_POINTS - MIN_TEXT_BOX_WIDTH).max(0.0);
+    let page_width = state.page_size.width_points();
+    let page_height = state.page_size.height_points();
+    let max_horizontal = (page_width - MIN_TEXT_BOX_WIDTH).max(0.0);
     if state.page_margin_left + state.page_margin_right > max_horizontal {
         let overflow = state.page_margin_left + state.page_margin_right - max_horizontal;
         state.page_margin_right = (state.page_margin_right - overflow).max(0.0);
     }
 
-    let max_vertical = (A4_HEIGHT_POINTS - MIN_TEXT_BOX_HEIGHT).max(0.0);
+    let max_vertical = (page_height - MIN_TEXT_BOX_HEIGHT).max(0.0);
     if state.page_margin_top + state.page_margin_bottom > max_vertical {
         let overflow = state.page_margin_top + state.page_margin_bottom - max_vertical;
         state.page_margin_bottom = (state.page_margin_bottom - overflow).max(0.0);
@@ -954,25 +1140,27 @@ fn normalize_page_margins(state: &mut EditorState) {
 }
 
 fn adjust_page_margin(state: &mut EditorState, edge: MarginEdge, delta: f32) {
+    let page_width = state.page_size.width_points();
+    let page_height = state.page_size.height_points();
     match edge {
         MarginEdge::Left => {
             let max_left =
-                (A4_WIDTH_POINTS - MIN_TEXT_BOX_WIDTH - state.page_margin_right).max(0.0);
+                (page_width - MIN_TEXT_BOX_WIDTH - state.page_margin_right).max(0.0);
             state.page_margin_left = (state.page_margin_left + delta).clamp(0.0, max_left);
         }
         MarginEdge::Right => {
             let max_right =
-                (A4_WIDTH_POINTS - MIN_TEXT_BOX_WIDTH - state.page_margin_left).max(0.0);
+                (page_width - MIN_TEXT_BOX_WIDTH - state.page_margin_left).max(0.0);
             state.page_margin_right = (state.page_margin_right + delta).clamp(0.0, max_right);
         }
         MarginEdge::Top => {
             let max_top =
-                (A4_HEIGHT_POINTS - MIN_TEXT_BOX_HEIGHT - state.page_margin_bottom).max(0.0);
+                (page_height - MIN_TEXT_BOX_HEIGHT - state.page_margin_bottom).max(0.0);
             state.page_margin_top = (state.page_margin_top + delta).clamp(0.0, max_top);
         }
         MarginEdge::Bottom => {
             let max_bottom =
-                (A4_HEIGHT_POINTS - MIN_TEXT_BOX_HEIGHT - state.page_margin_top).max(0.0);
+                (page_height - MIN_TEXT_BOX_HEIGHT - state.page_margin_top).max(0.0);
             state.page_margin_bottom = (state.page_margin_bottom + delta).clamp(0.0, max_bottom);
         }
     }
@@ -980,6 +1168,60 @@ fn adjust_page_margin(state: &mut EditorState, edge: MarginEdge, delta: f32) {
     normalize_page_margins(state);
 }
 
+fn apply_screenplay_page_margins(state: &mut EditorState) {
+    let margins = PageMargins::SCREENPLAY;
+    state.page_margin_left = margins.left;
+    state.page_margin_right = margins.right;
+    state.page_margin_top = margins.top;
+    state.page_margin_bottom = margins.bottom;
+    normalize_page_margins(state);
+}
+
+fn clamp_element_width_columns(columns: usize) -> usize {
+    columns.clamp(MIN_ELEMENT_WIDTH_COLUMNS, MAX_ELEMENT_WIDTH_COLUMNS)
+}
+
+fn adjust_element_width(state: &mut EditorState, element: WidthElement, delta: isize) {
+    let columns = match element {
+        WidthElement::Dialogue => &mut state.dialogue_width_columns,
+        WidthElement::Action => &mut state.action_width_columns,
+        WidthElement::WrapGuide => &mut state.wrap_guide_column,
+        WidthElement::Indent(index) => {
+            if let Some(width) = indent_width_slot(&mut state.indent_widths, index) {
+                *width = width.saturating_add_signed(delta).min(MAX_INDENT_WIDTH);
+                // Parsed lines carry their indent, so they pick up the new width here.
+                state.reparse();
+            }
+            return;
+        }
+    };
+    *columns = clamp_element_width_columns(columns.saturating_add_signed(delta));
+}
+
+fn element_width_columns(state: &EditorState, element: WidthElement) -> usize {
+    match element {
+        WidthElement::Dialogue => state.dialogue_width_columns,
+        WidthElement::Action => state.action_width_columns,
+        WidthElement::WrapGuide => state.wrap_guide_column,
+        WidthElement::Indent(index) => {
+            let mut widths = state.indent_widths;
+            indent_width_slot(&mut widths, index).map_or(0, |width| *width)
+        }
+    }
+}
+
+fn indent_width_slot(widths: &mut IndentWidths, index: usize) -> Option<&mut usize> {
+    match index {
+        0 => Some(&mut widths.scene_heading),
+        1 => Some(&mut widths.character),
+        2 => Some(&mut widths.dialogue),
+        3 => Some(&mut widths.parenthetical),
+        4 => Some(&mut widths.transition),
+        5 => Some(&mut widths.synopsis),
+        _ => None,
+    }
+}
+
 fn scaled_font_size(state: &EditorState) -> f32 {
     FONT_SIZE * state.zoom
 }
@@ -1007,6 +1249,11 @@ fn scaled_text_padding_y(state: &EditorState) -> f32 {
     TEXT_PADDING_Y * state.zoom
 }
 
+// Where the first Plain row is drawn, shifted up by any partly scrolled-off line.
+fn plain_text_top(state: &EditorState) -> f32 {
+    scaled_text_padding_y(state) - state.plain_scroll_fraction * state.measured_line_step.max(1.0)
+}
+
 fn plain_horizontal_scroll_max(state: &EditorState, plain_panel_size: Option<Vec2>) -> f32 {
     let Some(panel_size) = plain_panel_size else {
         return 0.0;
@@ -1017,7 +1264,9 @@ fn plain_horizontal_scroll_max(state: &EditorState, plain_panel_size: Option<Vec
         .document
         .lines()
         .iter()
-        .map(|line| line.chars().count())
+        .enumerate()
+        .filter(|(index, _)| !state.plain_line_wraps(*index))
+        .map(|(_, line)| line.chars().count())
         .max()
         .unwrap_or(0) as f32;
     let content_width =
@@ -1025,9 +1274,131 @@ fn plain_horizontal_scroll_max(state: &EditorState, plain_panel_size: Option<Vec
     (content_width - panel_size.x).max(0.0)
 }
 
+fn plain_wrap_columns(state: &EditorState, plain_panel_size: Option<Vec2>) -> Option<usize> {
+    if !state.wrap_action_lines {
+        return None;
+    }
+    let panel_size = plain_panel_size?;
+    let char_width = scaled_char_width(state).max(1.0);
+    let text_width = panel_size.x - scaled_text_padding_x(state) * 2.0;
+    Some((text_width / char_width).floor().max(1.0) as usize)
+}
+
 fn processed_horizontal_scroll_bounds(
     state: &EditorState,
     processed_panel_size: Option<Vec2>,
 ) -> (f32, f32) {
     processed_horizontal_scroll_bounds_with_overscroll(state, processed_panel_size)
 }
+
+#[cfg(test)]
+mod settings_tests {
+    use super::*;
+
+    #[test]
+    fn persistent_settings_round_trip_through_ron() {
+        let settings = PersistentSettings {
+            dialogue_double_space_newline: true,
+            non_dialogue_double_space_newline: true,
+            show_system_titlebar: true,
+            processed_right_to_left: true,
+            wrap_action_lines: true,
+            smooth_scrolling: true,
+            trim_trailing_whitespace_on_save: true,
+            dialogue_page_continuations: true,
+            auto_number_scenes: true,
+            show_wrap_guide: true,
+            autosave_on_focus_loss: true,
+            unified_processed_edit: true,
+            processed_cue_ruler: true,
+            element_width_warnings: true,
+            focus_current_scene: true,
+            scene_heading_requires_content: true,
+            processed_kind_labels: true,
+            auto_uppercase_character_cues: true,
+            auto_capitalize_sentences: true,
+            auto_pair_parentheses: true,
+            dialogue_spans_single_blank: true,
+            sync_scroll_panes: true,
+            auto_reload_external_changes: true,
+            hyphenate_exported_dialogue: true,
+            page_size: PageSize::UsLetter,
+            page_margin_left: 40.5,
+            page_margin_right: 32.0,
+            page_margin_top: 28.25,
+            page_margin_bottom: 36.0,
+            dialogue_width_columns: 30,
+            action_width_columns: 58,
+            wrap_guide_column: 60,
+            tab_width: 2,
+            autosave_interval_secs: 90,
+            caret_width: 3.5,
+            caret_color: Some(Color::srgb_u8(0xd9, 0x4f, 0x30)),
+            caret_blink_secs: 0.0,
+            indent_widths: IndentWidths {
+                character: 21,
+                synopsis: 6,
+                ..IndentWidths::default()
+            },
+            zoom: 1.3,
+            hidden_processed_kinds: vec![LineKind::Action, LineKind::Parenthetical],
+            element_colors: {
+                let mut colors = PersistentSettings::default().element_colors;
+                colors[2] = "#8a2be2".to_string();
+                colors
+            },
+            workspace_root_path: Some("C:/scripts/pilot".to_string()),
+        };
+
+        let contents = persistent_settings_ron(&settings);
+        let loaded = persistent_settings_from_ron(&contents).expect("settings parse back");
+        assert_eq!(loaded, settings);
+    }
+
+    #[test]
+    fn flat_settings_files_keep_their_values() {
+        let contents = "(\n\
+             \ttab_width: 2,\n\
+             \tpage_size: \"us_letter\",\n\
+             \tcaret_color: \"\",\n\
+             \tcharacter_indent: 21,\n\
+             \tdialogue_color: \"#8a2be2\",\n\
+             \thidden_processed_kinds: \"action\",\n\
+             \tworkspace_root_path: \"\",\n\
+             \tretired_setting: true,\n\
+             )\n";
+        let settings = persistent_settings_from_ron(contents).expect("flat settings parse");
+
+        assert_eq!(settings.tab_width, 2);
+        assert_eq!(settings.page_size, PageSize::UsLetter);
+        assert_eq!(settings.caret_color, None);
+        assert_eq!(settings.indent_widths.character, 21);
+        assert_eq!(settings.element_colors[3], "#8a2be2");
+        assert_eq!(settings.hidden_processed_kinds, vec![LineKind::Action]);
+        assert_eq!(settings.workspace_root_path, None);
+        assert_eq!(settings.zoom, PersistentSettings::default().zoom);
+    }
+
+    #[test]
+    fn invalid_element_colors_fall_back_to_their_defaults() {
+        let mut values = PersistentSettings::default().element_colors;
+        values[0] = "#ff0000".to_string();
+        values[3] = "not a color".to_string();
+
+        let (rgba, invalid) = element_rgba_from_hex(&values);
+        assert_eq!(rgba[0], Vec4::new(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(color_from_rgba(rgba[3]).to_srgba().to_hex(), color_hex(COLOR_DIALOGUE));
+        assert_eq!(invalid, vec!["dialogue_color"]);
+    }
+
+    #[test]
+    fn legacy_toml_settings_accept_the_parenthetical_key() {
+        let settings = persistent_settings_from_toml(
+            "# old settings\nparenthetical_double_space_newline = true\npage_margin_top = 12.5\n",
+        )
+        .expect("legacy settings parse");
+        assert!(settings.dialogue_double_space_newline);
+        assert_eq!(settings.page_margin_top, 12.5);
+        assert_eq!(settings.tab_width, PersistentSettings::default().tab_width);
+    }
+}
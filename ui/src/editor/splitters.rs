@@ -21,9 +21,9 @@ fn sync_panel_split_layout(
         &mut layout,
         total_width,
         state.display_mode,
-        state.workspace_sidebar_visible,
+        state.workspace_sidebar_shown(),
     );
-    let workspace_splitter_width = if state.workspace_sidebar_visible {
+    let workspace_splitter_width = if state.workspace_sidebar_shown() {
         PANEL_SPLITTER_WIDTH
     } else {
         0.0
@@ -45,7 +45,7 @@ fn sync_panel_split_layout(
 
     for mut node in node_queries.p0().iter_mut() {
         node.width = px(workspace_width);
-        node.display = if state.workspace_sidebar_visible {
+        node.display = if state.workspace_sidebar_shown() {
             Display::Flex
         } else {
             Display::None
@@ -87,7 +87,7 @@ fn sync_panel_split_layout(
         node.display = if splitter_visible_for_mode(
             *splitter,
             state.display_mode,
-            state.workspace_sidebar_visible,
+            state.workspace_sidebar_shown(),
         ) {
             Display::Flex
         } else {
@@ -112,7 +112,7 @@ fn handle_panel_splitter_drag(
     if mouse_buttons.just_pressed(MouseButton::Left)
         && !keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
     {
-        let workspace_sidebar_visible = state.workspace_sidebar_visible;
+        let workspace_sidebar_visible = state.workspace_sidebar_shown();
         let hovered_splitter =
             body_row_query
                 .iter()
@@ -173,7 +173,7 @@ fn handle_panel_splitter_drag(
 
     match active_splitter {
         PanelSplitter::Workspace => {
-            if !state.workspace_sidebar_visible {
+            if !state.workspace_sidebar_shown() {
                 return;
             }
             let workspace_width =
@@ -181,7 +181,7 @@ fn handle_panel_splitter_drag(
                     &mut layout,
                     total_width,
                     state.display_mode,
-                    state.workspace_sidebar_visible,
+                    state.workspace_sidebar_shown(),
                 ) + delta_x;
             let min_editor_width = min_editor_content_width(state.display_mode);
             let max_workspace_width = (total_width - PANEL_SPLITTER_WIDTH - min_editor_width).max(0.0);
@@ -196,9 +196,9 @@ fn handle_panel_splitter_drag(
                 &mut layout,
                 total_width,
                 state.display_mode,
-                state.workspace_sidebar_visible,
+                state.workspace_sidebar_shown(),
             );
-            let workspace_splitter_width = if state.workspace_sidebar_visible {
+            let workspace_splitter_width = if state.workspace_sidebar_shown() {
                 PANEL_SPLITTER_WIDTH
             } else {
                 0.0
@@ -231,7 +231,7 @@ fn style_panel_splitters(
         color.0 = if !splitter_visible_for_mode(
             *splitter,
             state.display_mode,
-            state.workspace_sidebar_visible,
+            state.workspace_sidebar_shown(),
         ) {
             Color::srgba(0.0, 0.0, 0.0, 0.0)
         } else if drag_state.active == Some(*splitter) {
@@ -53,11 +53,13 @@ fn status_line_bundle(font: Handle<Font>, background: Color) -> impl Bundle {
 impl EditorState {
     fn visible_status(&self) -> String {
         format!(
-            "{} | format: {} | line {}, col {} | load: {} | save: {}",
+            "{}{} | format: {} | line {}, col {}{} | load: {} | save: {}",
+            if self.is_modified { "* " } else { "" },
             self.status_message,
             document_format_label(self.document_format),
             self.cursor.position.line + 1,
             self.cursor.position.column + 1,
+            if self.overwrite { " | OVR" } else { "" },
             status_path_label(&self.paths.load_path),
             status_path_label(&self.paths.save_path)
         )
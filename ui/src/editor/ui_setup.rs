@@ -118,6 +118,31 @@ fn setup(
                                         ToolbarAction::OpenWorkspace,
                                     ),
                                     toolbar_button(font.clone(), "Save As", ToolbarAction::SaveAs),
+                                    toolbar_button(
+                                        font.clone(),
+                                        "Save Bundle",
+                                        ToolbarAction::SaveBundle,
+                                    ),
+                                    toolbar_button(
+                                        font.clone(),
+                                        "Export Notes",
+                                        ToolbarAction::ExportNotes,
+                                    ),
+                                    toolbar_button(
+                                        font.clone(),
+                                        "Export Fountain",
+                                        ToolbarAction::ExportFountain,
+                                    ),
+                                    toolbar_button(
+                                        font.clone(),
+                                        "Export HTML",
+                                        ToolbarAction::ExportHtml,
+                                    ),
+                                    toolbar_button(
+                                        font.clone(),
+                                        "Export Text",
+                                        ToolbarAction::ExportText,
+                                    ),
                                     toolbar_button(font.clone(), "Zoom -", ToolbarAction::ZoomOut),
                                     toolbar_button(font.clone(), "Zoom +", ToolbarAction::ZoomIn),
                                     toolbar_button(font.clone(), "Settings", ToolbarAction::Settings),
@@ -218,6 +243,68 @@ fn setup(
                         SettingsAction::NonDialogueDoubleSpaceNewline,
                     ),
                     settings_toggle_button(font.clone(), SettingsAction::ShowSystemTitlebar),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleProcessedRightToLeft,
+                    ),
+                    settings_toggle_button(font.clone(), SettingsAction::ToggleSmoothScrolling),
+                    settings_toggle_button(font.clone(), SettingsAction::ToggleWrapActionLines),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleTrimTrailingWhitespaceOnSave,
+                    ),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleDialoguePageContinuations,
+                    ),
+                    settings_toggle_button(font.clone(), SettingsAction::ToggleAutoNumberScenes),
+                    settings_toggle_button(font.clone(), SettingsAction::ToggleWrapGuide),
+                    settings_toggle_button(font.clone(), SettingsAction::ToggleAutosaveOnFocusLoss),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleUnifiedProcessedEdit,
+                    ),
+                    settings_toggle_button(font.clone(), SettingsAction::ToggleProcessedCueRuler),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleElementWidthWarnings,
+                    ),
+                    settings_toggle_button(font.clone(), SettingsAction::ToggleFocusCurrentScene),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleSceneHeadingRequiresContent,
+                    ),
+                    settings_toggle_button(font.clone(), SettingsAction::ToggleProcessedKindLabels),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleAutoUppercaseCharacterCues,
+                    ),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleAutoCapitalizeSentences,
+                    ),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleAutoPairParentheses,
+                    ),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleDialogueSpansSingleBlank,
+                    ),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleSyncScrollPanes,
+                    ),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleAutoReloadExternalChanges,
+                    ),
+                    settings_toggle_button(
+                        font.clone(),
+                        SettingsAction::ToggleHyphenateExportedDialogue,
+                    ),
+                    processed_kind_visibility_row(font.clone()),
+                    settings_toggle_button(font.clone(), SettingsAction::TogglePageSize),
                     margin_setting_row(
                         font.clone(),
                         "Left margin (pt)",
@@ -246,6 +333,38 @@ fn setup(
                         SettingsAction::MarginBottomDecrease,
                         SettingsAction::MarginBottomIncrease,
                     ),
+                    settings_action_button(
+                        font.clone(),
+                        "Standard screenplay margins",
+                        SettingsAction::ScreenplayMargins,
+                    ),
+                    element_width_setting_row(
+                        font.clone(),
+                        "Dialogue width (columns)",
+                        WidthElement::Dialogue,
+                        SettingsAction::DialogueWidthDecrease,
+                        SettingsAction::DialogueWidthIncrease,
+                    ),
+                    element_width_setting_row(
+                        font.clone(),
+                        "Action width (columns)",
+                        WidthElement::Action,
+                        SettingsAction::ActionWidthDecrease,
+                        SettingsAction::ActionWidthIncrease,
+                    ),
+                    element_width_setting_row(
+                        font.clone(),
+                        "Wrap guide column",
+                        WidthElement::WrapGuide,
+                        SettingsAction::WrapGuideColumnDecrease,
+                        SettingsAction::WrapGuideColumnIncrease,
+                    ),
+                    indent_width_setting_row(font.clone(), 0),
+                    indent_width_setting_row(font.clone(), 1),
+                    indent_width_setting_row(font.clone(), 2),
+                    indent_width_setting_row(font.clone(), 3),
+                    indent_width_setting_row(font.clone(), 4),
+                    indent_width_setting_row(font.clone(), 5),
                     settings_action_button(font.clone(), "Theme", SettingsAction::OpenTheme),
                     settings_action_button(
                         font.clone(),
@@ -321,8 +440,30 @@ fn setup(
                     ),
                     keybind_setting_row(font.clone(), ShortcutAction::ToggleExplorer),
                     keybind_setting_row(font.clone(), ShortcutAction::ToggleTopMenu),
+                    keybind_setting_row(font.clone(), ShortcutAction::JoinLines),
+                    keybind_setting_row(font.clone(), ShortcutAction::SelectSection),
+                    keybind_setting_row(font.clone(), ShortcutAction::ToggleCharacterDialogue),
+                    keybind_setting_row(font.clone(), ShortcutAction::DuplicateScene),
+                    keybind_setting_row(font.clone(), ShortcutAction::NextDifferentElement),
+                    keybind_setting_row(font.clone(), ShortcutAction::PreviousDifferentElement),
+                    keybind_setting_row(font.clone(), ShortcutAction::PreviewForcedMarkers),
+                    keybind_setting_row(font.clone(), ShortcutAction::ToggleDistractionFree),
+                    keybind_setting_row(font.clone(), ShortcutAction::NextSentence),
+                    keybind_setting_row(font.clone(), ShortcutAction::PreviousSentence),
+                    keybind_setting_row(font.clone(), ShortcutAction::InsertPageBreak),
+                    keybind_setting_row(font.clone(), ShortcutAction::TogglePlainTextMode),
+                    keybind_row(font.clone(), "Cmd/Ctrl+C / X / V", "Copy, cut, paste"),
+                    keybind_setting_row(font.clone(), ShortcutAction::DeleteLine),
+                    keybind_setting_row(font.clone(), ShortcutAction::DuplicateLine),
+                    keybind_setting_row(font.clone(), ShortcutAction::OpenFind),
+                    keybind_setting_row(font.clone(), ShortcutAction::ToggleStats),
+                    keybind_setting_row(font.clone(), ShortcutAction::NextScene),
+                    keybind_setting_row(font.clone(), ShortcutAction::PreviousScene),
+                    keybind_setting_row(font.clone(), ShortcutAction::ToggleOutline),
                     keybind_row(font.clone(), "Arrow keys", "Move cursor"),
                     keybind_row(font.clone(), "Home / End", "Move to line start/end"),
+                    keybind_row(font.clone(), "Alt+Up / Alt+Down", "Move line up/down"),
+                    keybind_row(font.clone(), "Ctrl/Alt+Backspace / Delete", "Delete word"),
                     keybind_row(font.clone(), "Page Up / Page Down", "Move by viewport"),
                     keybind_row(font.clone(), "Escape", "Cancel middle-click autoscroll"),
                     (
@@ -420,6 +561,17 @@ fn setup(
                                     theme_color_row(font.clone(), ThemeColorTarget::ExplorerBackground),
                                     theme_color_row(font.clone(), ThemeColorTarget::ProcessedBackground),
                                     theme_color_row(font.clone(), ThemeColorTarget::SelectionBackground),
+                                    theme_color_row(font.clone(), ThemeColorTarget::WrapGuide),
+                                    theme_color_row(font.clone(), ThemeColorTarget::Element(0)),
+                                    theme_color_row(font.clone(), ThemeColorTarget::Element(1)),
+                                    theme_color_row(font.clone(), ThemeColorTarget::Element(2)),
+                                    theme_color_row(font.clone(), ThemeColorTarget::Element(3)),
+                                    theme_color_row(font.clone(), ThemeColorTarget::Element(4)),
+                                    theme_color_row(font.clone(), ThemeColorTarget::Element(5)),
+                                    theme_only_setting_button(
+                                        font.clone(),
+                                        SettingsAction::ToggleDarkMode,
+                                    ),
                                     theme_only_setting_button(
                                         font.clone(),
                                         SettingsAction::ToggleProcessedGlass,
@@ -618,6 +770,122 @@ fn setup(
                     ),
                 ],
             ));
+
+            root.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    right: px(24.0),
+                    top: px(64.0),
+                    max_width: px(520.0),
+                    padding: UiRect::all(px(12.0)),
+                    display: Display::None,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.96, 0.97, 0.98, 0.97)),
+                ZIndex(49),
+                ReclassifyPreviewOverlay,
+                children![(
+                    Text::new(""),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(COLOR_TEXT_MAIN),
+                    ReclassifyPreviewText,
+                )],
+            ));
+
+            root.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    right: px(24.0),
+                    top: px(64.0),
+                    max_width: px(520.0),
+                    padding: UiRect::axes(px(12.0), px(8.0)),
+                    display: Display::None,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.96, 0.97, 0.98, 0.97)),
+                ZIndex(49),
+                FindBarOverlay,
+                children![(
+                    Text::new(""),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(COLOR_TEXT_MAIN),
+                    FindBarText,
+                )],
+            ));
+
+            root.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    right: px(24.0),
+                    bottom: px(40.0),
+                    max_width: px(360.0),
+                    padding: UiRect::all(px(12.0)),
+                    display: Display::None,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.96, 0.97, 0.98, 0.97)),
+                ZIndex(49),
+                StatsOverlay,
+                children![(
+                    Text::new(""),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(COLOR_TEXT_MAIN),
+                    StatsText,
+                )],
+            ));
+
+            root.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: px(24.0),
+                    top: px(64.0),
+                    max_width: px(360.0),
+                    max_height: percent(80.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(px(12.0)),
+                    overflow: Overflow::clip(),
+                    display: Display::None,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.96, 0.97, 0.98, 0.97)),
+                ZIndex(49),
+                OutlineOverlay,
+            ))
+            .with_children(|list| {
+                for slot in 0..OUTLINE_ROW_CAPACITY {
+                    list.spawn((
+                        Button,
+                        OutlineRow { slot, line: None },
+                        Node {
+                            display: Display::None,
+                            padding: UiRect::vertical(px(2.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
+                        children![(
+                            Text::new(""),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 13.0,
+                                ..default()
+                            },
+                            TextColor(COLOR_TEXT_MAIN),
+                        )],
+                    ));
+                }
+            });
         });
 }
 
@@ -765,17 +1033,100 @@ fn resolve_workspace_asset_path(path: &str) -> Option<PathBuf> {
     None
 }
 
+fn setup_plain_wrap_guide(mut commands: Commands, body_query: Query<(Entity, &PanelBody)>) {
+    for (entity, panel_body) in body_query.iter() {
+        if panel_body.kind != PanelKind::Plain {
+            continue;
+        }
+
+        commands.entity(entity).with_children(|body| {
+            body.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: px(0.0),
+                    top: px(0.0),
+                    width: px(1.0),
+                    height: percent(100.0),
+                    display: Display::None,
+                    ..default()
+                },
+                BackgroundColor(Color::NONE),
+                ZIndex(3),
+                PlainWrapGuide,
+            ));
+        });
+    }
+}
+
 fn setup_processed_papers(
     mut commands: Commands,
     canvas_query: Query<(Entity, &PanelCanvas)>,
     paper_query: Query<(Entity, &PanelPaper)>,
     text_query: Query<(Entity, &PanelText)>,
+    body_query: Query<(Entity, &PanelBody)>,
     fonts: Res<EditorFonts>,
     checklist_icons: Res<ChecklistIcons>,
 ) {
     let regular_font = fonts.regular.clone();
+
+    for (entity, panel_body) in body_query.iter() {
+        if panel_body.kind != PanelKind::Processed {
+            continue;
+        }
+
+        let ruler_font = regular_font.clone();
+        commands.entity(entity).with_children(|body| {
+            body.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: px(0.0),
+                    top: px(0.0),
+                    width: percent(100.0),
+                    height: px(CUE_RULER_HEIGHT),
+                    display: Display::None,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.96, 0.96, 0.97, 0.92)),
+                ZIndex(6),
+                ProcessedCueRuler,
+            ))
+            .with_children(|ruler| {
+                for (kind, label) in CUE_RULER_MARKS {
+                    ruler.spawn((
+                        Node {
+                            position_type: PositionType::Absolute,
+                            top: px(0.0),
+                            width: px(1.0),
+                            height: px(CUE_RULER_HEIGHT),
+                            overflow: Overflow::visible(),
+                            ..default()
+                        },
+                        BackgroundColor(COLOR_KIND_LABEL),
+                        ProcessedCueRulerTick { kind },
+                        children![(
+                            Text::new(label),
+                            TextLayout::new_with_no_wrap(),
+                            TextFont {
+                                font: ruler_font.clone(),
+                                font_size: FONT_SIZE * 0.6,
+                                ..default()
+                            },
+                            TextColor(COLOR_TEXT_MUTED),
+                            Node {
+                                position_type: PositionType::Absolute,
+                                left: px(3.0),
+                                top: px(1.0),
+                                ..default()
+                            },
+                        )],
+                    ));
+                }
+            });
+        });
+    }
+
     let unchecked_icon = checklist_icons.unchecked.clone();
-    let span_capacity = processed_page_step_lines().max(1);
+    let span_capacity = max_processed_page_step_lines().max(1);
 
     for (entity, panel_canvas) in canvas_query.iter() {
         if panel_canvas.kind != PanelKind::Processed {
@@ -820,11 +1171,11 @@ fn setup_processed_papers(
                                     position_type: PositionType::Absolute,
                                     left: px(PAGE_TEXT_MARGIN_LEFT),
                                     top: px(PAGE_TEXT_MARGIN_TOP),
-                                    width: px((A4_WIDTH_POINTS
+                                    width: px((PageSize::A4.width_points()
                                         - PAGE_TEXT_MARGIN_LEFT
                                         - PAGE_TEXT_MARGIN_RIGHT)
                                         .max(1.0)),
-                                    height: px((A4_HEIGHT_POINTS
+                                    height: px((PageSize::A4.height_points()
                                         - PAGE_TEXT_MARGIN_TOP
                                         - PAGE_TEXT_MARGIN_BOTTOM)
                                         .max(1.0)),
@@ -848,6 +1199,8 @@ fn setup_processed_papers(
                                             },
                                             LineHeight::Px(LINE_HEIGHT),
                                             TextColor(COLOR_ACTION),
+                                            Underline,
+                                            UnderlineColor(Color::NONE),
                                             ProcessedPaperLineSpan {
                                                 slot,
                                                 line_offset,
@@ -875,6 +1228,47 @@ fn setup_processed_papers(
                                 ProcessedChecklistIcon { slot, line_offset },
                             ));
                         }
+
+                        paper.spawn((
+                            Text::new(""),
+                            TextLayout::new_with_no_wrap(),
+                            TextFont {
+                                font: slot_font.clone(),
+                                font_size: FONT_SIZE * 0.6,
+                                ..default()
+                            },
+                            LineHeight::Px(LINE_HEIGHT),
+                            TextColor(COLOR_KIND_LABEL),
+                            Node {
+                                position_type: PositionType::Absolute,
+                                left: px(0.0),
+                                top: px(PAGE_TEXT_MARGIN_TOP),
+                                ..default()
+                            },
+                            Visibility::Hidden,
+                            ZIndex(2),
+                            GlobalZIndex(1),
+                            ProcessedKindLabel { slot },
+                        ));
+
+                        paper.spawn((
+                            Text::new(""),
+                            TextLayout::new_with_no_wrap(),
+                            TextFont {
+                                font: slot_font.clone(),
+                                font_size: FONT_SIZE,
+                                ..default()
+                            },
+                            TextColor(COLOR_ACTION),
+                            Node {
+                                position_type: PositionType::Absolute,
+                                display: Display::None,
+                                ..default()
+                            },
+                            ZIndex(2),
+                            GlobalZIndex(1),
+                            ProcessedPageNumber { slot },
+                        ));
                     });
             }
         });
@@ -904,11 +1298,11 @@ fn setup_processed_papers(
                         position_type: PositionType::Absolute,
                         left: px(PAGE_TEXT_MARGIN_LEFT),
                         top: px(PAGE_TEXT_MARGIN_TOP),
-                        width: px((A4_WIDTH_POINTS
+                        width: px((PageSize::A4.width_points()
                             - PAGE_TEXT_MARGIN_LEFT
                             - PAGE_TEXT_MARGIN_RIGHT)
                             .max(1.0)),
-                        height: px((A4_HEIGHT_POINTS
+                        height: px((PageSize::A4.height_points()
                             - PAGE_TEXT_MARGIN_TOP
                             - PAGE_TEXT_MARGIN_BOTTOM)
                             .max(1.0)),
@@ -932,6 +1326,8 @@ fn setup_processed_papers(
                                 },
                                 LineHeight::Px(LINE_HEIGHT),
                                 TextColor(COLOR_ACTION),
+                                Underline,
+                                UnderlineColor(Color::NONE),
                                 ProcessedPaperLineSpan {
                                     slot,
                                     line_offset,
@@ -959,6 +1355,47 @@ fn setup_processed_papers(
                     ProcessedChecklistIcon { slot, line_offset },
                 ));
             }
+
+            paper.spawn((
+                Text::new(""),
+                TextLayout::new_with_no_wrap(),
+                TextFont {
+                    font: regular_font.clone(),
+                    font_size: FONT_SIZE * 0.6,
+                    ..default()
+                },
+                LineHeight::Px(LINE_HEIGHT),
+                TextColor(COLOR_KIND_LABEL),
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: px(0.0),
+                    top: px(PAGE_TEXT_MARGIN_TOP),
+                    ..default()
+                },
+                Visibility::Hidden,
+                ZIndex(2),
+                GlobalZIndex(1),
+                ProcessedKindLabel { slot },
+            ));
+
+            paper.spawn((
+                Text::new(""),
+                TextLayout::new_with_no_wrap(),
+                TextFont {
+                    font: regular_font.clone(),
+                    font_size: FONT_SIZE,
+                    ..default()
+                },
+                TextColor(COLOR_ACTION),
+                Node {
+                    position_type: PositionType::Absolute,
+                    display: Display::None,
+                    ..default()
+                },
+                ZIndex(2),
+                GlobalZIndex(1),
+                ProcessedPageNumber { slot },
+            ));
         });
     }
 
@@ -1117,6 +1554,36 @@ fn keybind_row(font: Handle<Font>, binding: &str, description: &str) -> impl Bun
     )
 }
 
+fn processed_kind_visibility_row(font: Handle<Font>) -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Row,
+            flex_wrap: FlexWrap::Wrap,
+            align_items: AlignItems::Center,
+            column_gap: px(8.0),
+            row_gap: px(6.0),
+            ..default()
+        },
+        children![
+            (
+                Text::new("Show in processed:"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(COLOR_TEXT_MAIN),
+            ),
+            settings_toggle_button(font.clone(), SettingsAction::ToggleKindVisibility(0)),
+            settings_toggle_button(font.clone(), SettingsAction::ToggleKindVisibility(1)),
+            settings_toggle_button(font.clone(), SettingsAction::ToggleKindVisibility(2)),
+            settings_toggle_button(font.clone(), SettingsAction::ToggleKindVisibility(3)),
+            settings_toggle_button(font.clone(), SettingsAction::ToggleKindVisibility(4)),
+            settings_toggle_button(font, SettingsAction::ToggleKindVisibility(5)),
+        ],
+    )
+}
+
 fn margin_setting_row(
     font: Handle<Font>,
     label: &str,
@@ -1157,18 +1624,68 @@ fn margin_setting_row(
     )
 }
 
-fn theme_color_row(font: Handle<Font>, target: ThemeColorTarget) -> impl Bundle {
+fn element_width_setting_row(
+    font: Handle<Font>,
+    label: &str,
+    element: WidthElement,
+    decrease_action: SettingsAction,
+    increase_action: SettingsAction,
+) -> impl Bundle {
     (
         Node {
             flex_direction: FlexDirection::Row,
             align_items: AlignItems::Center,
-            column_gap: px(10.0),
+            column_gap: px(8.0),
             ..default()
         },
-        ThemeColorRow { target },
         children![
             (
-                Text::new(""),
+                Text::new(label),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(COLOR_TEXT_MAIN),
+            ),
+            settings_action_button(font.clone(), "-", decrease_action),
+            (
+                Text::new(""),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(COLOR_TEXT_MAIN),
+                SettingElementWidthLabel { element },
+            ),
+            settings_action_button(font, "+", increase_action),
+        ],
+    )
+}
+
+fn indent_width_setting_row(font: Handle<Font>, index: usize) -> impl Bundle {
+    element_width_setting_row(
+        font,
+        INDENT_WIDTH_SETTINGS[index].1,
+        WidthElement::Indent(index),
+        SettingsAction::IndentWidthDecrease(index),
+        SettingsAction::IndentWidthIncrease(index),
+    )
+}
+
+fn theme_color_row(font: Handle<Font>, target: ThemeColorTarget) -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: px(10.0),
+            ..default()
+        },
+        ThemeColorRow { target },
+        children![
+            (
+                Text::new(""),
                 TextFont {
                     font: font.clone(),
                     font_size: 13.0,
@@ -1434,6 +1951,34 @@ fn panel_splitter_bundle(kind: PanelSplitter) -> impl Bundle {
     )
 }
 
+fn panel_scrollbar_bundle(kind: PanelKind) -> impl Bundle {
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            right: px(0.0),
+            top: px(0.0),
+            width: px(PANEL_SCROLLBAR_WIDTH),
+            height: percent(100.0),
+            ..default()
+        },
+        RelativeCursorPosition::default(),
+        Visibility::Hidden,
+        ZIndex(4),
+        PanelScrollbar { kind },
+        children![(
+            Node {
+                position_type: PositionType::Absolute,
+                left: px(2.0),
+                width: px(PANEL_SCROLLBAR_WIDTH - 4.0),
+                border_radius: BorderRadius::MAX,
+                ..default()
+            },
+            BackgroundColor(COLOR_SCROLLBAR_THUMB),
+            PanelScrollbarThumb { kind },
+        )],
+    )
+}
+
 fn panel_bundle(font: Handle<Font>, kind: PanelKind) -> impl Bundle {
     let body_color = match kind {
         PanelKind::Plain => COLOR_PANEL_BODY_PLAIN,
@@ -1465,80 +2010,87 @@ fn panel_bundle(font: Handle<Font>, kind: PanelKind) -> impl Bundle {
                 BackgroundColor(body_color),
                 RelativeCursorPosition::default(),
                 PanelBody { kind },
-                children![(
-                    Node {
-                        position_type: PositionType::Absolute,
-                        left: px(0.0),
-                        top: px(0.0),
-                        width: percent(100.0),
-                        height: percent(100.0),
-                        ..default()
-                    },
-                    UiTransform::default(),
-                    PanelCanvas { kind },
-                    children![
-                        (
-                            Node {
-                                position_type: PositionType::Absolute,
-                                overflow: Overflow::clip(),
-                                ..default()
-                            },
-                            UiTransform::default(),
-                            BackgroundColor(COLOR_PAPER),
-                            Visibility::Hidden,
-                            ZIndex(0),
-                            PanelPaper { kind, slot: 0 },
-                        ),
-                        (
-                            Node {
-                                position_type: PositionType::Absolute,
-                                left: px(0.0),
-                                top: px(0.0),
-                                width: percent(100.0),
-                                height: percent(100.0),
-                                overflow: Overflow::clip(),
-                                ..default()
-                            },
-                            PanelSelectionLayer { kind },
-                            ZIndex(1),
-                        ),
-                        (
-                            Node {
-                                position_type: PositionType::Absolute,
-                                left: px(TEXT_PADDING_X),
-                                top: px(TEXT_PADDING_Y),
-                                width: px(CARET_WIDTH),
-                                height: px(LINE_HEIGHT),
-                                ..default()
-                            },
-                            UiTransform::default(),
-                            BackgroundColor(Color::srgba(0.12, 0.12, 0.13, 0.35)),
-                            Visibility::Hidden,
-                            ZIndex(2),
-                            PanelCaret { kind },
-                        ),
-                        (
-                            Text::new(""),
-                            TextLayout::new_with_no_wrap(),
-                            TextFont {
-                                font: font.clone(),
-                                font_size: FONT_SIZE,
-                                ..default()
-                            },
-                            LineHeight::Px(LINE_HEIGHT),
-                            TextColor(COLOR_ACTION),
-                            Node {
-                                position_type: PositionType::Absolute,
-                                left: px(TEXT_PADDING_X),
-                                top: px(TEXT_PADDING_Y),
-                                ..default()
-                            },
-                            UiTransform::default(),
-                            ZIndex(3),
-                            PanelText { kind },
-                        )
-                    ],
-                )],
+                children![
+                    (
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: px(0.0),
+                            top: px(0.0),
+                            width: percent(100.0),
+                            height: percent(100.0),
+                            ..default()
+                        },
+                        UiTransform::default(),
+                        PanelCanvas { kind },
+                        children![
+                            (
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    overflow: Overflow::clip(),
+                                    ..default()
+                                },
+                                UiTransform::default(),
+                                BackgroundColor(COLOR_PAPER),
+                                Visibility::Hidden,
+                                ZIndex(0),
+                                PanelPaper { kind, slot: 0 },
+                            ),
+                            (
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    left: px(0.0),
+                                    top: px(0.0),
+                                    width: percent(100.0),
+                                    height: percent(100.0),
+                                    overflow: Overflow::clip(),
+                                    ..default()
+                                },
+                                PanelSelectionLayer { kind },
+                                ZIndex(1),
+                            ),
+                            (
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    left: px(TEXT_PADDING_X),
+                                    top: px(TEXT_PADDING_Y),
+                                    width: px(CARET_WIDTH),
+                                    height: px(LINE_HEIGHT),
+                                    ..default()
+                                },
+                                UiTransform::default(),
+                                BackgroundColor(LIGHT_PALETTE.plain_caret),
+                                Visibility::Hidden,
+                                ZIndex(2),
+                                PanelCaret { kind, slot: 0 },
+                            ),
+                            (
+                                Text::new(""),
+                                TextLayout::new_with_no_wrap(),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: FONT_SIZE,
+                                    ..default()
+                                },
+                                LineHeight::Px(LINE_HEIGHT),
+                                TextColor(COLOR_ACTION),
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    left: px(TEXT_PADDING_X),
+                                    top: px(TEXT_PADDING_Y),
+                                    ..default()
+                                },
+                                UiTransform::default(),
+                                ZIndex(3),
+                                PanelText { kind },
+                                children![
+                                    (TextSpan::new(""), PlainFocusSpan { part: 0 }),
+                                    (TextSpan::new(""), PlainFocusSpan { part: 1 }),
+                                ],
+                            )
+                        ],
+                    ),
+                    panel_scrollbar_bundle(kind),
+                ],
             )
         ],
     )
@@ -1576,6 +2128,21 @@ fn handle_toolbar_buttons(
                 open_workspace_dialog(&mut state, &mut dialogs, parent_handle)
             }
             ToolbarAction::SaveAs => open_save_dialog(&mut state, &mut dialogs, parent_handle),
+            ToolbarAction::SaveBundle => {
+                open_save_bundle_dialog(&mut state, &mut dialogs, parent_handle)
+            }
+            ToolbarAction::ExportNotes => {
+                open_export_notes_dialog(&mut state, &mut dialogs, parent_handle)
+            }
+            ToolbarAction::ExportFountain => {
+                open_export_fountain_dialog(&mut state, &mut dialogs, parent_handle)
+            }
+            ToolbarAction::ExportHtml => {
+                open_export_html_dialog(&mut state, &mut dialogs, parent_handle)
+            }
+            ToolbarAction::ExportText => {
+                open_export_text_dialog(&mut state, &mut dialogs, parent_handle)
+            }
             ToolbarAction::ZoomOut => {
                 let next_zoom = state.zoom - ZOOM_STEP;
                 set_zoom_preserving_processed_anchor(&mut state, processed_panel_size, next_zoom);
@@ -1595,10 +2162,11 @@ fn handle_toolbar_buttons(
 }
 
 fn style_toolbar_buttons(
+    state: Res<EditorState>,
+    mut styled_dark_mode: Local<Option<bool>>,
     mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
+        (Ref<Interaction>, &mut BackgroundColor),
         (
-            Changed<Interaction>,
             With<Button>,
             Or<(
                 With<ToolbarAction>,
@@ -1609,12 +2177,60 @@ fn style_toolbar_buttons(
         ),
     >,
 ) {
+    // A theme switch restyles every button, not just the ones being hovered.
+    let theme_switched = *styled_dark_mode != Some(state.dark_mode);
+    *styled_dark_mode = Some(state.dark_mode);
+    let palette = state.palette();
     for (interaction, mut color) in button_query.iter_mut() {
+        if !theme_switched && !interaction.is_changed() {
+            continue;
+        }
         color.0 = match *interaction {
-            Interaction::Pressed => BUTTON_PRESSED,
-            Interaction::Hovered => BUTTON_HOVER,
-            Interaction::None => BUTTON_NORMAL,
+            Interaction::Pressed => palette.button_pressed,
+            Interaction::Hovered => palette.button_hover,
+            Interaction::None => palette.button_normal,
+        };
+    }
+}
+
+// Chrome text is spawned with the light palette's colors. Those, and the other
+// palette's, are swapped for the active palette's on new entities and after a
+// theme switch. Text in other colors is left alone.
+fn sync_theme_palette_colors(
+    state: Res<EditorState>,
+    mut synced_dark_mode: Local<Option<bool>>,
+    mut text_color_query: Query<&mut TextColor>,
+    mut caret_query: Query<(&PanelCaret, &mut BackgroundColor)>,
+) {
+    let theme_switched = *synced_dark_mode != Some(state.dark_mode);
+    *synced_dark_mode = Some(state.dark_mode);
+    let palette = state.palette();
+    for (caret, mut color) in caret_query.iter_mut() {
+        // The processed caret sits on white paper in either theme.
+        let caret_color = match (state.caret_color, caret.kind) {
+            (Some(custom), _) => custom,
+            (None, PanelKind::Plain) => palette.plain_caret,
+            (None, PanelKind::Processed) => LIGHT_PALETTE.plain_caret,
+        };
+        if color.0 != caret_color {
+            color.0 = caret_color;
+        }
+    }
+    for mut text_color in text_color_query.iter_mut() {
+        if !theme_switched && !text_color.is_added() {
+            continue;
+        }
+        let color = text_color.0;
+        let themed = if [LIGHT_PALETTE.text_main, DARK_PALETTE.text_main].contains(&color) {
+            palette.text_main
+        } else if [LIGHT_PALETTE.text_muted, DARK_PALETTE.text_muted].contains(&color) {
+            palette.text_muted
+        } else {
+            continue;
         };
+        if themed != color {
+            text_color.0 = themed;
+        }
     }
 }
 
@@ -1710,6 +2326,47 @@ fn handle_settings_buttons(
                     if state.settings_glass { "ON" } else { "OFF" }
                 );
             }
+            SettingsAction::ToggleDarkMode => {
+                let dark_mode = !state.dark_mode;
+                set_dark_mode(&mut state, dark_mode);
+                theme_changed = true;
+                state.status_message = format!(
+                    "Dark mode: {}",
+                    if state.dark_mode { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::DialogueWidthDecrease => {
+                adjust_element_width(&mut state, WidthElement::Dialogue, -1);
+                settings_changed = true;
+            }
+            SettingsAction::DialogueWidthIncrease => {
+                adjust_element_width(&mut state, WidthElement::Dialogue, 1);
+                settings_changed = true;
+            }
+            SettingsAction::ActionWidthDecrease => {
+                adjust_element_width(&mut state, WidthElement::Action, -1);
+                settings_changed = true;
+            }
+            SettingsAction::ActionWidthIncrease => {
+                adjust_element_width(&mut state, WidthElement::Action, 1);
+                settings_changed = true;
+            }
+            SettingsAction::WrapGuideColumnDecrease => {
+                adjust_element_width(&mut state, WidthElement::WrapGuide, -1);
+                settings_changed = true;
+            }
+            SettingsAction::WrapGuideColumnIncrease => {
+                adjust_element_width(&mut state, WidthElement::WrapGuide, 1);
+                settings_changed = true;
+            }
+            SettingsAction::IndentWidthDecrease(index) => {
+                adjust_element_width(&mut state, WidthElement::Indent(*index), -1);
+                settings_changed = true;
+            }
+            SettingsAction::IndentWidthIncrease(index) => {
+                adjust_element_width(&mut state, WidthElement::Indent(*index), 1);
+                settings_changed = true;
+            }
             SettingsAction::MarginLeftDecrease => {
                 adjust_page_margin(&mut state, MarginEdge::Left, -PAGE_MARGIN_STEP);
                 settings_changed = true;
@@ -1742,6 +2399,208 @@ fn handle_settings_buttons(
                 adjust_page_margin(&mut state, MarginEdge::Bottom, PAGE_MARGIN_STEP);
                 settings_changed = true;
             }
+            SettingsAction::ToggleKindVisibility(index) => {
+                let Some((kind, _, label)) = PROCESSED_KIND_TOGGLES.get(*index) else {
+                    continue;
+                };
+                let visible = state.toggle_processed_kind_visibility(kind);
+                // Hiding kinds changes every page break, so the whole cache is stale.
+                state.processed_cache = None;
+                settings_changed = true;
+                state.status_message = format!(
+                    "{label} in processed pane: {}",
+                    if visible { "SHOWN" } else { "HIDDEN" }
+                );
+            }
+            SettingsAction::ToggleProcessedRightToLeft => {
+                state.processed_right_to_left = !state.processed_right_to_left;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Right-to-left processed pane: {}",
+                    if state.processed_right_to_left { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleSmoothScrolling => {
+                state.smooth_scrolling = !state.smooth_scrolling;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Smooth scrolling (Plain pane): {}",
+                    if state.smooth_scrolling { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleWrapActionLines => {
+                state.wrap_action_lines = !state.wrap_action_lines;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Wrap action lines (Plain pane): {}",
+                    if state.wrap_action_lines { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleTrimTrailingWhitespaceOnSave => {
+                state.trim_trailing_whitespace_on_save = !state.trim_trailing_whitespace_on_save;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Trim trailing whitespace on save: {}",
+                    if state.trim_trailing_whitespace_on_save { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleDialoguePageContinuations => {
+                state.dialogue_page_continuations = !state.dialogue_page_continuations;
+                state.processed_cache = None;
+                settings_changed = true;
+                state.status_message = format!(
+                    "(MORE) / (CONT'D) at page breaks: {}",
+                    if state.dialogue_page_continuations { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleAutoNumberScenes => {
+                state.auto_number_scenes = !state.auto_number_scenes;
+                state.processed_cache = None;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Auto-number scenes: {}",
+                    if state.auto_number_scenes { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleWrapGuide => {
+                state.show_wrap_guide = !state.show_wrap_guide;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Wrap guide: {}",
+                    if state.show_wrap_guide { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleAutosaveOnFocusLoss => {
+                state.autosave_on_focus_loss = !state.autosave_on_focus_loss;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Autosave on focus loss: {}",
+                    if state.autosave_on_focus_loss { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleUnifiedProcessedEdit => {
+                state.unified_processed_edit = !state.unified_processed_edit;
+                let mode = if state.unified_processed_edit {
+                    DisplayMode::Processed
+                } else {
+                    DisplayMode::Split
+                };
+                state.set_display_mode(mode);
+                settings_changed = true;
+                state.status_message = format!(
+                    "Unified processed editing: {}",
+                    if state.unified_processed_edit { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleProcessedCueRuler => {
+                state.processed_cue_ruler = !state.processed_cue_ruler;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Element column ruler: {}",
+                    if state.processed_cue_ruler { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleElementWidthWarnings => {
+                state.element_width_warnings = !state.element_width_warnings;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Dialogue/action width warnings: {}",
+                    if state.element_width_warnings { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleFocusCurrentScene => {
+                state.focus_current_scene = !state.focus_current_scene;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Focus current scene: {}",
+                    if state.focus_current_scene { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleSceneHeadingRequiresContent => {
+                state.scene_heading_requires_content = !state.scene_heading_requires_content;
+                state.reparse();
+                settings_changed = true;
+                state.status_message = format!(
+                    "Scene heading needs location: {}",
+                    if state.scene_heading_requires_content { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleProcessedKindLabels => {
+                state.processed_kind_labels = !state.processed_kind_labels;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Element labels in margin: {}",
+                    if state.processed_kind_labels { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleAutoUppercaseCharacterCues => {
+                state.auto_uppercase_character_cues = !state.auto_uppercase_character_cues;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Uppercase character cues on Enter: {}",
+                    if state.auto_uppercase_character_cues { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleAutoCapitalizeSentences => {
+                state.auto_capitalize_sentences = !state.auto_capitalize_sentences;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Capitalize action sentences: {}",
+                    if state.auto_capitalize_sentences { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleAutoPairParentheses => {
+                state.auto_pair_parentheses = !state.auto_pair_parentheses;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Auto-close dialogue parentheses: {}",
+                    if state.auto_pair_parentheses { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleDialogueSpansSingleBlank => {
+                state.dialogue_spans_single_blank = !state.dialogue_spans_single_blank;
+                state.reparse();
+                settings_changed = true;
+                state.status_message = format!(
+                    "Blank line inside dialogue keeps the speech: {}",
+                    if state.dialogue_spans_single_blank { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleSyncScrollPanes => {
+                state.sync_scroll_panes = !state.sync_scroll_panes;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Synced scrolling: {}",
+                    if state.sync_scroll_panes { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleAutoReloadExternalChanges => {
+                state.auto_reload_external_changes = !state.auto_reload_external_changes;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Auto-reload external changes: {}",
+                    if state.auto_reload_external_changes { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::ToggleHyphenateExportedDialogue => {
+                state.hyphenate_exported_dialogue = !state.hyphenate_exported_dialogue;
+                settings_changed = true;
+                state.status_message = format!(
+                    "Hyphenate exported dialogue: {}",
+                    if state.hyphenate_exported_dialogue { "ON" } else { "OFF" }
+                );
+            }
+            SettingsAction::TogglePageSize => {
+                state.page_size = state.page_size.next();
+                normalize_page_margins(&mut state);
+                settings_changed = true;
+                state.status_message = format!("Page size: {}", state.page_size.label());
+            }
+            SettingsAction::ScreenplayMargins => {
+                apply_screenplay_page_margins(&mut state);
+                settings_changed = true;
+                state.status_message = "Applied standard screenplay margins.".to_string();
+            }
             SettingsAction::LinkHoverHsvValueDecrease => {
                 state.link_hover_hsv_value_adjustment -= LINK_HOVER_HSV_VALUE_STEP;
                 sync_theme_colors(&mut state);
@@ -1909,6 +2768,14 @@ fn handle_theme_color_picker_input(
     }
 
     if theme_changed {
+        // Element colors belong to the editor settings rather than the theme.
+        if matches!(state.theme_color_target, ThemeColorTarget::Element(_)) {
+            let persistent = persistent_settings_from_state(&state);
+            if let Err(error) = save_persistent_settings(&persistent) {
+                state.status_message = format!("Settings save failed: {error}");
+            }
+            return;
+        }
         let theme = theme_settings_from_state(&state);
         if let Err(error) = save_theme_settings(&theme) {
             state.status_message = format!("Theme save failed: {error}");
@@ -2075,16 +2942,17 @@ fn sync_glass_surfaces(
         };
     }
 
+    let palette = state.palette();
     for (panel_root, mut color) in color_queries.p3().iter_mut() {
         color.0 = match panel_root.kind {
-            PanelKind::Plain => COLOR_PANEL_BG,
+            PanelKind::Plain => palette.panel_bg,
             PanelKind::Processed => Color::NONE,
         };
     }
 
     for (panel_body, mut color) in color_queries.p4().iter_mut() {
         color.0 = match panel_body.kind {
-            PanelKind::Plain => COLOR_PANEL_BODY_PLAIN,
+            PanelKind::Plain => palette.panel_body_plain,
             PanelKind::Processed if processed_glass_active => Color::NONE,
             PanelKind::Processed => state.processed_bg_color,
         };
@@ -2101,9 +2969,10 @@ fn sync_glass_surfaces(
 
 fn sync_top_menu_visibility(
     state: Res<EditorState>,
-    mut top_menu_query: Query<&mut Node, With<TopMenuSection>>,
+    mut top_menu_query: Query<&mut Node, (With<TopMenuSection>, Without<StatusLineRoot>)>,
+    mut status_line_query: Query<&mut Node, (With<StatusLineRoot>, Without<TopMenuSection>)>,
 ) {
-    let display = if state.top_menu_collapsed {
+    let display = if state.top_menu_hidden() {
         Display::None
     } else {
         Display::Flex
@@ -2112,6 +2981,15 @@ fn sync_top_menu_visibility(
     for mut node in top_menu_query.iter_mut() {
         node.display = display;
     }
+
+    let status_display = if state.distraction_free {
+        Display::None
+    } else {
+        Display::Flex
+    };
+    for mut node in status_line_query.iter_mut() {
+        node.display = status_display;
+    }
 }
 
 fn sync_rounded_window_surfaces(
@@ -2163,7 +3041,7 @@ fn sync_rounded_window_surfaces(
     let round_window = !state.show_system_titlebar;
     let editor_screen_active = *screen_state.get() == UiScreenState::Editor;
     let editor_top_radius_active =
-        round_window && editor_screen_active && state.top_menu_collapsed;
+        round_window && editor_screen_active && state.top_menu_hidden();
     let clipped_overflow = if round_window {
         window_surface_overflow(false)
     } else {
@@ -2207,11 +3085,12 @@ fn sync_rounded_window_surfaces(
     }
 
     if let Ok(mut top_menu) = node_queries.p4().single_mut() {
-        top_menu.border_radius = if round_window && editor_screen_active && !state.top_menu_collapsed {
-            window_surface_top_border_radius(true, true)
-        } else {
-            BorderRadius::ZERO
-        };
+        top_menu.border_radius =
+            if round_window && editor_screen_active && !state.top_menu_hidden() {
+                window_surface_top_border_radius(true, true)
+            } else {
+                BorderRadius::ZERO
+            };
         top_menu.overflow = clipped_overflow;
     }
 
@@ -2328,6 +3207,16 @@ fn sync_settings_ui(
             Without<ThemeColorLabel>,
         ),
     >,
+    mut element_width_label_query: Query<
+        (&SettingElementWidthLabel, &mut Text),
+        (
+            Without<SettingToggleLabel>,
+            Without<SettingMarginLabel>,
+            Without<KeybindBindingLabel>,
+            Without<ThemeColorLabel>,
+            Without<ThemeColorValueLabel>,
+        ),
+    >,
 ) {
     if let Ok(mut editor_root) = editor_root_query.single_mut() {
         editor_root.display = if *screen_state.get() == UiScreenState::Editor {
@@ -2363,6 +3252,12 @@ fn sync_settings_ui(
 
     for (label, mut text) in toggle_label_query.iter_mut() {
         **text = match label.action {
+            SettingsAction::ToggleKindVisibility(index) => {
+                PROCESSED_KIND_TOGGLES.get(index).map_or_else(String::new, |(kind, _, label)| {
+                    let mark = if state.processed_kind_visible(kind) { "x" } else { " " };
+                    format!("[{mark}] {label}")
+                })
+            }
             SettingsAction::DialogueDoubleSpaceNewline => format!(
                 "Double space as newline in dialogue (processed modes): {}",
                 if state.dialogue_double_space_newline {
@@ -2387,6 +3282,95 @@ fn sync_settings_ui(
                     "OFF"
                 }
             ),
+            SettingsAction::ToggleProcessedRightToLeft => format!(
+                "Right-to-left processed pane: {}",
+                if state.processed_right_to_left {
+                    "ON"
+                } else {
+                    "OFF"
+                }
+            ),
+            SettingsAction::ToggleSmoothScrolling => format!(
+                "Smooth scrolling (Plain pane): {}",
+                if state.smooth_scrolling { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleWrapActionLines => format!(
+                "Wrap action lines (Plain pane): {}",
+                if state.wrap_action_lines { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleTrimTrailingWhitespaceOnSave => format!(
+                "Trim trailing whitespace on save: {}",
+                if state.trim_trailing_whitespace_on_save { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleDialoguePageContinuations => format!(
+                "(MORE) / (CONT'D) at page breaks: {}",
+                if state.dialogue_page_continuations { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleAutoNumberScenes => format!(
+                "Auto-number scenes: {}",
+                if state.auto_number_scenes { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleWrapGuide => format!(
+                "Wrap guide: {}",
+                if state.show_wrap_guide { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleAutosaveOnFocusLoss => format!(
+                "Autosave on focus loss: {}",
+                if state.autosave_on_focus_loss { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleUnifiedProcessedEdit => format!(
+                "Unified processed editing: {}",
+                if state.unified_processed_edit { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleProcessedCueRuler => format!(
+                "Element column ruler: {}",
+                if state.processed_cue_ruler { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleElementWidthWarnings => format!(
+                "Dialogue/action width warnings: {}",
+                if state.element_width_warnings { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleFocusCurrentScene => format!(
+                "Focus current scene: {}",
+                if state.focus_current_scene { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleSceneHeadingRequiresContent => format!(
+                "Scene heading needs location: {}",
+                if state.scene_heading_requires_content { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleProcessedKindLabels => format!(
+                "Element labels in margin: {}",
+                if state.processed_kind_labels { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleAutoUppercaseCharacterCues => format!(
+                "Uppercase character cues on Enter: {}",
+                if state.auto_uppercase_character_cues { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleAutoCapitalizeSentences => format!(
+                "Capitalize action sentences: {}",
+                if state.auto_capitalize_sentences { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleAutoPairParentheses => format!(
+                "Auto-close dialogue parentheses: {}",
+                if state.auto_pair_parentheses { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleDialogueSpansSingleBlank => format!(
+                "Blank line inside dialogue keeps the speech: {}",
+                if state.dialogue_spans_single_blank { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleSyncScrollPanes => format!(
+                "Synced scrolling: {}",
+                if state.sync_scroll_panes { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleAutoReloadExternalChanges => format!(
+                "Auto-reload external changes: {}",
+                if state.auto_reload_external_changes { "ON" } else { "OFF" }
+            ),
+            SettingsAction::ToggleHyphenateExportedDialogue => format!(
+                "Hyphenate exported dialogue: {}",
+                if state.hyphenate_exported_dialogue { "ON" } else { "OFF" }
+            ),
+            SettingsAction::TogglePageSize => format!("Page size: {}", state.page_size.label()),
             SettingsAction::ToggleProcessedGlass => format!(
                 "Processed background glass: {}",
                 if state.processed_glass { "ON" } else { "OFF" }
@@ -2399,6 +3383,10 @@ fn sync_settings_ui(
                 "Settings glass: {}",
                 if state.settings_glass { "ON" } else { "OFF" }
             ),
+            SettingsAction::ToggleDarkMode => format!(
+                "Dark mode: {}",
+                if state.dark_mode { "ON" } else { "OFF" }
+            ),
             _ => String::new(),
         };
     }
@@ -2413,6 +3401,11 @@ fn sync_settings_ui(
         **text = format!("{value:.1} pt");
     }
 
+    for (label, mut text) in element_width_label_query.iter_mut() {
+        let columns = element_width_columns(&state, label.element);
+        **text = format!("{columns} cols");
+    }
+
     for (label, mut text) in keybind_label_query.iter_mut() {
         **text = if state.pending_keybind_capture == Some(label.action) {
             "Press key...".to_string()
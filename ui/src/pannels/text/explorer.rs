@@ -136,7 +136,7 @@ impl EditorState {
             return;
         };
 
-        self.load_from_path(entry.path.clone());
+        self.request_load(entry.path.clone());
     }
 
     fn toggle_workspace_folder(&mut self, folder_key: &str) {
@@ -547,7 +547,7 @@ fn sync_workspace_sidebar(
                     let text_color = if state.workspace_selected == Some(file_index) {
                         COLOR_WORKSPACE_FILE_SELECTED
                     } else {
-                        COLOR_WORKSPACE_FILE
+                        state.palette().text_main
                     };
 
                     parent.spawn((
@@ -605,7 +605,7 @@ fn style_workspace_file_entry_text(
                 if state.workspace_selected == Some(workspace_file_button.index) {
                     COLOR_WORKSPACE_FILE_SELECTED
                 } else {
-                    COLOR_WORKSPACE_FILE
+                    state.palette().text_main
                 }
             }
         };
@@ -0,0 +1,101 @@
+// The outline keeps a fixed pool of rows and only rewrites their text, so a
+// reparse never despawns anything and the list does not flicker while typing.
+const OUTLINE_ROW_CAPACITY: usize = 40;
+const OUTLINE_DEPTH_INDENT: f32 = 12.0;
+
+#[derive(Component)]
+struct OutlineOverlay;
+
+// `line` is the source line the row jumps to; None while the slot is unused.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+struct OutlineRow {
+    slot: usize,
+    line: Option<usize>,
+}
+
+// First entry shown by the pool, chosen so the caret's entry stays in view.
+fn outline_window_start(entry_count: usize, current: Option<usize>) -> usize {
+    let max_start = entry_count.saturating_sub(OUTLINE_ROW_CAPACITY);
+    current
+        .map_or(0, |index| index.saturating_sub(OUTLINE_ROW_CAPACITY / 2))
+        .min(max_start)
+}
+
+fn sync_outline_overlay(
+    state: Res<EditorState>,
+    mut overlay_query: Query<&mut Node, (With<OutlineOverlay>, Without<OutlineRow>)>,
+    mut row_query: Query<(&mut OutlineRow, &mut Node, &Children)>,
+    mut text_query: Query<(&mut Text, &mut TextColor)>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+
+    let Ok(mut overlay) = overlay_query.single_mut() else {
+        return;
+    };
+    if !state.outline_open {
+        overlay.display = Display::None;
+        return;
+    }
+    overlay.display = Display::Flex;
+
+    let entries = outline_entries(&state.parsed);
+    let caret_line = state.cursor.position.line;
+    let current = entries.iter().rposition(|entry| entry.line <= caret_line);
+    let start = outline_window_start(entries.len(), current);
+
+    for (mut row, mut node, children) in row_query.iter_mut() {
+        let index = start + row.slot;
+        let Some(entry) = entries.get(index) else {
+            row.line = None;
+            node.display = Display::None;
+            continue;
+        };
+
+        row.line = Some(entry.line);
+        node.display = Display::Flex;
+        node.padding.left = px(entry.depth as f32 * OUTLINE_DEPTH_INDENT);
+        let color = if Some(index) == current {
+            COLOR_WORKSPACE_FILE_SELECTED
+        } else {
+            state.palette().text_main
+        };
+        for child in children.iter() {
+            if let Ok((mut text, mut text_color)) = text_query.get_mut(child) {
+                if text.0 != entry.label {
+                    text.0.clone_from(&entry.label);
+                }
+                text_color.0 = color;
+            }
+        }
+    }
+}
+
+fn handle_outline_row_buttons(
+    interaction_query: Query<(&Interaction, &OutlineRow), Changed<Interaction>>,
+    body_query: Query<(&PanelBody, &ComputedNode)>,
+    mut state: ResMut<EditorState>,
+) {
+    for (interaction, row) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(line) = row.line else {
+            continue;
+        };
+
+        let processed_panel_size = body_query
+            .iter()
+            .find(|(panel, _)| panel.kind == PanelKind::Processed)
+            .map(|(_, computed)| computed.size() * computed.inverse_scale_factor());
+        let visible_lines = viewport_lines(
+            &body_query,
+            state.display_mode,
+            state.measured_line_step,
+            scaled_text_padding_y(&state),
+        );
+        state.set_cursor(Position { line, column: 0 }, true);
+        apply_cursor_follow_scroll_policy(&mut state, processed_panel_size, visible_lines);
+    }
+}
@@ -103,6 +103,44 @@ fn apply_processed_panel_vertical_scroll(
     actual_whole_lines != 0 || leftover_px.abs() > f32::EPSILON
 }
 
+// Points the processed view at the first visual row of `processed_top_line`.
+fn align_processed_top_visual(state: &mut EditorState, processed_panel_size: Option<Vec2>) {
+    let Some(panel_size) = processed_panel_size else {
+        return;
+    };
+
+    let processed_layout = processed_page_layout(panel_size, state);
+    let all_lines = processed_display_lines(
+        state,
+        processed_layout.wrap_columns,
+        processed_layout.lines_per_page,
+        processed_layout.spacer_lines,
+    );
+    if all_lines.is_empty() {
+        state.processed_top_visual = 0;
+    } else {
+        state.processed_top_visual =
+            first_visual_index_for_source_line(&all_lines, state.processed_top_line).unwrap_or(0);
+    }
+}
+
+// In synced scrolling mode a Plain-driven scroll pins the first visual row of the
+// plain top line to the top edge of the Processed pane, carrying over the smooth
+// scroll fraction so both panes sit at the same offset within that line.
+fn sync_processed_top_to_plain(state: &mut EditorState, all_lines: &[ProcessedVisualLine]) {
+    if !state.sync_scroll_panes || state.focused_panel != PanelKind::Plain || all_lines.is_empty()
+    {
+        return;
+    }
+
+    state.processed_top_line = state.top_line;
+    state.clamp_processed_top_line();
+    state.processed_top_visual =
+        first_visual_index_for_source_line(all_lines, state.processed_top_line).unwrap_or(0);
+    state.processed_zoom_anchor_bias_px =
+        -state.plain_scroll_fraction * scaled_line_height(state).max(1.0);
+}
+
 fn apply_cursor_follow_scroll_policy(
     state: &mut EditorState,
     processed_panel_size: Option<Vec2>,
@@ -115,23 +153,7 @@ fn apply_cursor_follow_scroll_policy(
             state.processed_top_line = state.top_line;
             state.clamp_processed_top_line();
             state.processed_zoom_anchor_bias_px = 0.0;
-
-            if let Some(panel_size) = processed_panel_size {
-                let processed_layout = processed_page_layout(panel_size, state);
-                let all_lines = processed_display_lines(
-                    state,
-                    processed_layout.wrap_columns,
-                    processed_layout.lines_per_page,
-                    processed_layout.spacer_lines,
-                );
-                if all_lines.is_empty() {
-                    state.processed_top_visual = 0;
-                } else {
-                    state.processed_top_visual =
-                        first_visual_index_for_source_line(&all_lines, state.processed_top_line)
-                            .unwrap_or(0);
-                }
-            }
+            align_processed_top_visual(state, processed_panel_size);
         }
         PanelKind::Processed => {
             // Processed is the anchor: adjust only plain top-line.
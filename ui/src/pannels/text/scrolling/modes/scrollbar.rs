@@ -0,0 +1,229 @@
+// Thumb placement as fractions of the track height.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ScrollbarMetrics {
+    thumb_top: f32,
+    thumb_height: f32,
+}
+
+// None when the whole document fits, which hides the scrollbar. The thumb covers
+// the visible share of the document and travels the track as `position` goes from
+// the first line to the last one at the top of the pane.
+fn scrollbar_metrics(
+    line_count: usize,
+    visible_lines: usize,
+    position: f32,
+) -> Option<ScrollbarMetrics> {
+    if line_count <= visible_lines {
+        return None;
+    }
+
+    let max_top = line_count.saturating_sub(1).max(1) as f32;
+    let range = max_top + visible_lines as f32;
+    let thumb_height = (visible_lines as f32 / range).clamp(PANEL_SCROLLBAR_MIN_THUMB, 1.0);
+    let progress = (position / max_top).clamp(0.0, 1.0);
+    Some(ScrollbarMetrics {
+        thumb_top: progress * (1.0 - thumb_height),
+        thumb_height,
+    })
+}
+
+// The top line a thumb grabbed at `grab_offset` lands on with the cursor at
+// `cursor_y`, both as fractions of the track.
+fn scrollbar_drag_target_line(
+    metrics: ScrollbarMetrics,
+    line_count: usize,
+    cursor_y: f32,
+    grab_offset: f32,
+) -> usize {
+    let travel = (1.0 - metrics.thumb_height).max(f32::EPSILON);
+    let progress = ((cursor_y - grab_offset) / travel).clamp(0.0, 1.0);
+    (progress * line_count.saturating_sub(1) as f32).round() as usize
+}
+
+fn scrollbar_position(state: &EditorState, kind: PanelKind) -> f32 {
+    match kind {
+        PanelKind::Plain => state.top_line as f32 + state.plain_scroll_fraction,
+        PanelKind::Processed => state.processed_top_line as f32,
+    }
+}
+
+// Jumps both panels to `line`, keeping the plain top-line as the anchor the way
+// cursor-follow scrolling does.
+fn scroll_panels_to_line(
+    state: &mut EditorState,
+    processed_panel_size: Option<Vec2>,
+    line: usize,
+    visible_lines: usize,
+) -> bool {
+    let before = (state.top_line, state.processed_top_line);
+    state.top_line = line.min(state.max_top_line(visible_lines));
+    state.plain_scroll_fraction = 0.0;
+    state.processed_top_line = state.top_line;
+    state.clamp_processed_top_line();
+    state.processed_zoom_anchor_bias_px = 0.0;
+    align_processed_top_visual(state, processed_panel_size);
+    state.clamp_cursor_to_visible_range(visible_lines);
+    (state.top_line, state.processed_top_line) != before
+}
+
+fn handle_scrollbar_drag(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    body_query: Query<(&PanelBody, &ComputedNode)>,
+    track_query: Query<(&PanelScrollbar, &RelativeCursorPosition, &Visibility)>,
+    mut splitter_drag: ResMut<PanelSplitterDragState>,
+    mut drag_state: ResMut<ScrollbarDragState>,
+    mut state: ResMut<EditorState>,
+) {
+    if !mouse_buttons.pressed(MouseButton::Left) {
+        drag_state.active = None;
+        drag_state.grab_offset = None;
+        return;
+    }
+
+    let visible_lines = viewport_lines(
+        &body_query,
+        state.display_mode,
+        state.measured_line_step,
+        scaled_text_padding_y(&state),
+    );
+    let processed_panel_size = body_query
+        .iter()
+        .find(|(panel, _)| panel.kind == PanelKind::Processed)
+        .map(|(_, computed)| computed.size() * computed.inverse_scale_factor());
+    let line_count = state.document.line_count();
+
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        let pressed = track_query.iter().find_map(|(track, relative_cursor, visibility)| {
+            if *visibility == Visibility::Hidden
+                || !state.panel_visible(track.kind)
+                || !relative_cursor.cursor_over()
+            {
+                return None;
+            }
+            relative_cursor
+                .normalized
+                .map(|normalized| (track.kind, normalized.y + 0.5))
+        });
+        let Some((kind, cursor_y)) = pressed else {
+            return;
+        };
+        let Some(metrics) =
+            scrollbar_metrics(line_count, visible_lines, scrollbar_position(&state, kind))
+        else {
+            return;
+        };
+
+        // The splitter pick radius reaches over the Plain scrollbar; the press is ours.
+        splitter_drag.active = None;
+        drag_state.active = Some(kind);
+        state.focused_panel = kind;
+
+        let top_line = scrollbar_position(&state, kind) as usize;
+        let page_step = visible_lines.saturating_sub(1).max(1);
+        let target_line = if cursor_y < metrics.thumb_top {
+            top_line.saturating_sub(page_step)
+        } else if cursor_y > metrics.thumb_top + metrics.thumb_height {
+            top_line.saturating_add(page_step)
+        } else {
+            drag_state.grab_offset = Some(cursor_y - metrics.thumb_top);
+            return;
+        };
+        if scroll_panels_to_line(&mut state, processed_panel_size, target_line, visible_lines) {
+            state.reset_blink();
+        }
+        return;
+    }
+
+    let (Some(kind), Some(grab_offset)) = (drag_state.active, drag_state.grab_offset) else {
+        return;
+    };
+    let Some(cursor_y) = track_query
+        .iter()
+        .find(|(track, _, _)| track.kind == kind)
+        .and_then(|(_, relative_cursor, _)| relative_cursor.normalized)
+        .map(|normalized| normalized.y + 0.5)
+    else {
+        return;
+    };
+    let Some(metrics) =
+        scrollbar_metrics(line_count, visible_lines, scrollbar_position(&state, kind))
+    else {
+        return;
+    };
+
+    let target_line = scrollbar_drag_target_line(metrics, line_count, cursor_y, grab_offset);
+    if scroll_panels_to_line(&mut state, processed_panel_size, target_line, visible_lines) {
+        state.reset_blink();
+    }
+}
+
+fn sync_panel_scrollbars(
+    state: Res<EditorState>,
+    drag_state: Res<ScrollbarDragState>,
+    body_query: Query<(&PanelBody, &ComputedNode)>,
+    mut track_query: Query<(&PanelScrollbar, &RelativeCursorPosition, &mut Visibility)>,
+    mut thumb_query: Query<(&PanelScrollbarThumb, &mut Node, &mut BackgroundColor)>,
+) {
+    let visible_lines = viewport_lines(
+        &body_query,
+        state.display_mode,
+        state.measured_line_step,
+        scaled_text_padding_y(&state),
+    );
+    let line_count = state.document.line_count();
+    let scrollable = line_count > visible_lines;
+    let mut hovered = None;
+
+    for (track, relative_cursor, mut visibility) in track_query.iter_mut() {
+        *visibility = if scrollable {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if relative_cursor.cursor_over() {
+            hovered = Some(track.kind);
+        }
+    }
+
+    for (thumb, mut node, mut color) in thumb_query.iter_mut() {
+        let position = scrollbar_position(&state, thumb.kind);
+        let Some(metrics) = scrollbar_metrics(line_count, visible_lines, position) else {
+            continue;
+        };
+
+        node.top = percent(metrics.thumb_top * 100.0);
+        node.height = percent(metrics.thumb_height * 100.0);
+        color.0 = if drag_state.active == Some(thumb.kind) {
+            COLOR_SCROLLBAR_THUMB_ACTIVE
+        } else if hovered == Some(thumb.kind) {
+            COLOR_SCROLLBAR_THUMB_HOVER
+        } else {
+            COLOR_SCROLLBAR_THUMB
+        };
+    }
+}
+
+#[cfg(test)]
+mod scrollbar_tests {
+    use super::*;
+
+    #[test]
+    fn thumb_spans_the_visible_share_and_follows_the_top_line() {
+        assert_eq!(scrollbar_metrics(20, 30, 0.0), None);
+
+        let top = scrollbar_metrics(101, 25, 0.0).unwrap();
+        assert_eq!(top.thumb_top, 0.0);
+        assert!((top.thumb_height - 0.2).abs() < 1e-6);
+
+        let bottom = scrollbar_metrics(101, 25, 100.0).unwrap();
+        assert!((bottom.thumb_top + bottom.thumb_height - 1.0).abs() < 1e-6);
+
+        let tiny = scrollbar_metrics(100_000, 10, 0.0).unwrap();
+        assert_eq!(tiny.thumb_height, PANEL_SCROLLBAR_MIN_THUMB);
+
+        let grab = 0.1;
+        let middle = scrollbar_drag_target_line(top, 101, 0.4 + grab, grab);
+        assert_eq!(middle, 50);
+        assert_eq!(scrollbar_drag_target_line(top, 101, 2.0, grab), 100);
+    }
+}
@@ -6,6 +6,12 @@ fn handle_mouse_scroll(
 ) {
     let shift_horizontal = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
     let panel_context = gather_scroll_panels_context(&panel_query, &state);
+    // The wheel belongs to the pane under the pointer. Turns over the toolbar, the
+    // status bar or anything else outside the text panes are dropped.
+    let Some(active_panel) = panel_context.hovered_panel else {
+        mouse_wheels.clear();
+        return;
+    };
     state.clamp_horizontal_scrolls(
         panel_context.plain_panel_size,
         panel_context.processed_panel_size,
@@ -79,9 +85,6 @@ fn handle_mouse_scroll(
         }
     }
 
-    let active_panel = panel_context
-        .hovered_panel
-        .unwrap_or_else(|| state.active_panel_for_display_mode());
     state.focused_panel = active_panel;
     let mut scrolled = false;
 
@@ -101,6 +104,12 @@ fn handle_mouse_scroll(
     }
 
     match active_panel {
+        PanelKind::Plain if state.smooth_scrolling => {
+            if processed_delta_lines.abs() > f32::EPSILON {
+                scrolled |= state.scroll_plain_smoothly(processed_delta_lines, visible_lines);
+                state.clamp_cursor_to_visible_range(visible_lines);
+            }
+        }
         PanelKind::Plain => {
             if plain_delta_lines != 0 {
                 scrolled |=